@@ -0,0 +1,273 @@
+use std::path::PathBuf;
+
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+
+/// Creates Kotlin wrapper classes for Java classes generated by CXF wsdl2java
+#[derive(Parser, Debug)]
+#[clap(name = "wsdl2kotlin", version, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
+    /// Folder with generated Java classes. Kotlin files are written next to them
+    #[clap(value_parser)]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Generate Kotlin wrappers for a folder of CXF generated Java classes
+    Generate(GenerateArgs),
+    /// Generate Kotlin wrappers from a model written with `generate --emit-model json`
+    Render(RenderArgs),
+}
+
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq)]
+pub enum Source {
+    /// Java classes generated by CXF wsdl2java
+    #[default]
+    Java,
+    /// WSDL and XSD files
+    Wsdl,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum ModelFormat {
+    Json,
+}
+
+#[derive(Args, Debug, Default, Clone)]
+pub struct GenerateArgs {
+    /// Root folder of the generated Java classes, or of the WSDL and XSD files
+    #[clap(short, long, value_parser)]
+    pub input: PathBuf,
+
+    /// Root folder for the Kotlin files. Defaults to the input folder
+    #[clap(short, long, value_parser)]
+    pub output: Option<PathBuf>,
+
+    /// Read the Java classes generated by CXF, or the WSDL and XSD files directly
+    #[clap(long, value_enum, default_value_t)]
+    pub source: Source,
+
+    #[clap(flatten)]
+    pub common: CommonArgs,
+
+    /// Write the parsed and resolved classes to wsdl2kotlin-model.json in the output folder,
+    /// instead of the Kotlin files
+    #[clap(long, value_enum)]
+    pub emit_model: Option<ModelFormat>,
+}
+
+#[derive(Args, Debug, Default, Clone)]
+pub struct RenderArgs {
+    /// Model file written with `generate --emit-model json`
+    #[clap(short, long, value_parser)]
+    pub input: PathBuf,
+
+    /// Root folder for the Kotlin files
+    #[clap(short, long, value_parser)]
+    pub output: PathBuf,
+
+    #[clap(flatten)]
+    pub common: CommonArgs,
+}
+
+/// The options of both `generate` and `render`
+#[derive(Args, Debug, Default, Clone)]
+pub struct CommonArgs {
+    /// Only generate classes in this package, or its subpackages. Can be repeated
+    #[clap(long = "package-filter", value_parser)]
+    pub package_filters: Vec<String>,
+
+    /// List the files that would be written, without writing them
+    #[clap(long)]
+    pub dry_run: bool,
+
+    /// Config file. Defaults to wsdl2kotlin.toml in the working directory, if there is one
+    #[clap(short, long, value_parser)]
+    pub config: Option<PathBuf>,
+
+    /// Number of worker threads. Defaults to the number of CPUs
+    #[clap(short, long, value_parser)]
+    pub jobs: Option<usize>,
+}
+
+impl Command {
+    pub fn common(&self) -> &CommonArgs {
+        match self {
+            Command::Generate(args) => &args.common,
+            Command::Render(args) => &args.common,
+        }
+    }
+}
+
+impl GenerateArgs {
+    pub fn from_path(path: PathBuf) -> GenerateArgs {
+        GenerateArgs {
+            input: path,
+            ..Default::default()
+        }
+    }
+
+    pub fn output(&self) -> &PathBuf {
+        self.output.as_ref().unwrap_or(&self.input)
+    }
+}
+
+impl CommonArgs {
+    pub fn includes_package(&self, package: &str) -> bool {
+        self.package_filters.is_empty()
+            || self.package_filters.iter().any(|filter| {
+                package == filter
+                    || package
+                        .strip_prefix(filter.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+    }
+}
+
+pub fn parse() -> Command {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(command) => command,
+        None => match cli.path {
+            Some(path) => Command::Generate(GenerateArgs::from_path(path)),
+            None => Cli::command().error(
+                clap::ErrorKind::MissingRequiredArgument,
+                "no path given. Use `wsdl2kotlin generate --input <INPUT>`",
+            )
+            .exit(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_args() {
+        let cli = Cli::parse_from([
+            "wsdl2kotlin",
+            "generate",
+            "--input",
+            "java",
+            "--output",
+            "kotlin",
+            "--package-filter",
+            "no.eika.cars",
+            "--dry-run",
+            "--jobs",
+            "4",
+        ]);
+
+        match cli.command {
+            Some(Command::Generate(args)) => {
+                assert_eq!(args.input, PathBuf::from("java"));
+                assert_eq!(args.output(), &PathBuf::from("kotlin"));
+                assert_eq!(
+                    args.common.package_filters,
+                    vec!["no.eika.cars".to_string()]
+                );
+                assert_eq!(args.source, Source::Java);
+                assert!(args.common.dry_run);
+                assert_eq!(args.common.jobs, Some(4));
+            }
+            _ => panic!("expected generate command"),
+        }
+    }
+
+    #[test]
+    fn test_wsdl_source() {
+        let cli = Cli::parse_from([
+            "wsdl2kotlin",
+            "generate",
+            "--input",
+            "wsdl",
+            "--output",
+            "kotlin",
+            "--source",
+            "wsdl",
+        ]);
+
+        match cli.command {
+            Some(Command::Generate(args)) => assert_eq!(args.source, Source::Wsdl),
+            _ => panic!("expected generate command"),
+        }
+    }
+
+    #[test]
+    fn test_emit_model() {
+        let cli = Cli::parse_from([
+            "wsdl2kotlin",
+            "generate",
+            "--input",
+            "java",
+            "--emit-model",
+            "json",
+        ]);
+
+        match cli.command {
+            Some(Command::Generate(args)) => assert_eq!(args.emit_model, Some(ModelFormat::Json)),
+            _ => panic!("expected generate command"),
+        }
+        assert!(Cli::try_parse_from([
+            "wsdl2kotlin",
+            "generate",
+            "--input",
+            "java",
+            "--source",
+            "model",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_render_args() {
+        let cli = Cli::parse_from([
+            "wsdl2kotlin",
+            "render",
+            "--input",
+            "model.json",
+            "--output",
+            "kotlin",
+            "--package-filter",
+            "no.eika.cars",
+        ]);
+
+        match cli.command {
+            Some(Command::Render(args)) => {
+                assert_eq!(args.input, PathBuf::from("model.json"));
+                assert_eq!(args.output, PathBuf::from("kotlin"));
+                assert_eq!(
+                    args.common.package_filters,
+                    vec!["no.eika.cars".to_string()]
+                );
+            }
+            _ => panic!("expected render command"),
+        }
+    }
+
+    #[test]
+    fn test_legacy_path() {
+        let cli = Cli::parse_from(["wsdl2kotlin", "java"]);
+
+        assert!(cli.command.is_none());
+        assert_eq!(cli.path, Some(PathBuf::from("java")));
+    }
+
+    #[test]
+    fn test_includes_package() {
+        let args = CommonArgs {
+            package_filters: vec!["no.eika".to_string()],
+            ..Default::default()
+        };
+
+        assert!(args.includes_package("no.eika"));
+        assert!(args.includes_package("no.eika.cars"));
+        assert!(!args.includes_package("no.eikagruppen"));
+        assert!(!args.includes_package("com.other"));
+        assert!(CommonArgs::default().includes_package("com.other"));
+    }
+}
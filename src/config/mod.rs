@@ -0,0 +1,561 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::kotlinprinter::{self, Templates};
+
+pub const CONFIG_FILE: &str = "wsdl2kotlin.toml";
+
+/// Policies that can be adjusted per service in `wsdl2kotlin.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Appended to the Java class names to name the Kotlin classes
+    pub class_suffix: String,
+    /// Data classes with more fields than this are split into parts
+    pub fields_chunking_limit: usize,
+    /// Java types that map directly to a Kotlin type, without conversion
+    pub builtin_types: BTreeMap<String, String>,
+    /// Imports starting with one of these are not carried over to the Kotlin files
+    pub ignored_imports: Vec<String>,
+    /// Java files that are not converted
+    pub skip_files: Vec<String>,
+    /// java.time types for `XMLGregorianCalendar` fields, by the XSD type in `@XmlSchemaType`.
+    /// Calendars without a mapping are kept
+    pub calendar_types: BTreeMap<String, TimeType>,
+    /// Serialization annotations on the Kotlin classes, if any
+    pub serialization: Option<Serialization>,
+    /// Abstract classes become sealed interfaces, when all their subclasses are in the same package
+    pub sealed_hierarchies: bool,
+    /// Service methods are suspending functions, using the `*Async` methods of the port type
+    pub coroutines: bool,
+    /// Runs the blocking calls of methods without an `*Async` method in coroutine mode
+    pub coroutine_dispatcher: String,
+    /// How service methods report the SOAP faults they declare. Faults are Java exceptions without it
+    pub faults: Option<FaultHandling>,
+    /// The package of the `OperationResult` class that is generated for `faults = "result"`, and
+    /// that all services return
+    pub operation_result_package: Option<String>,
+    /// Enums get an `UNKNOWN` constant for the values they don't know, instead of throwing
+    pub unknown_enum_constants: bool,
+    /// How data classes check the XSD facets of their properties. They are not checked without it
+    pub validation: Option<Validation>,
+    /// The qualified name of the `@DslMarker` annotation that is generated for the builder DSLs of
+    /// the data classes. They get no builders without it
+    pub dsl_marker: Option<String>,
+    /// Folder with `.tmpl` files replacing the embedded templates of the same name, relative to the
+    /// config file
+    pub templates_folder: Option<PathBuf>,
+    /// The templates read from `templates_folder`, by name
+    pub templates: Templates,
+}
+
+/// The java.time types an `XMLGregorianCalendar` can be converted to
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TimeType {
+    LocalDate,
+    LocalDateTime,
+    OffsetDateTime,
+    Instant,
+}
+
+impl TimeType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeType::LocalDate => "LocalDate",
+            TimeType::LocalDateTime => "LocalDateTime",
+            TimeType::OffsetDateTime => "OffsetDateTime",
+            TimeType::Instant => "Instant",
+        }
+    }
+}
+
+/// The libraries the Kotlin classes can be annotated for
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Serialization {
+    Kotlinx,
+    Jackson,
+}
+
+impl Serialization {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Serialization::Kotlinx => "kotlinx",
+            Serialization::Jackson => "jackson",
+        }
+    }
+}
+
+/// The Kotlin API for the checked fault exceptions of the port types
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FaultHandling {
+    /// Faults are rethrown as Kotlin exceptions carrying the converted fault bean
+    Exceptions,
+    /// Service methods return an `OperationResult` with the value or the Kotlin fault exception
+    Result,
+}
+
+impl FaultHandling {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FaultHandling::Exceptions => "exceptions",
+            FaultHandling::Result => "result",
+        }
+    }
+}
+
+/// Where data classes check the XSD facets of their properties, like `maxLength` and `pattern`
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Validation {
+    /// An `init` block, so that invalid values throw an `IllegalArgumentException`
+    Init,
+    /// A `validate()` method returning the violations
+    Method,
+}
+
+impl Validation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Validation::Init => "init",
+            Validation::Method => "method",
+        }
+    }
+}
+
+/// XSD types that can be mapped in `calendar_types`
+const CALENDAR_SCHEMA_TYPES: [&str; 2] = ["date", "dateTime"];
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            class_suffix: "Kt".to_string(),
+            fields_chunking_limit: 50,
+            builtin_types: [
+                ("String", "String"),
+                ("Integer", "Int"),
+                ("int", "Int"),
+                ("Double", "Double"),
+                ("double", "Double"),
+                ("Float", "Float"),
+                ("float", "Float"),
+                ("Short", "Short"),
+                ("short", "Short"),
+                ("Boolean", "Boolean"),
+                ("boolean", "Boolean"),
+                ("Long", "Long"),
+                ("long", "Long"),
+                ("Byte", "Byte"),
+                ("byte", "Byte"),
+                ("byte[]", "ByteArray"),
+                ("Object", "Any"),
+            ]
+            .iter()
+            .map(|(java, kotlin)| (java.to_string(), kotlin.to_string()))
+            .collect(),
+            ignored_imports: vec![
+                "javax.xml.ws".to_string(),
+                "java.util".to_string(),
+                "javax.xml.bind".to_string(),
+                "javax.jws".to_string(),
+            ],
+            skip_files: vec!["package-info.java".to_string()],
+            calendar_types: BTreeMap::new(),
+            serialization: None,
+            sealed_hierarchies: false,
+            coroutines: false,
+            coroutine_dispatcher: "kotlinx.coroutines.Dispatchers.IO".to_string(),
+            faults: None,
+            operation_result_package: None,
+            unknown_enum_constants: false,
+            validation: None,
+            dsl_marker: None,
+            templates_folder: None,
+            templates: Templates::new(),
+        }
+    }
+}
+
+/// The config file as written. Missing values keep their defaults,
+/// and `builtin_types` is added to the default type map
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    class_suffix: Option<String>,
+    fields_chunking_limit: Option<usize>,
+    #[serde(default)]
+    builtin_types: BTreeMap<String, String>,
+    ignored_imports: Option<Vec<String>>,
+    skip_files: Option<Vec<String>>,
+    #[serde(default)]
+    calendar_types: BTreeMap<String, TimeType>,
+    serialization: Option<Serialization>,
+    sealed_hierarchies: Option<bool>,
+    coroutines: Option<bool>,
+    coroutine_dispatcher: Option<String>,
+    faults: Option<FaultHandling>,
+    operation_result_package: Option<String>,
+    unknown_enum_constants: Option<bool>,
+    validation: Option<Validation>,
+    dsl_marker: Option<String>,
+    templates: Option<PathBuf>,
+}
+
+impl Config {
+    /// Reads the given config file, or `wsdl2kotlin.toml` in the working directory if there is one
+    pub fn load(path: Option<&Path>) -> Result<Config> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None if Path::new(CONFIG_FILE).is_file() => PathBuf::from(CONFIG_FILE),
+            None => return Ok(Config::default()),
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|why| Error::config(why.to_string()).in_file(&path))?;
+        let mut config = Config::parse(&content).map_err(|error| error.in_file(&path))?;
+        if let Some(folder) = config.templates_folder.take() {
+            let folder = path.parent().unwrap_or(Path::new("")).join(folder);
+            config.templates = read_templates(&folder)?;
+            config.templates_folder = Some(folder);
+        }
+        Ok(config)
+    }
+
+    fn parse(content: &str) -> Result<Config> {
+        // The toml errors tell the line and column themselves
+        let file: ConfigFile =
+            toml::from_str(content).map_err(|why| Error::config(why.to_string()))?;
+
+        let mut config = Config::default();
+        if let Some(class_suffix) = file.class_suffix {
+            config.class_suffix = class_suffix;
+        }
+        if let Some(fields_chunking_limit) = file.fields_chunking_limit {
+            config.fields_chunking_limit = fields_chunking_limit;
+        }
+        config.builtin_types.extend(file.builtin_types);
+        if let Some(ignored_imports) = file.ignored_imports {
+            config.ignored_imports = ignored_imports;
+        }
+        if let Some(skip_files) = file.skip_files {
+            config.skip_files = skip_files;
+        }
+        config.calendar_types = file.calendar_types;
+        config.serialization = file.serialization;
+        if let Some(sealed_hierarchies) = file.sealed_hierarchies {
+            config.sealed_hierarchies = sealed_hierarchies;
+        }
+        if let Some(coroutines) = file.coroutines {
+            config.coroutines = coroutines;
+        }
+        if let Some(coroutine_dispatcher) = file.coroutine_dispatcher {
+            config.coroutine_dispatcher = coroutine_dispatcher;
+        }
+        config.faults = file.faults;
+        config.operation_result_package = file.operation_result_package;
+        if let Some(unknown_enum_constants) = file.unknown_enum_constants {
+            config.unknown_enum_constants = unknown_enum_constants;
+        }
+        config.validation = file.validation;
+        config.dsl_marker = file.dsl_marker;
+        config.templates_folder = file.templates;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(Error::config(message));
+
+        if self.class_suffix.is_empty() || !self.class_suffix.chars().all(is_identifier_char) {
+            return invalid(format!(
+                "class_suffix '{}' must be a non-empty Kotlin identifier, or the Kotlin classes would clash with the Java classes",
+                self.class_suffix
+            ));
+        }
+        if self.fields_chunking_limit == 0 {
+            return invalid("fields_chunking_limit must be at least 1".to_string());
+        }
+        for (java, kotlin) in &self.builtin_types {
+            if java.is_empty() || java.contains(char::is_whitespace) {
+                return invalid(format!("builtin_types has an invalid Java type '{}'", java));
+            }
+            if kotlin.is_empty() || kotlin.contains(char::is_whitespace) {
+                return invalid(format!(
+                    "builtin_types maps '{}' to an invalid Kotlin type '{}'",
+                    java, kotlin
+                ));
+            }
+        }
+        if let Some(import) = self.ignored_imports.iter().find(|import| {
+            import.is_empty() || !import.chars().all(|c| is_identifier_char(c) || c == '.')
+        }) {
+            return invalid(format!(
+                "ignored_imports has an invalid package or class '{}'",
+                import
+            ));
+        }
+        if let Some(file) = self
+            .skip_files
+            .iter()
+            .find(|file| file.is_empty() || file.contains(['/', '\\']))
+        {
+            return invalid(format!(
+                "skip_files must contain file names without folders, got '{}'",
+                file
+            ));
+        }
+        if let Some(schema_type) = self
+            .calendar_types
+            .keys()
+            .find(|schema_type| !CALENDAR_SCHEMA_TYPES.contains(&schema_type.as_str()))
+        {
+            return invalid(format!(
+                "calendar_types can map {}, got '{}'",
+                CALENDAR_SCHEMA_TYPES.join(" and "),
+                schema_type
+            ));
+        }
+        if self.coroutine_dispatcher.trim().is_empty() {
+            return invalid("coroutine_dispatcher must be a Kotlin expression".to_string());
+        }
+        match (&self.faults, &self.operation_result_package) {
+            (Some(FaultHandling::Result), None) => {
+                return invalid(
+                    "faults = \"result\" needs operation_result_package, the package of the generated OperationResult class".to_string(),
+                );
+            }
+            (_, Some(package)) if !is_qualified_name(package) => {
+                return invalid(format!(
+                    "operation_result_package must be a Kotlin package, got '{}'",
+                    package
+                ));
+            }
+            _ => {}
+        }
+        if let Some(dsl_marker) = self
+            .dsl_marker
+            .as_ref()
+            .filter(|dsl_marker| !is_qualified_name(dsl_marker))
+        {
+            return invalid(format!(
+                "dsl_marker must be a qualified Kotlin class name, got '{}'",
+                dsl_marker
+            ));
+        }
+        Ok(())
+    }
+
+    /// The java.time type for an `XMLGregorianCalendar` field. Without `@XmlSchemaType`
+    /// the field is a `dateTime`, as in JAXB
+    pub fn calendar_type(&self, schema_type: Option<&str>) -> Option<TimeType> {
+        self.calendar_types
+            .get(schema_type.unwrap_or("dateTime"))
+            .copied()
+    }
+
+    pub fn is_ignored_import(&self, import: &str) -> bool {
+        self.ignored_imports
+            .iter()
+            .any(|ignored| import.starts_with(ignored.as_str()))
+    }
+}
+
+/// The `.tmpl` files in the templates folder, by the name of the template they replace. Other files
+/// are ignored
+fn read_templates(folder: &Path) -> Result<Templates> {
+    let read_error = |why: std::io::Error| Error::config(why.to_string()).in_file(folder);
+    let mut templates = Templates::new();
+    for entry in fs::read_dir(folder).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some("tmpl") {
+            continue;
+        }
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if !kotlinprinter::template_names().any(|template_name| template_name == name) {
+            return Err(Error::config(format!(
+                "'{}' is not a template, expected one of {}",
+                name,
+                kotlinprinter::template_names()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+            .in_file(&path));
+        }
+        let template = fs::read_to_string(&path)
+            .map_err(|why| Error::config(why.to_string()).in_file(&path))?;
+        templates.insert(name.to_string(), template);
+    }
+    // The partials first, so that an error in one is not reported for the templates using it
+    for name in kotlinprinter::template_names() {
+        if templates.contains_key(name) {
+            kotlinprinter::check_template(&templates, name)
+                .map_err(|error| error.in_file(&folder.join(format!("{}.tmpl", name))))?;
+        }
+    }
+    Ok(templates)
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// A name of Kotlin identifiers separated by dots, like a package or a qualified class name
+fn is_qualified_name(name: &str) -> bool {
+    name.split('.')
+        .all(|part| !part.is_empty() && part.chars().all(is_identifier_char))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+class_suffix = "Dto"
+fields_chunking_limit = 20
+skip_files = ["package-info.java"]
+serialization = "jackson"
+sealed_hierarchies = true
+coroutines = true
+faults = "result"
+operation_result_package = "no.eika.cars"
+unknown_enum_constants = true
+validation = "method"
+dsl_marker = "no.eika.cars.CarsDsl"
+
+[builtin_types]
+BigDecimal = "java.math.BigDecimal"
+Object = "kotlin.Any"
+
+[calendar_types]
+date = "LocalDate"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.class_suffix, "Dto");
+        assert_eq!(config.fields_chunking_limit, 20);
+        assert_eq!(config.skip_files, vec!["package-info.java".to_string()]);
+        assert_eq!(config.ignored_imports, Config::default().ignored_imports);
+        assert_eq!(config.builtin_types["Object"], "kotlin.Any");
+        assert_eq!(config.builtin_types["BigDecimal"], "java.math.BigDecimal");
+        assert_eq!(config.builtin_types["String"], "String");
+        assert_eq!(
+            config.calendar_type(Some("date")),
+            Some(TimeType::LocalDate)
+        );
+        assert_eq!(config.calendar_type(None), None);
+        assert_eq!(config.serialization, Some(Serialization::Jackson));
+        assert!(config.sealed_hierarchies);
+        assert!(config.coroutines);
+        assert_eq!(config.faults, Some(FaultHandling::Result));
+        assert_eq!(
+            config.operation_result_package.as_deref(),
+            Some("no.eika.cars")
+        );
+        assert!(config.unknown_enum_constants);
+        assert_eq!(config.validation, Some(Validation::Method));
+        assert_eq!(config.dsl_marker.as_deref(), Some("no.eika.cars.CarsDsl"));
+        assert_eq!(
+            config.coroutine_dispatcher,
+            "kotlinx.coroutines.Dispatchers.IO"
+        );
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let error = |content: &str| Config::parse(content).unwrap_err().to_string();
+
+        assert_eq!(
+            error("class_suffix = \"\""),
+            "config failed: class_suffix '' must be a non-empty Kotlin identifier, or the Kotlin classes would clash with the Java classes"
+        );
+        assert_eq!(
+            error("fields_chunking_limit = 0"),
+            "config failed: fields_chunking_limit must be at least 1"
+        );
+        assert_eq!(
+            error("skip_files = [\"generated/ObjectFactory.java\"]"),
+            "config failed: skip_files must contain file names without folders, got 'generated/ObjectFactory.java'"
+        );
+        assert_eq!(
+            error("[builtin_types]\nchar = \"\""),
+            "config failed: builtin_types maps 'char' to an invalid Kotlin type ''"
+        );
+        assert_eq!(
+            error("[calendar_types]\ngYear = \"LocalDate\""),
+            "config failed: calendar_types can map date and dateTime, got 'gYear'"
+        );
+        assert!(error("[calendar_types]\ndate = \"Date\"").starts_with(
+            "config failed: unknown variant `Date`, expected one of `LocalDate`, `LocalDateTime`, `OffsetDateTime`, `Instant`"
+        ));
+        assert_eq!(
+            error("coroutine_dispatcher = \" \""),
+            "config failed: coroutine_dispatcher must be a Kotlin expression"
+        );
+        assert_eq!(
+            error("dsl_marker = \"no.eika..CarsDsl\""),
+            "config failed: dsl_marker must be a qualified Kotlin class name, got 'no.eika..CarsDsl'"
+        );
+        assert_eq!(
+            error("faults = \"result\""),
+            "config failed: faults = \"result\" needs operation_result_package, the package of the generated OperationResult class"
+        );
+        assert_eq!(
+            error("operation_result_package = \"no.eika.\""),
+            "config failed: operation_result_package must be a Kotlin package, got 'no.eika.'"
+        );
+        assert!(error("serialization = \"gson\"")
+            .starts_with("config failed: unknown variant `gson`, expected `kotlinx` or `jackson`"));
+        assert!(error("faults = \"ignore\"").starts_with(
+            "config failed: unknown variant `ignore`, expected `exceptions` or `result`"
+        ));
+        assert!(error("class_sufix = \"Dto\"")
+            .starts_with("config failed: unknown field `class_sufix`"));
+        assert!(error("fields_chunking_limit = \"many\"").ends_with("at line 1 column 25"));
+    }
+
+    #[test]
+    fn test_load_templates() {
+        let config = Config::load(Some(Path::new("test-resources/templates.toml"))).unwrap();
+
+        assert_eq!(
+            config.templates_folder,
+            Some(PathBuf::from("test-resources/templates"))
+        );
+        assert_eq!(
+            config.templates.keys().collect::<Vec<_>>(),
+            vec!["data_class"]
+        );
+        assert!(config.templates["data_class"].contains("@no.eika.Audited"));
+
+        let folder =
+            std::env::temp_dir().join(format!("wsdl2kotlin-templates-{}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("README.md"), "Not a template").unwrap();
+        fs::write(folder.join("enum.tmpl"), "{{if .package}}").unwrap();
+        let error = read_templates(&folder).unwrap_err();
+        assert_eq!(error.path, Some(folder.join("enum.tmpl")));
+        assert!(error
+            .to_string()
+            .contains("config failed: template enum failed: "));
+
+        fs::remove_file(folder.join("enum.tmpl")).unwrap();
+        fs::write(folder.join("record.tmpl"), "").unwrap();
+        assert!(read_templates(&folder)
+            .unwrap_err()
+            .to_string()
+            .contains("'record' is not a template, expected one of kdoc, validation, builder, choices, serialization, datatype_factory, data_class"));
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
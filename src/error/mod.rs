@@ -0,0 +1,136 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The step of the pipeline that failed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stage {
+    Config,
+    Read,
+    Parse,
+    Convert,
+    Write,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::Config => "config",
+            Stage::Read => "read",
+            Stage::Parse => "parse",
+            Stage::Convert => "convert",
+            Stage::Write => "write",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 1-based line and column in the Java source
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<tree_sitter::Point> for Position {
+    fn from(point: tree_sitter::Point) -> Self {
+        Position {
+            line: point.row + 1,
+            column: point.column + 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub stage: Stage,
+    pub path: Option<PathBuf>,
+    pub position: Option<Position>,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(stage: Stage, message: impl Into<String>) -> Error {
+        Error {
+            stage,
+            path: None,
+            position: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn config(message: impl Into<String>) -> Error {
+        Error::new(Stage::Config, message)
+    }
+
+    pub fn read(message: impl Into<String>) -> Error {
+        Error::new(Stage::Read, message)
+    }
+
+    pub fn parse(message: impl Into<String>, position: Position) -> Error {
+        Error::new(Stage::Parse, message).at(position)
+    }
+
+    pub fn convert(message: impl Into<String>) -> Error {
+        Error::new(Stage::Convert, message)
+    }
+
+    pub fn write(message: impl Into<String>) -> Error {
+        Error::new(Stage::Write, message)
+    }
+
+    pub fn at(mut self, position: Position) -> Error {
+        self.position = Some(position);
+        self
+    }
+
+    /// Sets the file the error belongs to, unless a nested step already did
+    pub fn in_file(mut self, path: &Path) -> Error {
+        if self.path.is_none() {
+            self.path = Some(path.to_path_buf());
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}", path.display())?;
+            if let Some(position) = &self.position {
+                write!(f, ":{}:{}", position.line, position.column)?;
+            }
+            write!(f, ": ")?;
+        } else if let Some(position) = &self.position {
+            write!(f, "{}:{}: ", position.line, position.column)?;
+        }
+        write!(f, "{} failed: {}", self.stage, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = Error::parse(
+            "no package declaration",
+            Position { line: 3, column: 5 },
+        )
+        .in_file(Path::new("dataclasses/Car.java"))
+        .in_file(Path::new("dataclasses/Other.java"));
+
+        assert_eq!(
+            error.to_string(),
+            "dataclasses/Car.java:3:5: parse failed: no package declaration"
+        );
+        assert_eq!(
+            Error::convert("unknown builtin type 'char'").to_string(),
+            "convert failed: unknown builtin type 'char'"
+        );
+    }
+}
@@ -1,18 +1,41 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-pub fn find_classes(generated_dir: &Path) -> Vec<PathBuf> {
-    walk(generated_dir, &is_class)
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::xsdparser::is_schema;
+
+pub fn find_classes(generated_dir: &Path, config: &Config) -> Result<Vec<PathBuf>> {
+    let mut classes = walk(generated_dir, &|path| is_class(path, &config.skip_files))?;
+    classes.sort();
+    Ok(classes)
+}
+
+pub fn find_schemas(schema_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut schemas = walk(schema_dir, &|path| {
+        Some(path).filter(|path| is_schema(path))
+    })?;
+    schemas.sort();
+    Ok(schemas)
+}
+
+/// The `ObjectFactory.java` files, also when they are in `skip_files`
+pub fn find_object_factories(generated_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut factories = walk(generated_dir, &|path| {
+        Some(path).filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name == "ObjectFactory.java")
+        })
+    })?;
+    factories.sort();
+    Ok(factories)
 }
 
-fn is_class(path: &Path) -> Option<&Path> {
-    let files_to_skip = vec![
-        "package-info.java",
-        "ObjectFactory.java",
-    ];
+fn is_class<'a>(path: &'a Path, files_to_skip: &[String]) -> Option<&'a Path> {
+    let file_name = path.file_name()?.to_str()?;
 
     if path.extension().map_or(false, |e| e == "java")
-        && !files_to_skip.contains(&path.file_name()?.to_str()?)
+        && !files_to_skip.iter().any(|file| file == file_name)
     {
         Some(path)
     } else {
@@ -20,26 +43,28 @@ fn is_class(path: &Path) -> Option<&Path> {
     }
 }
 
-fn walk(path: &Path, is_class: &dyn Fn(&Path) -> Option<&Path>) -> Vec<PathBuf> {
+fn walk(path: &Path, is_class: &dyn Fn(&Path) -> Option<&Path>) -> Result<Vec<PathBuf>> {
+    let read_error = |why: std::io::Error| Error::read(why.to_string()).in_file(path);
+
     let mut classes: Vec<PathBuf> = vec![];
     if path.is_dir() {
-        for entry in fs::read_dir(path).unwrap() {
-            let entry = entry.unwrap();
+        for entry in fs::read_dir(path).map_err(read_error)? {
+            let entry = entry.map_err(read_error)?;
             let path = entry.path();
             if path.is_dir() {
-                classes.extend(walk(&path, is_class));
+                classes.extend(walk(&path, is_class)?);
             } else {
                 if is_class(&path).is_some() {
                     classes.push(path);
                 }
             }
         }
-    } else {
-        if is_class(path).is_some() {
-            classes.push(path.to_path_buf())
-        }
+    } else if is_class(path).is_some() {
+        classes.push(path.to_path_buf())
+    } else if !path.exists() {
+        return Err(Error::read("no such file or directory").in_file(path));
     }
-    classes
+    Ok(classes)
 }
 
 //TODO skip hidden folders?
@@ -1,5 +1,10 @@
+use serde::{Deserialize, Serialize};
 use tree_sitter::{Language, Node, Parser, Query, QueryCursor, QueryMatch, Tree};
 
+use crate::config::Config;
+use crate::error::{Error, Position, Result, Stage};
+use crate::xsdparser::{facets, java_class_name};
+
 struct JavaFile {
     content: String,
     tree: Tree,
@@ -27,18 +32,19 @@ impl<'a> JavaQuery<'a> {
 }
 
 impl<'a> JavaFile {
-    fn new(content: &str) -> JavaFile {
+    fn new(content: &str) -> Result<JavaFile> {
         let mut parser = Parser::new();
-        match parser.set_language(lang()) {
-            Ok(_) => (),
-            Err(why) => panic!("{:?}", why),
-        }
-        let tree = parser.parse(content, None).unwrap();
-
-        JavaFile {
+        parser
+            .set_language(lang())
+            .map_err(|why| Error::new(Stage::Parse, format!("{:?}", why)))?;
+        let tree = parser
+            .parse(content, None)
+            .ok_or_else(|| Error::new(Stage::Parse, "tree-sitter returned no syntax tree"))?;
+
+        Ok(JavaFile {
             content: content.to_string(),
             tree,
-        }
+        })
     }
 
     fn new_query(&'a self, query: &'a str) -> JavaQuery {
@@ -49,20 +55,197 @@ impl<'a> JavaFile {
         }
     }
 
-    fn get_functions(&'a self) -> Vec<Function> {
+    fn get_functions(
+        &'a self,
+        class_package: &str,
+        imports: &Vec<Import>,
+        config: &Config,
+    ) -> Result<Vec<Function>> {
         let mut query =
             self.new_query("((method_declaration type: *@type name: *@name parameters: *@pars))");
         let matches = query.matches();
 
         matches
-            .map(|mat| Function {
-                name: mat.second_capture(self),
-                return_type: mat.first_capture(self),
-                arguments: self.parse_arguments(mat.captures[2].node),
+            .map(|mat| {
+                let name = mat.second_capture(self);
+                let type_node = mat.captures[0].node;
+                let return_type = if type_node.kind() == "void_type" {
+                    None
+                } else {
+                    let (inner_type, generic_type) =
+                        self.split_generic(type_node).ok_or_else(|| {
+                            Error::parse(
+                                format!("unsupported generic return type of '{}'", name),
+                                type_node.start_position().into(),
+                            )
+                        })?;
+                    let builtin = config.builtin_types.contains_key(inner_type);
+                    let package = type_package(builtin, class_package, inner_type, imports);
+                    Some(ReturnType {
+                        r#type: Type {
+                            stdlib: package.as_ref().is_none_or(|p| p.starts_with("java")),
+                            arguments: vec![],
+                            package,
+                            class: inner_type.to_string(),
+                        },
+                        nullable: !is_primitive(inner_type),
+                        generic_type,
+                        builtin,
+                    })
+                };
+                let method = mat.captures[1].node.parent();
+                Ok(Function {
+                    arguments: self.parse_arguments(mat.captures[2].node),
+                    faults: method.map_or(vec![], |method| self.throws(method)),
+                    fault_classes: vec![],
+                    doc: method.map_or(vec![], |method| self.javadoc(method)),
+                    name,
+                    return_type,
+                })
+            })
+            .collect()
+    }
+
+    /// The exceptions in the `throws` clause of a method
+    fn throws(&'a self, method_node: Node) -> Vec<String> {
+        let mut cursor = method_node.walk();
+        let throws = method_node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "throws");
+        let mut cursor = method_node.walk();
+        throws.map_or(vec![], |throws| {
+            throws
+                .named_children(&mut cursor)
+                .map(|exception| {
+                    exception
+                        .utf8_text(self.content.as_bytes())
+                        .unwrap()
+                        .to_string()
+                })
+                .collect()
+        })
+    }
+
+    fn get_element_decls(&'a self) -> Vec<ElementDecl> {
+        let mut query = self.new_query(
+            "(program (class_declaration body: (class_body (method_declaration (modifiers (annotation name: * @ann) @annotation) type: * @type name: * @name))))",
+        );
+        let matches = query.matches();
+        matches
+            .filter_map(|mat| {
+                // The captures are in the order of the nodes, so the annotation comes before its name
+                let capture = |index: u32| {
+                    mat.captures
+                        .iter()
+                        .find(|capture| capture.index == index)
+                        .map(|capture| capture.node)
+                };
+                let text =
+                    |node: Node| node.utf8_text(self.content.as_bytes()).unwrap().to_string();
+                if text(capture(0)?) != "XmlElementDecl" {
+                    return None;
+                }
+                let values = self.element_values(capture(1)?);
+                let value = |key: &str| {
+                    values
+                        .iter()
+                        .find(|(name, _)| name == key)
+                        .map(|(_, value)| value.clone())
+                };
+                Some(ElementDecl {
+                    method: text(capture(3)?),
+                    name: value("name").unwrap_or_default(),
+                    namespace: value("namespace").unwrap_or_default(),
+                    scope: value("scope").map(|scope| scope.trim_end_matches(".class").to_string()),
+                    r#type: self
+                        .split_generic(capture(2)?)
+                        .map_or(String::new(), |(inner_type, _)| inner_type.to_string()),
+                })
             })
             .collect()
     }
 
+    /// The return type of `getFaultInfo()`, the fault bean of a CXF fault exception
+    fn get_fault_info(&'a self) -> Option<String> {
+        let mut query = self.new_query(
+            "(program (class_declaration body: (class_body (method_declaration type: * @type name: * @name))))",
+        );
+        let mut matches = query.matches();
+        matches
+            .find(|mat| mat.second_capture(self) == "getFaultInfo")
+            .map(|mat| mat.first_capture(self))
+    }
+
+    /// The type argument and the generic type of `List<Car>`, or the type and None when not generic
+    fn split_generic(&'a self, type_node: Node) -> Option<(&'a str, Option<String>)> {
+        if type_node.kind() == "generic_type" {
+            let inner_type = type_node
+                .child(1)
+                .and_then(|type_arguments| type_arguments.child(1))?
+                .utf8_text(self.content.as_bytes())
+                .unwrap();
+            let generic_type = type_node
+                .child(0)?
+                .utf8_text(self.content.as_bytes())
+                .unwrap()
+                .to_string();
+            Some((inner_type, Some(generic_type)))
+        } else {
+            Some((type_node.utf8_text(self.content.as_bytes()).unwrap(), None))
+        }
+    }
+
+    /// The type of `type_node`, with the type arguments of a generic type at any depth
+    fn parse_type(
+        &'a self,
+        type_node: Node,
+        class_package: &str,
+        imports: &Vec<Import>,
+        config: &Config,
+    ) -> Type {
+        if type_node.kind() == "generic_type" {
+            let mut cursor = type_node.walk();
+            let arguments = type_node
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "type_arguments")
+                .map_or(vec![], |type_arguments| {
+                    let mut cursor = type_arguments.walk();
+                    type_arguments
+                        .named_children(&mut cursor)
+                        .map(|argument| self.parse_type(argument, class_package, imports, config))
+                        .collect()
+                });
+            // Generic types like List and JAXBElement are from the standard library, whose
+            // imports are usually ignored
+            if let Some(generic_type) = type_node.child(0) {
+                let class = generic_type
+                    .utf8_text(self.content.as_bytes())
+                    .unwrap()
+                    .to_string();
+                return Type {
+                    package: package_import(&class, imports),
+                    class,
+                    stdlib: true,
+                    arguments,
+                };
+            }
+        }
+        let class = type_node
+            .utf8_text(self.content.as_bytes())
+            .unwrap()
+            .to_string();
+        let builtin = config.builtin_types.contains_key(&class);
+        let package = type_package(builtin, class_package, &class, imports);
+        Type {
+            stdlib: package
+                .as_ref()
+                .is_none_or(|package| package.starts_with("java")),
+            package,
+            class,
+            arguments: vec![],
+        }
+    }
+
     fn get_superclass(&'a self) -> Option<String> {
         let mut query = self
             .new_query("((class_declaration superclass: (superclass (type_identifier) @type)))");
@@ -153,15 +336,38 @@ impl<'a> JavaFile {
         let matches = query.matches_node(arguments_node);
 
         matches
-            .map(|mat| Argument {
-                r#type: mat.second_capture(self),
-                name: mat.third_capture(self),
-                nullable: self.is_header(mat.captures[0].node),
+            .map(|mat| {
+                let web_param = self.element_values(mat.captures[0].node);
+                let value = |key: &str| {
+                    web_param
+                        .iter()
+                        .find(|(name, _)| name == key)
+                        .map(|(_, value)| value.clone())
+                };
+                let header = value("header").as_deref() == Some("true");
+                let mode = match value("mode")
+                    .as_deref()
+                    .and_then(|mode| mode.rsplit('.').next())
+                {
+                    Some("OUT") => Mode::Out,
+                    Some("INOUT") => Mode::InOut,
+                    _ => Mode::In,
+                };
+                Argument {
+                    r#type: mat.second_capture(self),
+                    name: mat.third_capture(self),
+                    nullable: header,
+                    part_name: value("partName"),
+                    xml_name: value("name"),
+                    target_namespace: value("targetNamespace"),
+                    header,
+                    mode,
+                }
             })
             .collect()
     }
 
-    fn get_imports(&'a self) -> Vec<Import> {
+    fn get_imports(&'a self, config: &Config) -> Vec<Import> {
         let mut query = self.new_query("((import_declaration) @import)");
         let matches = query.matches();
 
@@ -172,14 +378,9 @@ impl<'a> JavaFile {
                     .utf8_text(self.content.as_bytes())
                     .unwrap()
             })
-            .filter(|import_declaration| {
-                !import_declaration.starts_with("import javax.xml.ws")
-                    && !import_declaration.starts_with("import java.util")
-                    && !import_declaration.starts_with("import javax.xml.bind")
-                    && !import_declaration.starts_with("import javax.jws")
-            })
             .map(|import_declaration| import_declaration.trim_end_matches(";"))
             .map(|import_declaration| import_declaration.trim_start_matches("import "))
+            .filter(|import_declaration| !config.is_ignored_import(import_declaration))
             .map(|import_declaration| {
                 let parts: Vec<&str> = import_declaration.rsplitn(2, ".").collect();
                 Import {
@@ -201,50 +402,107 @@ impl<'a> JavaFile {
         is_abstract
     }
 
-    fn is_header(&'a self, annotations_node: Node) -> bool {
+    /// The keys and values of the annotations, without quotes
+    fn element_values(&'a self, annotations_node: Node) -> Vec<(String, String)> {
         let mut query = self.new_query("((element_value_pair key: *@key value: *@value))");
-        let mut matches = query.matches_node(annotations_node);
+        let matches = query.matches_node(annotations_node);
 
-        matches.any(|mat| {
-            let key = mat.first_capture(self);
-            let value = mat.second_capture(self);
-            key == "header" && value == "true"
-        })
+        matches
+            .map(|mat| {
+                (
+                    mat.first_capture(self),
+                    mat.second_capture(self).replace('"', ""),
+                )
+            })
+            .collect()
     }
 
-    fn get_enum_constants(&'a self) -> Vec<String> {
-        let mut query = self.new_query("((enum_constant name: *@const))");
-        let matches = query.matches();
+    fn get_enum_constants(&'a self) -> Vec<EnumConstant> {
+        let mut query = self.new_query("((enum_constant) @constant)");
+        let constant_nodes: Vec<Node> = query.matches().map(|mat| mat.captures[0].node).collect();
+
+        constant_nodes
+            .into_iter()
+            .filter_map(|constant| {
+                let name = constant
+                    .child_by_field_name("name")?
+                    .utf8_text(self.content.as_bytes())
+                    .unwrap()
+                    .to_string();
+                let mut query = self.new_query(
+                    "((annotation name: * @ann arguments: (annotation_argument_list (string_literal) @value)))",
+                );
+                let xml_value = query
+                    .matches_node(constant)
+                    .find(|mat| mat.first_capture(self) == "XmlEnumValue")
+                    .map(|mat| mat.second_capture(self).replace('"', ""));
+                Some(EnumConstant {
+                    xml_value: xml_value.unwrap_or_else(|| name.clone()),
+                    name,
+                    doc: self.javadoc(constant),
+                })
+            })
+            .collect()
+    }
 
-        matches.map(|mat| mat.first_capture(self)).collect()
+    /// The Javadoc right before a declaration, as KDoc lines
+    fn javadoc(&'a self, declaration_node: Node) -> Vec<String> {
+        declaration_node
+            .prev_sibling()
+            .filter(|comment| comment.kind() == "comment")
+            .map(|comment| comment.utf8_text(self.content.as_bytes()).unwrap())
+            .filter(|comment| comment.starts_with("/**"))
+            .map_or(vec![], javadoc_lines)
     }
 
-    fn get_class_name(&'a self, class_node: Node) -> String {
+    fn get_class_name(&'a self, class_node: Node) -> Result<String> {
         let mut class_name_query = self.new_query("((class_declaration name: * @class))");
         let mut matches = class_name_query.matches_node(class_node);
-        matches.next().unwrap().first_capture(self)
+        matches
+            .next()
+            .map(|mat| mat.first_capture(self))
+            .ok_or_else(|| Error::parse("class without name", class_node.start_position().into()))
     }
 
-    fn get_inner_classes(&'a self, class_package: &str, imports: &Vec<Import>) -> Vec<InnerClass> {
+    fn get_inner_classes(
+        &'a self,
+        class_name: &str,
+        class_package: &str,
+        imports: &Vec<Import>,
+        config: &Config,
+    ) -> Result<Vec<InnerClass>> {
         let mut query = self.new_query(
             "(program (class_declaration body: (class_body (class_declaration) @inner_class)))",
         );
         let matches = query.matches();
         matches
             .map(|mat| mat.captures[0].node)
-            .map(|inner_class_node| InnerClass {
-                name: self.get_class_name(inner_class_node),
-                fields: self.get_fields(Some(inner_class_node), class_package, imports),
+            .map(|inner_class_node| {
+                Ok(InnerClass {
+                    name: self.get_class_name(inner_class_node)?,
+                    doc: self.javadoc(inner_class_node),
+                    fields: self.get_fields(
+                        Some(inner_class_node),
+                        &format!("{}{}", class_name, self.get_class_name(inner_class_node)?),
+                        class_package,
+                        imports,
+                        config,
+                    )?,
+                })
             })
             .collect()
     }
 
+    /// The fields of the class, or of the inner class. `class_name` names the sealed types of
+    /// choice fields, and includes the outer class for inner classes
     fn get_fields(
         &'a self,
         inner_class_node: Option<Node>,
+        class_name: &str,
         class_package: &str,
         imports: &Vec<Import>,
-    ) -> Vec<Field> {
+        config: &Config,
+    ) -> Result<Vec<Field>> {
         let mut query: JavaQuery;
         let matches = if inner_class_node.is_some() {
             query = self
@@ -267,7 +525,7 @@ impl<'a> JavaFile {
             let captures = query
                 .matches_node(*node)
                 .next()
-                .unwrap()
+                .ok_or_else(|| Error::parse("field without type or name", node.start_position().into()))?
                 .captures;
 
             let type_node = captures[0].node;
@@ -277,59 +535,112 @@ impl<'a> JavaFile {
                 .unwrap()
                 .to_string();
 
-            let inner_type: &str;
-            let generic_type: Option<String>;
-
-            if type_node.kind() == "generic_type" {
-                inner_type = type_node
-                    .child(1)
-                    .unwrap()
-                    .child(1)
-                    .unwrap()
-                    .utf8_text(self.content.as_bytes())
-                    .unwrap();
-                generic_type = Some(
-                    type_node
-                        .child(0)
-                        .unwrap()
-                        .utf8_text(self.content.as_bytes())
-                        .unwrap()
-                        .to_string(),
-                );
-            } else {
-                inner_type = type_node.utf8_text(self.content.as_bytes()).unwrap();
-                generic_type = None;
-            }
-
-            let primitive = inner_type.chars().next().unwrap().is_lowercase() && !inner_type.ends_with("[]");
+            let java_type = self.parse_type(type_node, class_package, imports, config);
+            let generic_type = (!java_type.arguments.is_empty()).then(|| java_type.class.clone());
+            let r#type = java_type.arguments.first().cloned().unwrap_or_else(|| java_type.clone());
 
-            let is_builtin = [
-                "Long", "long", "Double", "double", "Float", "float", "String", "Integer", "int",
-                "Short", "short", "Boolean", "boolean", "byte[]", "Object",
-            ]
-            .iter()
-            .any(|builtin| builtin == &inner_type);
+            let primitive = is_primitive(&r#type.class);
 
-            let package = type_package(is_builtin, class_package, inner_type, imports);
-            let is_stdlib = package.as_ref().map_or(true, |package| package.starts_with("java"));
+            let is_builtin = config.builtin_types.contains_key(&r#type.class);
+            let choice =
+                self.choice_type(*node, class_name, &variable_name, class_package, imports, config);
 
-            Field {
+            Ok(Field {
                 name: variable_name,
                 xml_name: self.xml_name(*node),
                 package: class_package.to_owned(),
-                r#type: Type {
-                    package,
-                    class: inner_type.to_string(),
-                    stdlib: is_stdlib,
-                },
+                r#type,
                 generic_type,
+                type_arguments: java_type.arguments,
                 builtin: is_builtin,
                 nullable: !primitive && self.is_nullable(*node),
-            }
+                binding: self.binding(*node),
+                schema_type: self.annotation_value(*node, &["XmlSchemaType"], "name"),
+                factory_method: None,
+                choice,
+                doc: self.javadoc(*node),
+                facets: Facets::default(),
+            })
         })
         .collect()
     }
 
+    /// The elements of an `xs:choice` field, annotated `@XmlElements` or `@XmlElementRefs`
+    fn choice_type(
+        &'a self,
+        field_node: Node,
+        class_name: &str,
+        field_name: &str,
+        class_package: &str,
+        imports: &Vec<Import>,
+        config: &Config,
+    ) -> Option<ChoiceType> {
+        let mut query = self.new_query(
+            "((field_declaration (modifiers (annotation name: * @ann arguments: (annotation_argument_list (element_value_array_initializer (annotation arguments: * @arg)))))))",
+        );
+        let choices: Vec<Choice> = query
+            .matches_node(field_node)
+            .filter(|mat| {
+                ["XmlElements", "XmlElementRefs"].contains(&mat.first_capture(self).as_str())
+            })
+            .map(|mat| {
+                let values = self.element_values(mat.captures[1].node);
+                let value = |key: &str| {
+                    values
+                        .iter()
+                        .find(|(name, _)| name == key)
+                        .map(|(_, value)| value.trim_end_matches(".class").to_string())
+                };
+                // An @XmlElementRef without a type also refers to a JAXBElement
+                let class = value("type").unwrap_or_else(|| "JAXBElement".to_string());
+                let builtin = config.builtin_types.contains_key(&class);
+                let package = type_package(builtin, class_package, &class, imports);
+                Choice {
+                    xml_name: value("name").unwrap_or_default(),
+                    jaxb_element: class == "JAXBElement",
+                    r#type: Type {
+                        stdlib: package
+                            .as_ref()
+                            .is_none_or(|package| package.starts_with("java")),
+                        package,
+                        class,
+                        arguments: vec![],
+                    },
+                    builtin,
+                    factory_method: None,
+                }
+            })
+            .collect();
+        if choices.is_empty() {
+            return None;
+        }
+        Some(ChoiceType {
+            name: format!("{}{}", class_name, crate::title(field_name)),
+            choices,
+        })
+    }
+
+    /// How the field is bound to XML, from its annotations
+    fn binding(&'a self, field_node: Node) -> Binding {
+        let mut query = self.new_query(
+            "((field_declaration (modifiers [(annotation name: * @ann) (marker_annotation name: * @ann)])))",
+        );
+        let annotations: Vec<String> = query
+            .matches_node(field_node)
+            .map(|mat| mat.first_capture(self))
+            .collect();
+        let annotated = |name: &str| annotations.iter().any(|annotation| annotation == name);
+        if annotated("XmlAttribute") {
+            Binding::Attribute
+        } else if annotated("XmlValue") {
+            Binding::Value
+        } else if annotated("XmlAnyElement") || annotated("XmlAnyAttribute") {
+            Binding::Any
+        } else {
+            Binding::Element
+        }
+    }
+
     fn is_nullable(&'a self, field_node: Node) -> bool {
         let mut query = self.new_query(
             "((field_declaration (modifiers (annotation name: * @ann arguments: * @arg))))",
@@ -337,7 +648,9 @@ impl<'a> JavaFile {
         let matches = query.matches_node(field_node);
 
         let xml_element_arguments: Option<Node> = matches
-            .filter(|mat| mat.first_capture(self) == "XmlElement")
+            .filter(|mat| {
+                ["XmlElement", "XmlAttribute"].contains(&mat.first_capture(self).as_str())
+            })
             .map(|mat| mat.captures[1].node)
             .next();
 
@@ -357,24 +670,38 @@ impl<'a> JavaFile {
     }
 
     fn xml_name(&'a self, field_node: Node) -> Option<String> {
+        self.annotation_value(
+            field_node,
+            &["XmlElement", "XmlElementRef", "XmlAttribute"],
+            "name",
+        )
+    }
+
+    /// Value of `key` in the first of the `annotations` on the field, without quotes
+    fn annotation_value(
+        &'a self,
+        field_node: Node,
+        annotations: &[&str],
+        key: &str,
+    ) -> Option<String> {
         let mut query = self.new_query(
             "((field_declaration (modifiers (annotation name: * @ann arguments: * @arg))))",
         );
         let matches = query.matches_node(field_node);
 
-        let xml_element_arguments: Option<Node> = matches
-            .filter(|mat| mat.first_capture(self) == "XmlElement" || mat.first_capture(self) == "XmlElementRef")
+        let annotation_arguments: Option<Node> = matches
+            .filter(|mat| annotations.contains(&mat.first_capture(self).as_str()))
             .map(|mat| mat.captures[1].node)
             .next();
 
-        match xml_element_arguments {
-            Some(xml_element_arguments) => {
+        match annotation_arguments {
+            Some(annotation_arguments) => {
                 let mut arg_query =
                     self.new_query("((element_value_pair key: * @key value: * @value))");
-                let mut arg_matches = arg_query.matches_node(xml_element_arguments);
+                let mut arg_matches = arg_query.matches_node(annotation_arguments);
 
                 arg_matches
-                    .find(|mat| mat.first_capture(self) == "name")
+                    .find(|mat| mat.first_capture(self) == key)
                     .map(|mat| mat.second_capture(self).replace("\"", ""))
             }
             None => return None,
@@ -411,27 +738,42 @@ impl<'a> CapureContent for QueryMatch<'a> {
     }
 }
 
-pub fn parse_class(source_code: &str) -> Class {
-    let java_file = JavaFile::new(source_code);
+pub fn parse_class(source_code: &str, config: &Config) -> Result<Class> {
+    let java_file = JavaFile::new(source_code)?;
+    let start = Position { line: 1, column: 1 };
 
     let mut package_query =
-        java_file.new_query("((package_declaration (scoped_identifier) @package))");
+        java_file.new_query("((package_declaration [(scoped_identifier) (identifier)] @package))");
 
-    let package_match = package_query.matches().next().unwrap();
+    let package_match = package_query
+        .matches()
+        .next()
+        .ok_or_else(|| Error::parse("no package declaration", start))?;
     let package_name = package_match.first_capture(&java_file);
 
     let mut class_name_query = java_file.new_query("((class_declaration name: * @class))");
     let class_matches = class_name_query.matches().next();
-
-    let imports = java_file.get_imports();
+    // The outermost declaration, whose Javadoc is the class documentation
+    let mut cursor = java_file.tree.walk();
+    let declaration = java_file
+        .tree
+        .root_node()
+        .named_children(&mut cursor)
+        .find(|node| TYPE_DECLARATIONS.contains(&node.kind()));
+    let doc = declaration.map_or(vec![], |declaration| java_file.javadoc(declaration));
+
+    let imports = java_file.get_imports(config);
     let subclasses = java_file.get_subclasses(&imports);
 
     if class_matches.is_some() {
         let class_name = class_matches.unwrap().first_capture(&java_file);
-        let fields = java_file.get_fields(None, &package_name, &imports);
-        let inner_classes = java_file.get_inner_classes(&package_name, &imports);
+        let mut fields =
+            java_file.get_fields(None, &class_name, &package_name, &imports, config)?;
+        let mut inner_classes =
+            java_file.get_inner_classes(&class_name, &package_name, &imports, config)?;
+        set_fragment_facets(&doc, &mut fields, &mut inner_classes);
 
-        Class {
+        Ok(Class {
             package: package_name,
             imports,
             name: class_name,
@@ -445,53 +787,94 @@ pub fn parse_class(source_code: &str) -> Class {
                 })
             }),
             inner_classes,
+            fault_info: java_file.get_fault_info(),
+            element_decls: java_file.get_element_decls(),
             skip: false,
+            doc,
             ..Default::default()
-        }
+        })
     } else {
         let mut enum_name_query = java_file.new_query("((enum_declaration name: * @enum))");
         let enum_match = enum_name_query.matches().next();
         if enum_match.is_some() {
             let enum_name = enum_match.unwrap().first_capture(&java_file);
-            Class {
+            Ok(Class {
                 package: package_name.to_string(),
                 imports,
                 name: enum_name,
                 enum_constants: java_file.get_enum_constants(),
+                doc,
                 ..Default::default()
-            }
+            })
         } else {
             let mut interface_name_query =
                 java_file.new_query("((interface_declaration name: * @interface))");
-            let interface_match = interface_name_query.matches().next().unwrap();
+            let interface_match = interface_name_query
+                .matches()
+                .next()
+                .ok_or_else(|| Error::parse("no class, enum or interface declaration", start))?;
             let interface_name = interface_match.first_capture(&java_file);
+            let functions = java_file.get_functions(&package_name, &imports, config)?;
 
-            Class {
+            Ok(Class {
                 package: package_name.to_string(),
                 imports,
                 name: interface_name,
-                functions: java_file.get_functions(),
+                functions,
+                doc,
                 ..Default::default()
-            }
+            })
         }
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct Function {
     pub name: String,
     pub arguments: Vec<Argument>,
-    pub return_type: String,
+    /// None for `void`
+    pub return_type: Option<ReturnType>,
+    /// The fault exceptions in the `throws` clause
+    pub faults: Vec<String>,
+    /// The fault classes of `faults` that were found, with their package
+    #[serde(default)]
+    pub fault_classes: Vec<Import>,
+    /// The Javadoc as KDoc lines, see `javadoc_lines`
+    pub doc: Vec<String>,
+}
+
+/// The return type of a port type method, like `Field` without the XML mapping
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReturnType {
+    pub r#type: Type,
+    pub generic_type: Option<String>,
+    pub builtin: bool,
+    pub nullable: bool,
 }
 
-#[derive(PartialEq, Debug)]
+/// A parameter of a port type method, with its `@WebParam`
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Argument {
     pub name: String,
     pub r#type: String,
     pub nullable: bool,
+    pub part_name: Option<String>,
+    pub xml_name: Option<String>,
+    pub target_namespace: Option<String>,
+    pub header: bool,
+    pub mode: Mode,
 }
 
-#[derive(PartialEq, Debug, Default)]
+/// `WebParam.Mode`. OUT and INOUT parameters are a `Holder<T>`
+#[derive(PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Mode {
+    #[default]
+    In,
+    Out,
+    InOut,
+}
+
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Class {
     pub package: String,
     pub imports: Vec<Import>,
@@ -500,25 +883,32 @@ pub struct Class {
     pub is_abstract: bool,
     pub superclass: Option<Superclass>,
     pub subclasses: Vec<Subclass>,
-    pub enum_constants: Vec<String>,
+    pub enum_constants: Vec<EnumConstant>,
     pub functions: Vec<Function>,
     pub inner_classes: Vec<InnerClass>,
+    /// The fault bean of a fault exception, from `getFaultInfo()`
+    pub fault_info: Option<String>,
+    /// The factory methods of an `ObjectFactory`
+    pub element_decls: Vec<ElementDecl>,
     pub skip: bool,
+    /// The Javadoc as KDoc lines, with the schema fragment of the complex type
+    pub doc: Vec<String>,
 }
 
-#[derive(PartialEq, Debug, Default)]
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct InnerClass {
     pub name: String,
     pub fields: Vec<Field>,
+    pub doc: Vec<String>,
 }
 
-#[derive(PartialEq, Debug, Default)]
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Subclass {
     pub name: String,
     pub package: Option<String>,
 }
 
-#[derive(PartialEq, Debug, Default)]
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Superclass {
     pub name: String,
     pub package: String,
@@ -527,25 +917,118 @@ pub struct Superclass {
     pub is_abstract: bool,
 }
 
-#[derive(PartialEq, Debug, Clone, Default)]
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Type {
     pub package: Option<String>,
     pub class: String,
     pub stdlib: bool,
+    /// The type arguments of a generic type, like `String` and `List<Car>` of `Map<String, List<Car>>`
+    pub arguments: Vec<Type>,
 }
 
-#[derive(PartialEq, Debug, Clone, Default)]
+impl Type {
+    /// Whether this is the class, or it is one of the type arguments at any depth
+    pub fn contains(&self, class: &str) -> bool {
+        self.class == class
+            || self
+                .arguments
+                .iter()
+                .any(|argument| argument.contains(class))
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Field {
     pub name: String,
     pub xml_name: Option<String>,
     pub package: String,
+    /// The type, or the first type argument of a generic type
     pub r#type: Type,
     pub generic_type: Option<String>,
+    /// All the type arguments of a generic type, the first being `r#type`
+    pub type_arguments: Vec<Type>,
     pub builtin: bool,
     pub nullable: bool,
+    pub binding: Binding,
+    /// The XSD type from `@XmlSchemaType`, like `date` for an `XMLGregorianCalendar`
+    pub schema_type: Option<String>,
+    /// The `ObjectFactory` method creating the `JAXBElement` of the field, once resolved
+    pub factory_method: Option<String>,
+    /// The elements of a field from an `xs:choice`, which holds one or a list of them
+    pub choice: Option<ChoiceType>,
+    /// The Javadoc as KDoc lines, like the `xs:documentation` of the element
+    pub doc: Vec<String>,
+    /// The facets of the simple type of the element or attribute
+    pub facets: Facets,
 }
 
-#[derive(PartialEq, Debug, Clone)]
+/// The facets of the `xs:restriction` of a simple type, which the values must satisfy
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Facets {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<String>,
+    pub total_digits: Option<usize>,
+    /// The allowed values of a simple type that is not bound to a Java enum
+    pub enumeration: Vec<String>,
+}
+
+/// How a field is bound to XML
+#[derive(PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Binding {
+    /// `@XmlElement`, `@XmlElementRef` or no annotation
+    #[default]
+    Element,
+    /// `@XmlAttribute`
+    Attribute,
+    /// `@XmlValue`, the text content of a class from `xs:simpleContent`
+    Value,
+    /// `@XmlAnyElement` or `@XmlAnyAttribute`
+    Any,
+}
+
+/// The elements that a choice field can hold, as a sealed type in Kotlin
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChoiceType {
+    /// The name of the class and the field, like `GarageCarOrBike`
+    pub name: String,
+    pub choices: Vec<Choice>,
+}
+
+/// An element of a choice field, from an `@XmlElement` in `@XmlElements` or an `@XmlElementRef`
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Choice {
+    pub xml_name: String,
+    /// The type of the element. For a `JAXBElement`, it is the value type once resolved
+    pub r#type: Type,
+    pub builtin: bool,
+    /// The field holds the element as a `JAXBElement`, created by the `ObjectFactory`
+    pub jaxb_element: bool,
+    /// The `ObjectFactory` method creating the `JAXBElement`, once resolved
+    pub factory_method: Option<String>,
+}
+
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnumConstant {
+    pub name: String,
+    /// The value in XML, from `@XmlEnumValue` or else the name
+    pub xml_value: String,
+    pub doc: Vec<String>,
+}
+
+/// A `@XmlElementDecl` method of an `ObjectFactory`, creating the `JAXBElement` of an element
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ElementDecl {
+    pub method: String,
+    pub name: String,
+    pub namespace: String,
+    /// The class of the field for local elements, None for root elements
+    pub scope: Option<String>,
+    /// The value type of the `JAXBElement`
+    pub r#type: String,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct Import {
     pub package: String,
     pub class: String,
@@ -574,6 +1057,169 @@ fn to_callback<'a>(source: &'a str) -> impl Fn(Node) -> &'a [u8] {
     move |n| &source.as_bytes()[n.byte_range()]
 }
 
+/// The declarations of the classes, enums and interfaces that are parsed
+const TYPE_DECLARATIONS: [&str; 3] = [
+    "class_declaration",
+    "enum_declaration",
+    "interface_declaration",
+];
+
+/// Sets the facets of the fields from the anonymous simple types in the schema fragment of the
+/// class documentation. Named simple types are not in the fragment
+fn set_fragment_facets(doc: &[String], fields: &mut [Field], inner_classes: &mut [InnerClass]) {
+    let fragment: Vec<&str> = doc
+        .iter()
+        .skip_while(|line| *line != "```xml")
+        .skip(1)
+        .take_while(|line| *line != "```")
+        .map(String::as_str)
+        .collect();
+    let fragment = fragment.join("\n");
+    let document = match roxmltree::Document::parse(&fragment) {
+        Ok(document) => document,
+        Err(_) => return,
+    };
+
+    let complex_type = document.root_element();
+    set_facets(complex_type, fields);
+    for inner_class in inner_classes {
+        let inner_type = complex_type
+            .descendants()
+            .filter(|node| node.has_tag_name("element"))
+            .find(|element| {
+                element
+                    .attribute("name")
+                    .is_some_and(|name| java_class_name(name) == inner_class.name)
+            })
+            .and_then(|element| {
+                element
+                    .children()
+                    .find(|child| child.has_tag_name("complexType"))
+            });
+        if let Some(inner_type) = inner_type {
+            set_facets(inner_type, &mut inner_class.fields);
+        }
+    }
+}
+
+/// Sets the facets of the elements and attributes declared by the complex type itself
+fn set_facets(complex_type: roxmltree::Node, fields: &mut [Field]) {
+    let declarations = complex_type.descendants().filter(|node| {
+        (node.has_tag_name("element") || node.has_tag_name("attribute"))
+            && node
+                .ancestors()
+                .skip(1)
+                .find(|ancestor| ancestor.has_tag_name("complexType"))
+                == Some(complex_type)
+    });
+    for declaration in declarations {
+        let restriction = declaration
+            .children()
+            .find(|child| child.has_tag_name("simpleType"))
+            .and_then(|simple_type| {
+                simple_type
+                    .children()
+                    .find(|child| child.has_tag_name("restriction"))
+            });
+        let (name, restriction) = match (declaration.attribute("name"), restriction) {
+            (Some(name), Some(restriction)) => (name, restriction),
+            _ => continue,
+        };
+        if let Some(field) = fields
+            .iter_mut()
+            .find(|field| field.xml_name.as_deref().unwrap_or(&field.name) == name)
+        {
+            field.facets = facets(restriction);
+        }
+    }
+}
+
+/// Javadoc boilerplate of JAXB and CXF, which says nothing about the Kotlin classes
+const JAVADOC_BOILERPLATE: [&str; 5] = [
+    "Java class for ",
+    "This class was generated by ",
+    "Generated source version",
+    "Gets the value of the ",
+    "Sets the value of the ",
+];
+
+/// The lines of a Javadoc comment as Markdown for KDoc. The boilerplate and the block tags are
+/// left out, and the `<pre>` schema fragment of JAXB becomes an XML code block
+fn javadoc_lines(comment: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut in_pre = false;
+    for line in comment
+        .trim_start_matches("/**")
+        .trim_end_matches("*/")
+        .lines()
+    {
+        let line = line.trim_start();
+        let line = line.strip_prefix('*').unwrap_or(line);
+        let line = line.strip_prefix(' ').unwrap_or(line).trim_end();
+        let text = line.trim_start();
+        if in_pre {
+            if text.trim_start_matches('}').starts_with("</pre>") {
+                in_pre = false;
+                lines.push("```".to_string());
+            } else {
+                lines.push(unescape_html(line));
+            }
+            continue;
+        }
+        let text = text.strip_prefix("<p>").unwrap_or(text).trim_start();
+        if text.starts_with('@') {
+            break;
+        }
+        if text.starts_with("<pre>") {
+            in_pre = true;
+            lines.push("```xml".to_string());
+        } else if !JAVADOC_BOILERPLATE
+            .iter()
+            .any(|boilerplate| text.starts_with(boilerplate))
+        {
+            let text = replace_inline_tag(text, "{@link", "[", "]");
+            let text = replace_inline_tag(&text, "{@code", "`", "`");
+            lines.push(unescape_html(&text));
+        }
+    }
+
+    let mut kdoc: Vec<String> = vec![];
+    for line in lines {
+        if !line.is_empty() || kdoc.last().is_some_and(|last| !last.is_empty()) {
+            kdoc.push(line);
+        }
+    }
+    while kdoc.last().is_some_and(|last| last.is_empty()) {
+        kdoc.pop();
+    }
+    kdoc
+}
+
+/// Replaces inline tags like `{@link Car }` by their text between `open` and `close`
+fn replace_inline_tag(text: &str, tag: &str, open: &str, close: &str) -> String {
+    let mut text = text.to_string();
+    while let Some(start) = text.find(tag) {
+        let end = match text[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let content = text[start + tag.len()..end].trim().to_string();
+        text.replace_range(start..=end, &format!("{}{}{}", open, content, close));
+    }
+    text
+}
+
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+fn is_primitive(java_type: &str) -> bool {
+    java_type.chars().next().is_some_and(|c| c.is_lowercase()) && !java_type.ends_with("[]")
+}
+
 fn type_package(
     builtin: bool,
     class_package: &str,
@@ -605,7 +1251,7 @@ mod tests {
         let source_code =
             fs::read_to_string("test-resources/src/main/kotlin/misc/Types.java").unwrap();
 
-        let parsed_class = parse_class(&source_code);
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
 
         let expected = Class {
             package: "misc.a".to_string(),
@@ -619,10 +1265,18 @@ mod tests {
                         package: None,
                         class: "Integer".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "cars".to_string(),
@@ -632,10 +1286,23 @@ mod tests {
                         package: Some("misc.a".to_string()),
                         class: "Car".to_string(),
                         stdlib: false,
+                        arguments: vec![],
                     },
                     generic_type: Some("List".to_string()),
+                    type_arguments: vec![Type {
+                        package: Some("misc.a".to_string()),
+                        class: "Car".to_string(),
+                        stdlib: false,
+                        arguments: vec![],
+                    }],
                     builtin: false,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "bool".to_string(),
@@ -645,10 +1312,18 @@ mod tests {
                         package: None,
                         class: "Boolean".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "nillableShort".to_string(),
@@ -658,10 +1333,18 @@ mod tests {
                         package: None,
                         class: "Short".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "car".to_string(),
@@ -671,10 +1354,18 @@ mod tests {
                         package: Some("misc.a".to_string()),
                         class: "Car".to_string(),
                         stdlib: false,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: false,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "xmlElementString".to_string(),
@@ -684,10 +1375,23 @@ mod tests {
                         package: None,
                         class: "String".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: Some("JAXBElement".to_string()),
+                    type_arguments: vec![Type {
+                        package: None,
+                        class: "String".to_string(),
+                        stdlib: true,
+                        arguments: vec![],
+                    }],
                     builtin: true,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "primInt".to_string(),
@@ -697,10 +1401,18 @@ mod tests {
                         package: None,
                         class: "int".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: false,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "primBool".to_string(),
@@ -710,10 +1422,18 @@ mod tests {
                         package: None,
                         class: "boolean".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: false,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "primLong".to_string(),
@@ -723,10 +1443,18 @@ mod tests {
                         package: None,
                         class: "long".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: false,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "primByteArray".to_string(),
@@ -736,10 +1464,18 @@ mod tests {
                         package: None,
                         class: "byte[]".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "primShort".to_string(),
@@ -749,10 +1485,18 @@ mod tests {
                         package: None,
                         class: "short".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: false,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "primDouble".to_string(),
@@ -762,10 +1506,18 @@ mod tests {
                         package: None,
                         class: "double".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: false,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "primFloat".to_string(),
@@ -775,10 +1527,18 @@ mod tests {
                         package: None,
                         class: "float".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: false,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
             ],
             ..Default::default()
@@ -792,7 +1552,7 @@ mod tests {
         let source_code =
             fs::read_to_string("test-resources/src/main/kotlin/misc/VehicleType.java")
                 .unwrap();
-        let parsed_class = parse_class(&source_code);
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
         let expected = Class {
             package: "misc.a".to_string(),
             name: "VehicleType".to_string(),
@@ -808,10 +1568,18 @@ mod tests {
                     package: None,
                     class: "int".to_string(),
                     stdlib: true,
+                    arguments: vec![],
                 },
                 generic_type: None,
+                type_arguments: vec![],
                 builtin: true,
                 nullable: false,
+                binding: Binding::Element,
+                schema_type: None,
+                factory_method: None,
+                choice: None,
+                doc: vec![],
+                facets: Facets::default(),
             }],
             subclasses: vec![
                 Subclass {
@@ -834,7 +1602,7 @@ mod tests {
             "test-resources/src/main/kotlin/misc/SuperclassWithSubclassesWithSameName.java",
         )
         .unwrap();
-        let parsed_class = parse_class(&source_code);
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
         let expected = Class {
             package: "misc.a".to_string(),
             name: "SuperclassWithSubclassesWithSameName".to_string(),
@@ -858,7 +1626,7 @@ mod tests {
         let source_code =
             fs::read_to_string("test-resources/src/main/kotlin/misc/BaseResponseType.java")
                 .unwrap();
-        let parsed_class = parse_class(&source_code);
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
         let expected = Class {
             package: "misc.a".to_string(),
             imports: vec![
@@ -880,10 +1648,18 @@ mod tests {
                     package: Some("misc.b".to_string()),
                     class: "ResponseStateType".to_string(),
                     stdlib: false,
+                    arguments: vec![],
                 },
                 generic_type: None,
+                type_arguments: vec![],
                 builtin: false,
                 nullable: false,
+                binding: Binding::Element,
+                schema_type: None,
+                factory_method: None,
+                choice: None,
+                doc: vec![],
+                facets: Facets::default(),
             }],
             is_abstract: true,
             subclasses: vec![Subclass {
@@ -902,7 +1678,7 @@ mod tests {
         )
         .unwrap();
 
-        let parsed_class = parse_class(&source_code);
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
         let expected = Class {
             package: "misc.a".to_string(),
             name: "CarsService".to_string(),
@@ -913,20 +1689,535 @@ mod tests {
                         name: "soapHeader".to_string(),
                         r#type: "misc.b.HeaderType".to_string(),
                         nullable: true,
+                        part_name: Some("SoapHeader".to_string()),
+                        xml_name: Some("AutHeader".to_string()),
+                        target_namespace: Some("http://asdf.com".to_string()),
+                        header: true,
+                        mode: Mode::In,
                     },
                     Argument {
                         name: "getCars".to_string(),
                         r#type: "GetCars".to_string(),
                         nullable: false,
+                        part_name: Some("GetCars".to_string()),
+                        xml_name: Some("GetCars".to_string()),
+                        target_namespace: Some("http://asdf.com".to_string()),
+                        header: false,
+                        mode: Mode::In,
                     },
                 ],
-                return_type: "GetCarsResponse".to_string(),
+                return_type: Some(ReturnType {
+                    r#type: Type {
+                        package: Some("misc.a".to_string()),
+                        class: "GetCarsResponse".to_string(),
+                        stdlib: false,
+                        arguments: vec![],
+                    },
+                    generic_type: None,
+                    builtin: false,
+                    nullable: true,
+                }),
+                faults: vec![],
+                fault_classes: vec![],
+                doc: vec!["Finds the cars of a brand".to_string()],
             }],
             ..Default::default()
         };
         assert_eq!(parsed_class, expected)
     }
 
+    #[test]
+    fn test_parse_holder_modes() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/misc/CarsHolderService.java")
+                .unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+        let arguments: Vec<(&str, &str, Mode, bool)> = parsed_class.functions[0]
+            .arguments
+            .iter()
+            .map(|arg| (arg.name.as_str(), arg.r#type.as_str(), arg.mode, arg.header))
+            .collect();
+        assert_eq!(
+            arguments,
+            vec![
+                ("getCars", "GetCars", Mode::In, false),
+                ("authHeader", "AuthHeader", Mode::In, true),
+                ("status", "Holder<String>", Mode::Out, false),
+                ("paging", "javax.xml.ws.Holder<Paging>", Mode::InOut, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_return_types() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/misc/CarsReturnsService.java")
+                .unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+        let return_types: Vec<_> = parsed_class
+            .functions
+            .iter()
+            .map(|func| {
+                func.return_type.as_ref().map(|return_type| {
+                    (
+                        return_type.r#type.class.as_str(),
+                        return_type.generic_type.as_deref(),
+                        return_type.builtin,
+                        return_type.nullable,
+                    )
+                })
+            })
+            .collect();
+        assert_eq!(
+            return_types,
+            vec![
+                None,
+                Some(("int", None, true, false)),
+                Some(("String", None, true, true)),
+                Some(("Car", Some("List"), false, true)),
+                Some(("String", Some("List"), true, true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_faults() {
+        let read = |path: &str| {
+            let source_code = fs::read_to_string(path).unwrap();
+            parse_class(&source_code, &Config::default()).unwrap()
+        };
+
+        let fault = read("test-resources/src/main/kotlin/misc/CarNotFoundFault.java");
+        assert_eq!(fault.fault_info, Some("misc.b.CarNotFound".to_string()));
+
+        let service = read("test-resources/src/main/kotlin/misc/CarsFaultService.java");
+        let faults: Vec<&Vec<String>> = service.functions.iter().map(|func| &func.faults).collect();
+        assert_eq!(
+            faults,
+            vec![
+                &vec!["CarNotFoundFault".to_string()],
+                &vec!["CarNotFoundFault".to_string(), "CarLockedFault".to_string()],
+                &vec!["IOException".to_string()],
+            ]
+        );
+        assert_eq!(
+            read("test-resources/src/main/kotlin/misc/Types.java").fault_info,
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_object_factory() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/ObjectFactory.java")
+                .unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+
+        assert_eq!(
+            parsed_class.element_decls,
+            vec![
+                ElementDecl {
+                    method: "createCar".to_string(),
+                    name: "Car".to_string(),
+                    namespace: "http://www.eika.no/cars".to_string(),
+                    scope: None,
+                    r#type: "Car".to_string(),
+                },
+                ElementDecl {
+                    method: "createStringJAXBElement".to_string(),
+                    name: "stringJAXBElement".to_string(),
+                    namespace: "http://www.eika.no/cars".to_string(),
+                    scope: Some("Car".to_string()),
+                    r#type: "String".to_string(),
+                },
+                ElementDecl {
+                    method: "createInternalClassJAXBElement".to_string(),
+                    name: "internalClassJAXBElement".to_string(),
+                    namespace: "http://www.eika.no/cars".to_string(),
+                    scope: Some("Car".to_string()),
+                    r#type: "Door".to_string(),
+                },
+                ElementDecl {
+                    method: "createGarageDoor".to_string(),
+                    name: "door".to_string(),
+                    namespace: "http://www.eika.no/cars".to_string(),
+                    scope: Some("Garage".to_string()),
+                    r#type: "Door".to_string(),
+                },
+                ElementDecl {
+                    method: "createGarageOwner".to_string(),
+                    name: "owner".to_string(),
+                    namespace: "http://www.eika.no/cars".to_string(),
+                    scope: Some("Garage".to_string()),
+                    r#type: "String".to_string(),
+                },
+                ElementDecl {
+                    method: "createParkingSpareDoor".to_string(),
+                    name: "spareDoor".to_string(),
+                    namespace: "http://www.eika.no/cars".to_string(),
+                    scope: Some("Parking".to_string()),
+                    r#type: "Door".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_choices() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Garage.java").unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+        let choices: Vec<Option<&ChoiceType>> = parsed_class
+            .fields
+            .iter()
+            .map(|field| field.choice.as_ref())
+            .collect();
+
+        let choice = |xml_name: &str, class: &str, builtin: bool, jaxb_element: bool| Choice {
+            xml_name: xml_name.to_string(),
+            r#type: Type {
+                package: (!builtin).then(|| "dataclasses".to_string()),
+                class: class.to_string(),
+                stdlib: builtin,
+                arguments: vec![],
+            },
+            builtin,
+            jaxb_element,
+            factory_method: None,
+        };
+        assert_eq!(
+            choices,
+            vec![
+                Some(&ChoiceType {
+                    name: "GarageCarOrRegistrationNumber".to_string(),
+                    choices: vec![
+                        choice("car", "Car", false, false),
+                        choice("registrationNumber", "String", true, false),
+                    ],
+                }),
+                Some(&ChoiceType {
+                    name: "GarageDoorOrOwner".to_string(),
+                    choices: vec![
+                        choice("door", "JAXBElement", false, true),
+                        choice("owner", "JAXBElement", false, true),
+                    ],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_bindings() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Price.java").unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+        let bindings: Vec<(&str, Option<&str>, Binding, bool)> = parsed_class
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.name.as_str(),
+                    field.xml_name.as_deref(),
+                    field.binding,
+                    field.nullable,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            bindings,
+            vec![
+                ("value", None, Binding::Value, true),
+                ("currency", Some("currency"), Binding::Attribute, false),
+                ("vatIncluded", Some("vat"), Binding::Attribute, true),
+            ]
+        );
+
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Extensible.java")
+                .unwrap();
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+        let bindings: Vec<(&str, Binding)> = parsed_class
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.binding))
+            .collect();
+
+        assert_eq!(
+            bindings,
+            vec![
+                ("name", Binding::Element),
+                ("any", Binding::Any),
+                ("extensions", Binding::Any),
+                ("otherAttributes", Binding::Any),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_enum() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/CarBrand.java").unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+        let constants: Vec<(&str, &str)> = parsed_class
+            .enum_constants
+            .iter()
+            .map(|constant| (constant.name.as_str(), constant.xml_value.as_str()))
+            .collect();
+
+        assert_eq!(
+            constants,
+            vec![
+                ("VW", "VW"),
+                ("BMW", "BMW"),
+                ("MERCEDES_BENZ", "mercedes-benz"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_javadoc() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Wheel.java").unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+
+        assert_eq!(
+            parsed_class.doc,
+            vec![
+                "A wheel of a car, with the tyre on it",
+                "",
+                "The following schema fragment specifies the expected content contained within this class.",
+                "",
+                "```xml",
+                "<complexType name=\"Wheel\">",
+                "  <complexContent>",
+                "    <restriction base=\"{http://www.w3.org/2001/XMLSchema}anyType\">",
+                "      <sequence>",
+                "        <element name=\"Diameter\" type=\"{http://www.w3.org/2001/XMLSchema}int\"/>",
+                "        <element name=\"Mounted\" type=\"{http://www.w3.org/2001/XMLSchema}date\" minOccurs=\"0\"/>",
+                "      </sequence>",
+                "      <attribute name=\"position\" type=\"{http://www.w3.org/2001/XMLSchema}string\" />",
+                "    </restriction>",
+                "  </complexContent>",
+                "</complexType>",
+                "```",
+            ]
+        );
+        let field_docs: Vec<Vec<String>> = parsed_class
+            .fields
+            .iter()
+            .map(|field| field.doc.clone())
+            .collect();
+        assert_eq!(
+            field_docs,
+            vec![
+                vec![
+                    "The rim diameter in inches, like 17 for a".to_string(),
+                    "[Car] of <= 2 tonnes".to_string()
+                ],
+                vec!["When the tyre was mounted".to_string()],
+                vec!["Front or rear, left or right".to_string()],
+            ]
+        );
+
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/CarBrand.java").unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+
+        assert_eq!(
+            parsed_class.doc[0..2],
+            [
+                "The following schema fragment specifies the expected content contained within this class.",
+                "```xml",
+            ]
+        );
+        let constant_docs: Vec<Vec<String>> = parsed_class
+            .enum_constants
+            .iter()
+            .map(|constant| constant.doc.clone())
+            .collect();
+        assert_eq!(
+            constant_docs,
+            vec![
+                vec![],
+                vec![],
+                vec!["Mercedes-Benz, written like in the registry".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_fragment_facets() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Tyre.java").unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+
+        let facets: Vec<(&str, &Facets)> = parsed_class
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), &field.facets))
+            .collect();
+        assert_eq!(
+            facets,
+            vec![
+                (
+                    "brand",
+                    &Facets {
+                        min_length: Some(1),
+                        max_length: Some(40),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "size",
+                    &Facets {
+                        pattern: Some("\\d{3}/\\d{2}R\\d{2}".to_string()),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "loadIndex",
+                    &Facets {
+                        total_digits: Some(3),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "tread",
+                    &Facets {
+                        max_length: Some(10),
+                        ..Default::default()
+                    }
+                ),
+                ("rating", &Facets::default()),
+                (
+                    "season",
+                    &Facets {
+                        enumeration: vec!["summer".to_string(), "winter".to_string()],
+                        ..Default::default()
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            parsed_class.inner_classes[0].fields[0].facets,
+            Facets {
+                pattern: Some("[A-G]".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_generics() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Parking.java").unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+        let class = |package: Option<&str>, class: &str, arguments: Vec<Type>| Type {
+            package: package.map(|package| package.to_string()),
+            class: class.to_string(),
+            stdlib: package.is_none(),
+            arguments,
+        };
+        let door = || class(Some("dataclasses"), "Door", vec![]);
+        let jaxb_element = || class(None, "JAXBElement", vec![door()]);
+        let list = |argument: Type| class(None, "List", vec![argument]);
+        let types: Vec<(&str, Option<&str>, &Type, &Vec<Type>)> = parsed_class
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.name.as_str(),
+                    field.generic_type.as_deref(),
+                    &field.r#type,
+                    &field.type_arguments,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            types,
+            vec![
+                (
+                    "spareDoors",
+                    Some("List"),
+                    &jaxb_element(),
+                    &vec![jaxb_element()],
+                ),
+                (
+                    "doorsByLevel",
+                    Some("Map"),
+                    &class(None, "String", vec![]),
+                    &vec![class(None, "String", vec![]), list(door())],
+                ),
+                (
+                    "levelsByDoor",
+                    Some("Map"),
+                    &door(),
+                    &vec![door(), class(None, "String", vec![])],
+                ),
+                (
+                    "rows",
+                    Some("List"),
+                    &list(class(None, "String", vec![])),
+                    &vec![list(class(None, "String", vec![]))],
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_type() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/misc/Dates.java").unwrap();
+
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
+        let schema_types: Vec<Option<&str>> = parsed_class
+            .fields
+            .iter()
+            .map(|field| field.schema_type.as_deref())
+            .collect();
+
+        assert_eq!(
+            schema_types,
+            vec![
+                Some("date"),
+                Some("dateTime"),
+                Some("date"),
+                None,
+                Some("gYear")
+            ]
+        );
+        assert_eq!(
+            parsed_class.fields[0].xml_name,
+            Some("BirthDate".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let missing_package = parse_class("public class Car {}", &Config::default()).unwrap_err();
+        assert_eq!(missing_package.stage, Stage::Parse);
+        assert_eq!(missing_package.position, Some(Position { line: 1, column: 1 }));
+
+        let missing_declaration =
+            parse_class("package dataclasses;\n", &Config::default()).unwrap_err();
+        assert_eq!(
+            missing_declaration.message,
+            "no class, enum or interface declaration"
+        );
+    }
+
     #[test]
     fn test_parse_class_with_innerclass() {
         let source_code = fs::read_to_string(
@@ -934,7 +2225,7 @@ mod tests {
         )
         .unwrap();
 
-        let parsed_class = parse_class(&source_code);
+        let parsed_class = parse_class(&source_code, &Config::default()).unwrap();
         let expected = Class {
             package: "inheritance.abstractclasses".to_string(),
             imports: vec![],
@@ -948,10 +2239,18 @@ mod tests {
                         package: None,
                         class: "int".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: true,
                     nullable: false,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
                 Field {
                     name: "inner".to_string(),
@@ -961,10 +2260,18 @@ mod tests {
                         package: Some("inheritance.abstractclasses".to_string()),
                         class: "ALevelWithInner.Inner".to_string(),
                         stdlib: false,
+                        arguments: vec![],
                     },
                     generic_type: None,
+                    type_arguments: vec![],
                     builtin: false,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: Facets::default(),
                 },
             ],
             is_abstract: true,
@@ -980,12 +2287,21 @@ mod tests {
                             package: None,
                             class: "String".to_string(),
                             stdlib: true,
+                            arguments: vec![],
                         },
                         generic_type: None,
+                        type_arguments: vec![],
                         builtin: true,
                         nullable: true,
+                        binding: Binding::Element,
+                        schema_type: None,
+                        factory_method: None,
+                        choice: None,
+                        doc: vec![],
+                        facets: Facets::default(),
                     },
                 ],
+                doc: vec![],
             }],
             ..Default::default()
         };
@@ -1,23 +1,102 @@
+use std::collections::BTreeMap;
 use std::io::Write;
 
-use gtmpl::{Func, FuncError};
+use gtmpl::{Context, Func, FuncError, Template, TemplateError};
 use gtmpl_derive::Gtmpl;
 use gtmpl_value::Value;
 
+use crate::error::{Error, Result};
+
+/// Templates that replace the embedded ones, by name, like `data_class`
+pub type Templates = BTreeMap<String, String>;
+
 #[derive(Gtmpl, Default, Debug, PartialEq)]
 pub struct KotlinClass {
     pub kotlin_name: String,
     pub java_name: String,
+    pub suffix: String,
     pub package: String,
     pub fields: Vec<Field>,
     pub parts: Vec<Part>,
     pub java_superclass: Option<Superclass>, // TODO change field to 'superclass'
     pub functions: Vec<Function>,
     pub is_abstract: bool,
+    pub is_sealed: bool,
     pub subclasses: Vec<String>,
     pub imports: Vec<Import>,
-    pub enum_constants: Vec<String>,
+    pub enum_constants: Vec<EnumConstant>,
+    pub unknown_enum_constant: bool, // the enum gets an UNKNOWN constant for values it doesn't know
     pub inner_classes: Vec<InnerClass>,
+    pub serialization: String, // "kotlinx", "jackson" or empty for no serialization annotations
+    pub contextual_types: Vec<String>, // Java types without a kotlinx serializer, set when written
+    pub coroutines: bool,
+    pub coroutine_dispatcher: String,
+    pub fault_handling: String, // "exceptions", "result" or empty when no method declares faults
+    pub faults: Vec<Fault>,     // the faults the methods of a service declare
+    pub sealed_faults: bool,    // some method has a sealed class of its faults
+    pub fault_info: String,     // the fault bean of a fault exception, empty for other classes
+    pub element_factories: Vec<ElementFactory>, // the root elements of an ObjectFactory
+    pub choices: Vec<Choice>, // sealed types of the choice fields of the class and its inner classes
+    pub kdoc: Vec<String>,    // lines of the class documentation in the KDoc of the file
+    pub validation: String,   // "init" or "method" when the properties have checks, or empty
+    pub dsl_marker: String,   // qualified name of the DslMarker of the builders, or empty for none
+    pub builder: Builder,     // set when written
+    pub datatype_factory: bool, // set when written, when properties convert java.time values to calendars
+    pub patterns: Vec<Check>, // the pattern checks, whose Regex the companion holds, set when written
+}
+
+#[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
+pub struct EnumConstant {
+    pub name: String,
+    pub xml_value: String,
+    pub kdoc: Vec<String>,
+}
+
+/// A check of an XSD facet of a property
+#[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
+pub struct Check {
+    pub property: String,
+    pub facet: String,     // the XSD facet, like maxLength
+    pub condition: String, // Kotlin expression that is true for valid values of the property
+    pub message: String,
+    pub pattern: String, // the companion val with the Regex of a pattern facet, or empty
+    pub regex: String,   // the Java regex of the pattern, escaped for a Kotlin string
+}
+
+#[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
+pub struct Choice {
+    pub name: String,
+    pub java_type: String, // the type of the elements in the Java field, Any or a JAXBElement
+    pub jaxb_elements: bool, // the Java field only holds JAXBElements
+    pub cases: Vec<ChoiceCase>,
+}
+
+#[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
+pub struct ChoiceCase {
+    pub name: String,
+    pub xml_name: String,
+    pub t: String,         // qualified, since a case may have the name of its type
+    pub java_type: String, // qualified like t
+    pub convert: bool,
+    pub factory_func: String, // ObjectFactory method creating the JAXBElement, or empty
+    pub object_factory_class: String,
+}
+
+#[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
+pub struct ElementFactory {
+    pub method: String,
+    pub qname: String,
+    pub namespace: String,
+    pub xml_name: String,
+    pub t: String,
+    pub java_type: String,
+    pub convert: bool,
+}
+
+#[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
+pub struct Fault {
+    pub java_name: String,
+    pub kotlin_name: String,
 }
 
 #[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
@@ -29,6 +108,28 @@ pub struct Part {
 pub struct InnerClass {
     pub name: String,
     pub fields: Vec<Field>,
+    pub kdoc: Vec<String>,
+    pub validation: String,   // like in KotlinClass
+    pub builder: Builder,     // set when written
+    pub patterns: Vec<Check>, // like in KotlinClass
+}
+
+/// The `Builder` of a data class, with the DSL of its properties
+#[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
+pub struct Builder {
+    pub class: String, // the data class it builds, or empty when the class has no builder
+    pub function: String, // top-level function building the class from a DSL block, empty for inner classes
+    pub dsl_marker: String,
+    pub fields: Vec<Field>,
+    pub nested: Vec<NestedBuilder>,
+}
+
+/// A builder function of a property whose type is an inner class, which sets or adds the value
+#[derive(Gtmpl, Default, Debug, PartialEq, Clone)]
+pub struct NestedBuilder {
+    pub property: String,
+    pub builder: String,
+    pub list: bool,
 }
 
 #[derive(Gtmpl, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -41,7 +142,17 @@ pub struct Import {
 pub struct Function {
     pub name: String,
     pub arguments: Vec<Arg>,
-    pub return_type: String,
+    pub return_type: String,   // Unit for void
+    pub return_prefix: String, // the Java return value is converted to return_type between these
+    pub return_suffix: String,
+    pub async_name: String, // the *Async method with an AsyncHandler, or empty
+    pub headers_class: String, // parameter object for the SOAP headers, or empty without headers
+    pub result_class: String, // returned with the OUT and INOUT parameters, or empty without them
+    pub fault_wrapper: String, // `faults`, `result<T, F>` or `result(this::<name>Fault)` around the body, or empty
+    pub declared_type: String, // the Kotlin return type, an OperationResult when fault_wrapper is `result`
+    pub fault_class: String, // sealed class of the faults in the OperationResult, empty for one fault
+    pub faults: Vec<Fault>,  // the cases of fault_class
+    pub kdoc: Vec<String>,
 }
 
 #[derive(Gtmpl, Default, Debug, PartialEq)]
@@ -49,6 +160,10 @@ pub struct Arg {
     pub name: String,
     pub t: String,
     pub nullable: bool,
+    pub convert: bool,
+    pub header: bool,
+    pub mode: String,      // "IN", "OUT" or "INOUT"
+    pub java_type: String, // the type in the Holder of OUT and INOUT parameters
 }
 
 #[derive(Gtmpl, Default, Debug, PartialEq)]
@@ -68,6 +183,14 @@ pub struct Field {
     pub convert: bool,
     pub nullable: bool,
     pub use_getter: bool,
+    pub time_type: String, // java.time type of an XMLGregorianCalendar field, or empty
+    pub map_entry: String, // Java class of the key/value entries of a Map field, or empty
+    pub map_fields: Vec<Field>, // the key and value of the entries of a Map field
+    pub type_arguments: Vec<Field>, // the type arguments of a nested generic type, like List<JAXBElement<Car>>
+    pub xml_name: String,
+    pub kdoc: Vec<String>, // lines of the KDoc of the property, like the XML attribute it is bound to
+    pub checks: Vec<Check>, // the XSD facets the values must satisfy, with validation
+    annotation: String,    // serialization annotation of the property, set when written
     get_type: Func,
     get_type_with_default: Func,
     to_java: Func,
@@ -96,12 +219,57 @@ impl Field {
             convert,
             nullable,
             use_getter,
+            time_type: "".to_string(),
+            map_entry: "".to_string(),
+            map_fields: vec![],
+            type_arguments: vec![],
+            xml_name: name.to_owned(),
+            kdoc: vec![],
+            checks: vec![],
+            annotation: "".to_string(),
             get_type,
             get_type_with_default,
             to_java,
             from_java,
         }
     }
+
+    /// A `Map` field for a list of CXF key/value entries of the Java class `map_entry`.
+    /// The key is never null in the map
+    pub fn map(name: &str, map_entry: String, key: Field, value: Field, use_getter: bool) -> Field {
+        let key = Field {
+            nullable: false,
+            ..key
+        };
+        Field {
+            name: name.to_owned(),
+            r#type: format!("{}, {}", key.r#type, call(get_type, &value)),
+            generic_type: "Map".to_string(),
+            convert: true,
+            nullable: true,
+            use_getter,
+            map_entry,
+            map_fields: vec![key, value],
+            xml_name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the type arguments of a generic type whose type arguments are generic themselves.
+    /// The type arguments are fields without names, and `JAXBElement`s need a factory function
+    pub fn with_type_arguments(self, type_arguments: Vec<Field>) -> Field {
+        Field {
+            r#type: type_arguments
+                .iter()
+                .map(|argument| call(get_type, argument))
+                .collect::<Vec<String>>()
+                .join(", "),
+            convert: self.generic_type == "JAXBElement"
+                || type_arguments.iter().any(|argument| argument.convert),
+            type_arguments,
+            ..self
+        }
+    }
 }
 
 impl Clone for Field {
@@ -116,6 +284,14 @@ impl Clone for Field {
             convert: self.convert,
             nullable: self.nullable,
             use_getter: self.use_getter,
+            time_type: self.time_type.clone(),
+            map_entry: self.map_entry.clone(),
+            map_fields: self.map_fields.clone(),
+            type_arguments: self.type_arguments.clone(),
+            xml_name: self.xml_name.clone(),
+            kdoc: self.kdoc.clone(),
+            checks: self.checks.clone(),
+            annotation: self.annotation.clone(),
             get_type: self.get_type,
             get_type_with_default: self.get_type_with_default,
             to_java: self.to_java,
@@ -133,6 +309,13 @@ impl PartialEq for Field {
             && self.convert == other.convert
             && self.nullable == other.nullable
             && self.use_getter == other.use_getter
+            && self.time_type == other.time_type
+            && self.map_entry == other.map_entry
+            && self.map_fields == other.map_fields
+            && self.type_arguments == other.type_arguments
+            && self.xml_name == other.xml_name
+            && self.kdoc == other.kdoc
+            && self.checks == other.checks
     }
 }
 
@@ -146,6 +329,13 @@ impl std::fmt::Debug for Field {
             .field("convert", &self.convert)
             .field("nullable", &self.nullable)
             .field("use_getter", &self.use_getter)
+            .field("time_type", &self.time_type)
+            .field("map_entry", &self.map_entry)
+            .field("map_fields", &self.map_fields)
+            .field("type_arguments", &self.type_arguments)
+            .field("xml_name", &self.xml_name)
+            .field("kdoc", &self.kdoc)
+            .field("checks", &self.checks)
             .finish()
     }
 }
@@ -162,6 +352,14 @@ impl Default for Field {
             object_factory_class: "".to_string(),
             object_factory_package: "".to_string(),
             use_getter: false,
+            time_type: "".to_string(),
+            map_entry: "".to_string(),
+            map_fields: vec![],
+            type_arguments: vec![],
+            xml_name: "".to_string(),
+            kdoc: vec![],
+            checks: vec![],
+            annotation: "".to_string(),
             get_type,
             get_type_with_default,
             to_java,
@@ -194,6 +392,16 @@ impl Clone for Function {
             name: self.name.clone(),
             arguments: self.arguments.clone(),
             return_type: self.return_type.clone(),
+            return_prefix: self.return_prefix.clone(),
+            return_suffix: self.return_suffix.clone(),
+            async_name: self.async_name.clone(),
+            headers_class: self.headers_class.clone(),
+            result_class: self.result_class.clone(),
+            fault_wrapper: self.fault_wrapper.clone(),
+            declared_type: self.declared_type.clone(),
+            fault_class: self.fault_class.clone(),
+            faults: self.faults.clone(),
+            kdoc: self.kdoc.clone(),
         }
     }
 }
@@ -203,6 +411,10 @@ impl Clone for InnerClass {
         InnerClass {
             name: self.name.clone(),
             fields: self.fields.clone(),
+            kdoc: self.kdoc.clone(),
+            validation: self.validation.clone(),
+            builder: self.builder.clone(),
+            patterns: self.patterns.clone(),
         }
     }
 }
@@ -212,45 +424,600 @@ impl Clone for Arg {
         Arg {
             name: self.name.clone(),
             t: self.t.clone(),
-            nullable: self.nullable.clone(),
+            nullable: self.nullable,
+            convert: self.convert,
+            header: self.header,
+            mode: self.mode.clone(),
+            java_type: self.java_type.clone(),
         }
     }
 }
 
-pub fn write_class<W: Write>(kotlin_class: KotlinClass, writer: &mut W) {
+pub fn write_class<W: Write>(
+    mut kotlin_class: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    add_serialization(&mut kotlin_class);
+    add_builders(&mut kotlin_class);
+    kotlin_class.datatype_factory = converts_calendars(&kotlin_class);
+    add_patterns(&mut kotlin_class);
+
     if kotlin_class.is_abstract {
-        write_abstract_class(kotlin_class, writer)
+        write_abstract_class(kotlin_class, templates, writer)
     } else if !kotlin_class.subclasses.is_empty() {
-        write_open_superclass(kotlin_class, writer)
+        write_open_superclass(kotlin_class, templates, writer)
     } else if kotlin_class.java_superclass.is_some() {
-        write_subclass(kotlin_class, writer)
+        write_subclass(kotlin_class, templates, writer)
     } else if !kotlin_class.enum_constants.is_empty() {
-        write_enum(kotlin_class, writer)
+        write_enum(kotlin_class, templates, writer)
     } else if !kotlin_class.functions.is_empty() {
-        write_interface(kotlin_class, writer)
+        write_interface(kotlin_class, templates, writer)
+    } else if !kotlin_class.fault_info.is_empty() {
+        write_fault(kotlin_class, templates, writer)
+    } else if !kotlin_class.element_factories.is_empty() {
+        write_object_factory(kotlin_class, templates, writer)
     } else if !kotlin_class.parts.is_empty() {
-        write_large_data_class(kotlin_class, writer)
+        write_large_data_class(kotlin_class, templates, writer)
+    } else {
+        write_data_class(kotlin_class, templates, writer)
+    }
+}
+
+/// Collects the pattern checks of the properties, for the companion objects to compile their
+/// regexes once. The class checks the properties of its superclass and parts too
+fn add_patterns(kotlin_class: &mut KotlinClass) {
+    let patterns = |fields: &mut dyn Iterator<Item = &Field>| -> Vec<Check> {
+        fields
+            .flat_map(|field| &field.checks)
+            .filter(|check| !check.pattern.is_empty())
+            .cloned()
+            .collect()
+    };
+    kotlin_class.patterns = patterns(
+        &mut kotlin_class
+            .java_superclass
+            .iter()
+            .flat_map(|superclass| &superclass.fields)
+            .chain(&kotlin_class.fields)
+            .chain(kotlin_class.parts.iter().flat_map(|part| &part.fields)),
+    );
+    for inner_class in kotlin_class.inner_classes.iter_mut() {
+        inner_class.patterns = patterns(&mut inner_class.fields.iter());
+    }
+}
+
+/// Whether a property of the class or its inner classes converts a java.time value to an
+/// `XMLGregorianCalendar`, which needs the `DatatypeFactory` of the file
+fn converts_calendars(kotlin_class: &KotlinClass) -> bool {
+    let mut fields = kotlin_class
+        .fields
+        .iter()
+        .chain(
+            kotlin_class
+                .java_superclass
+                .iter()
+                .flat_map(|superclass| &superclass.fields),
+        )
+        .chain(kotlin_class.parts.iter().flat_map(|part| &part.fields))
+        .chain(
+            kotlin_class
+                .inner_classes
+                .iter()
+                .flat_map(|inner_class| &inner_class.fields),
+        );
+    fields.any(|field| {
+        !field.time_type.is_empty()
+            || field
+                .map_fields
+                .iter()
+                .any(|value| !value.time_type.is_empty())
+    })
+}
+
+/// Kotlin types that kotlinx.serialization has serializers for
+const SERIALIZABLE_TYPES: [&str; 9] = [
+    "String",
+    "Int",
+    "Double",
+    "Float",
+    "Short",
+    "Boolean",
+    "Long",
+    "Byte",
+    "ByteArray",
+];
+
+/// Sets the builders of a data class and its inner classes, and imports the DSL marker. Abstract
+/// classes only get them for their inner classes, and other classes have none
+fn add_builders(kotlin_class: &mut KotlinClass) {
+    let is_data_class = kotlin_class.enum_constants.is_empty()
+        && kotlin_class.functions.is_empty()
+        && kotlin_class.fault_info.is_empty()
+        && kotlin_class.element_factories.is_empty();
+    let has_builders = !kotlin_class.is_abstract || !kotlin_class.inner_classes.is_empty();
+    if kotlin_class.dsl_marker.is_empty() || !is_data_class || !has_builders {
+        return;
+    }
+    let (marker_package, dsl_marker) = match kotlin_class.dsl_marker.rsplit_once('.') {
+        Some((package, marker)) => (package.to_string(), marker.to_string()),
+        None => (String::new(), kotlin_class.dsl_marker.clone()),
+    };
+    if !marker_package.is_empty() && marker_package != kotlin_class.package {
+        kotlin_class.imports.push(Import {
+            package: marker_package,
+            class: dsl_marker.clone(),
+        });
+        kotlin_class.imports.sort();
+        kotlin_class.imports.dedup();
+    }
+
+    let suffix = kotlin_class.suffix.clone();
+    for inner_class in kotlin_class.inner_classes.iter_mut() {
+        inner_class.builder = Builder {
+            class: format!("{}{}", inner_class.name, suffix),
+            dsl_marker: dsl_marker.clone(),
+            fields: inner_class.fields.clone(),
+            ..Default::default()
+        };
+    }
+    if kotlin_class.is_abstract {
+        return;
+    }
+
+    let class = if kotlin_class.subclasses.is_empty() {
+        kotlin_class.kotlin_name.clone()
+    } else {
+        format!("{}Impl{}", kotlin_class.java_name, suffix)
+    };
+    let superclass_fields = kotlin_class
+        .java_superclass
+        .iter()
+        .flat_map(|superclass| &superclass.fields);
+    let part_fields = kotlin_class.parts.iter().flat_map(|part| &part.fields);
+    let fields: Vec<Field> = superclass_fields
+        .chain(&kotlin_class.fields)
+        .chain(part_fields)
+        .cloned()
+        .collect();
+    let nested = fields
+        .iter()
+        .filter(|field| field.generic_type.is_empty() || field.generic_type == "List")
+        .filter_map(|field| {
+            let inner_class = kotlin_class
+                .inner_classes
+                .iter()
+                .find(|inner_class| inner_class.builder.class == field.r#type)?;
+            Some(NestedBuilder {
+                property: field.name.clone(),
+                builder: format!("{}.Builder", inner_class.builder.class),
+                list: field.generic_type == "List",
+            })
+        })
+        .collect();
+    let mut function = kotlin_class.java_name.clone();
+    function[..1].make_ascii_lowercase();
+    kotlin_class.builder = Builder {
+        class,
+        function: kotlin_identifier(&function),
+        dsl_marker,
+        fields,
+        nested,
+    };
+}
+
+/// The hard keywords of Kotlin, which are escaped with backticks when used as names
+const KOTLIN_KEYWORDS: [&str; 28] = [
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+fn kotlin_identifier(name: &str) -> String {
+    if KOTLIN_KEYWORDS.contains(&name) {
+        format!("`{}`", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Sets the serialization annotations of the properties, and imports what the templates use.
+/// Enums, services and data classes split into parts are left as they are
+fn add_serialization(kotlin_class: &mut KotlinClass) {
+    let is_interface = kotlin_class.is_abstract || !kotlin_class.subclasses.is_empty();
+    let is_hierarchy = is_interface || kotlin_class.java_superclass.is_some();
+    let is_data_class = kotlin_class.enum_constants.is_empty()
+        && kotlin_class.functions.is_empty()
+        && kotlin_class.fault_info.is_empty()
+        && kotlin_class.element_factories.is_empty()
+        && kotlin_class.parts.is_empty();
+    if kotlin_class.serialization.is_empty() || !(is_hierarchy || is_data_class) {
+        return;
+    }
+    let kotlinx = kotlin_class.serialization == "kotlinx";
+    // Subclasses are registered by their own name, unlike the interfaces of a hierarchy
+    let is_registered = is_hierarchy && !kotlin_class.is_abstract;
+
+    // Abstract classes only write the properties of their inner classes
+    let mut fields: Vec<&mut Field> = kotlin_class
+        .inner_classes
+        .iter_mut()
+        .flat_map(|inner_class| inner_class.fields.iter_mut())
+        .collect();
+    if !kotlin_class.is_abstract {
+        fields.extend(kotlin_class.fields.iter_mut());
+        fields.extend(
+            kotlin_class
+                .java_superclass
+                .iter_mut()
+                .flat_map(|superclass| superclass.fields.iter_mut()),
+        );
+    }
+    let has_fields = !fields.is_empty();
+    let has_choices = !kotlin_class.choices.is_empty();
+
+    let mut contextual_types = vec![];
+    for field in fields {
+        field.annotation = if kotlinx {
+            format!("@SerialName(\"{}\") ", field.xml_name)
+        } else {
+            format!("@JsonProperty(\"{}\") ", field.xml_name)
+        };
+        for value in type_leaves(field).into_iter().chain(&field.map_fields) {
+            if !kotlinx || value.convert {
+                continue;
+            }
+            // The key and the value of a Map are both in the type
+            for t in value.r#type.split(", ") {
+                if !SERIALIZABLE_TYPES.contains(&t) {
+                    contextual_types.push(t.to_string());
+                }
+            }
+        }
+    }
+
+    for case in kotlin_class.choices.iter().flat_map(|choice| &choice.cases) {
+        if kotlinx && !case.convert && !SERIALIZABLE_TYPES.contains(&case.t.as_str()) {
+            contextual_types.push(case.t.clone());
+        }
+    }
+
+    contextual_types.sort();
+    contextual_types.dedup();
+
+    let imports = if kotlinx {
+        vec![
+            (
+                "kotlinx.serialization.Serializable",
+                !kotlin_class.is_abstract || !kotlin_class.inner_classes.is_empty() || has_choices,
+            ),
+            (
+                "kotlinx.serialization.SerialName",
+                has_fields || is_registered || has_choices,
+            ),
+            (
+                "kotlinx.serialization.UseContextualSerialization",
+                !contextual_types.is_empty(),
+            ),
+            (
+                "kotlinx.serialization.modules.PolymorphicModuleBuilder",
+                is_hierarchy,
+            ),
+            (
+                "kotlinx.serialization.modules.SerializersModule",
+                is_interface,
+            ),
+            ("kotlinx.serialization.modules.polymorphic", is_interface),
+            ("kotlinx.serialization.modules.subclass", is_registered),
+        ]
     } else {
-        write_data_class(kotlin_class, writer)
+        vec![
+            ("com.fasterxml.jackson.annotation.JsonProperty", has_fields),
+            (
+                "com.fasterxml.jackson.annotation.JsonSubTypes",
+                is_interface || has_choices,
+            ),
+            (
+                "com.fasterxml.jackson.annotation.JsonTypeInfo",
+                is_interface || has_choices,
+            ),
+            (
+                "com.fasterxml.jackson.annotation.JsonTypeName",
+                is_registered,
+            ),
+        ]
+    };
+    kotlin_class.contextual_types = contextual_types;
+    kotlin_class
+        .imports
+        .extend(
+            imports
+                .into_iter()
+                .filter(|(_, used)| *used)
+                .map(|(import, _)| {
+                    let (package, class) = import.rsplit_once('.').unwrap();
+                    Import {
+                        package: package.to_string(),
+                        class: class.to_string(),
+                    }
+                }),
+        );
+    kotlin_class.imports.sort();
+    kotlin_class.imports.dedup();
+}
+
+/// The KDoc of the generated file with the class documentation, and of the properties, functions
+/// and enum constants at the indentation of classes and inner classes. Prepended to the templates
+const KDOC_TEMPLATE: &str = "{{- define \"header\"}}/**
+{{- range .}}
+ *{{if .}} {{kdoc_text .}}{{end}}
+{{- end}}
+{{- if .}}
+ *
+{{- end}}
+ * This file is GENERATED. Please don't change
+ */
+{{- end}}
+{{- define \"kdoc\"}}
+{{- if eq (len .) 1}}
+    /** {{kdoc_text (index . 0)}} */
+{{- else if .}}
+    /**
+{{- range .}}
+     *{{if .}} {{kdoc_text .}}{{end}}
+{{- end}}
+     */
+{{- end}}
+{{- end}}
+{{- define \"inner_kdoc\"}}
+{{- if eq (len .) 1}}
+        /** {{kdoc_text (index . 0)}} */
+{{- else if .}}
+        /**
+{{- range .}}
+         *{{if .}} {{kdoc_text .}}{{end}}
+{{- end}}
+         */
+{{- end}}
+{{- end}}";
+
+/// The checks of the XSD facets of the properties of classes and inner classes, in an `init` block
+/// or a `validate` function. Prepended to the templates of the data classes
+const VALIDATION_TEMPLATE: &str = "
+{{- define \"require\"}}{{range .}}{{range .checks}}
+        require({{.condition}}) { \"{{.message}}\" }{{end}}{{end}}{{end}}
+{{- define \"violations\"}}{{range .}}{{range .checks}}
+        if (!({{.condition}})) violations += Violation(\"{{.property}}\", \"{{.message}}\"){{end}}{{end}}{{end}}
+{{- define \"validation\"}}
+{{- if eq .validation \"init\"}}
+
+    init {
+{{- if .java_superclass}}{{template \"require\" .java_superclass.fields}}{{end}}
+{{- template \"require\" .fields}}
+{{- range .parts}}{{template \"require\" .fields}}{{end}}
+    }
+{{- else if eq .validation \"method\"}}
+
+    /** The violations of the XSD facets by the properties */
+    fun validate(): List<Violation> {
+        val violations = mutableListOf<Violation>()
+{{- if .java_superclass}}{{template \"violations\" .java_superclass.fields}}{{end}}
+{{- template \"violations\" .fields}}
+{{- range .parts}}{{template \"violations\" .fields}}{{end}}
+        return violations
+    }
+
+    /** A property that violates an XSD facet */
+    data class Violation(val property: String, val message: String)
+{{- end}}
+{{- end}}
+{{- define \"patterns\"}}{{range .}}
+        private val {{.pattern}} = Regex(\"{{.regex}}\"){{end}}{{end}}
+{{- define \"inner_patterns\"}}{{range .}}
+            private val {{.pattern}} = Regex(\"{{.regex}}\"){{end}}{{end}}
+{{- define \"inner_validation\"}}
+{{- if eq .validation \"init\"}}
+
+        init {
+{{- range .fields}}{{range .checks}}
+            require({{.condition}}) { \"{{.message}}\" }{{end}}{{end}}
+        }
+{{- else if eq .validation \"method\"}}
+
+        /** The violations of the XSD facets by the properties */
+        fun validate(): List<Violation> {
+            val violations = mutableListOf<Violation>()
+{{- range .fields}}{{range .checks}}
+            if (!({{.condition}})) violations += Violation(\"{{.property}}\", \"{{.message}}\"){{end}}{{end}}
+            return violations
+        }
+
+        /** A property that violates an XSD facet */
+        data class Violation(val property: String, val message: String)
+{{- end}}
+{{- end}}";
+
+/// The builder DSL of data classes and inner classes, nested in the class they build, and the
+/// top-level function of data classes taking a DSL block. Prepended to the templates of the data
+/// classes
+const BUILDER_TEMPLATE: &str = "
+{{- define \"builder\"}}
+{{- if .class}}
+
+    /** Builds [{{.class}}] instances, failing when a required property is not set */
+    @{{.dsl_marker}}
+    class Builder {
+{{- range .fields}}
+        var {{.name}}: {{if or .nullable .generic_type}}{{.get_type_with_default}}{{else}}{{.get_type}}? = null{{end}}
+{{- end}}
+{{- range .nested}}
+
+        fun {{.property}}(block: {{.builder}}.() -> Unit) {
+            {{.property}} {{if .list}}+{{end}}= {{.builder}}().apply(block).build()
+        }
+{{- end}}
+
+        fun build(): {{.class}} = {{.class}}(
+{{- range .fields}}
+            {{.name}} = {{if or .nullable .generic_type}}{{.name}}{{else}}checkNotNull({{.name}}) { \"{{.name}} is required\" }{{end}},
+{{- end}}
+        )
+    }
+{{- end}}
+{{- end}}
+{{- define \"inner_builder\"}}
+{{- if .class}}
+
+        /** Builds [{{.class}}] instances, failing when a required property is not set */
+        @{{.dsl_marker}}
+        class Builder {
+{{- range .fields}}
+            var {{.name}}: {{if or .nullable .generic_type}}{{.get_type_with_default}}{{else}}{{.get_type}}? = null{{end}}
+{{- end}}
+
+            fun build(): {{.class}} = {{.class}}(
+{{- range .fields}}
+                {{.name}} = {{if or .nullable .generic_type}}{{.name}}{{else}}checkNotNull({{.name}}) { \"{{.name}} is required\" }{{end}},
+{{- end}}
+            )
+        }
+{{- end}}
+{{- end}}
+{{- define \"builder_function\"}}
+{{- if .class}}
+
+/** Builds [{{.class}}] in the DSL of its [{{.class}}.Builder] */
+fun {{.function}}(block: {{.class}}.Builder.() -> Unit): {{.class}} = {{.class}}.Builder().apply(block).build()
+{{- end}}
+{{- end}}";
+
+/// The sealed types of the choice fields, at the end of the templates of the data classes
+const CHOICES_TEMPLATE: &str = "
+{{- define \"choices\"}}
+{{- $serialization := .serialization}}
+{{- range .choices}}
+{{- $choice := .name}}
+{{- $java_type := .java_type}}
+{{- $jaxb_elements := .jaxb_elements}}
+
+{{if eq $serialization \"kotlinx\"}}@Serializable
+{{end}}{{if eq $serialization \"jackson\"}}@JsonTypeInfo(use = JsonTypeInfo.Id.NAME)
+@JsonSubTypes(
+	{{- range .cases}}
+    JsonSubTypes.Type(value = {{$choice}}.{{.name}}::class, name = \"{{.xml_name}}\"),
+	{{- end}}
+)
+{{end}}sealed interface {{.name}} {
+
+    fun toJava(): {{.java_type}}
+	{{- range .cases}}
+
+    {{if eq $serialization \"kotlinx\"}}@Serializable
+    @SerialName(\"{{.xml_name}}\")
+    {{end}}data class {{.name}}(val value: {{.t}}) : {{$choice}} {
+        override fun toJava(): {{$java_type}} =
+            {{- if .factory_func}} {{.object_factory_class}}.factory.{{.factory_func}}(value{{if .convert}}.toJava(){{end}})
+            {{- else}} value{{if .convert}}.toJava(){{end}}
+            {{- end}}
+    }
+	{{- end}}
+
+    companion object {
+        fun fromJava(value: {{.java_type}}): {{.name}} = when {
+			{{- range .cases}}
+			{{- if .factory_func}}
+            {{if not $jaxb_elements}}value is javax.xml.bind.JAXBElement<*> && {{end}}value.name.localPart == \"{{.xml_name}}\" -> {{.name}}({{if .convert}}{{.t}}.fromJava(value.value as {{.java_type}}){{else}}value.value as {{.java_type}}{{end}})
+			{{- else}}
+            value is {{.java_type}} -> {{.name}}({{if .convert}}{{.t}}.fromJava(value){{else}}value{{end}})
+			{{- end}}
+			{{- end}}
+            else -> throw IllegalArgumentException(\"Not able to find the element of {{.name}} for '{{if $jaxb_elements}}${value.name}{{else}}${value.javaClass.name}{{end}}'\")
+        }
     }
 }
+{{- end}}
+{{- end}}";
+
+/// The kotlinx.serialization declarations of the files and of the interfaces of superclasses.
+/// Prepended to the templates of the classes
+const SERIALIZATION_TEMPLATE: &str = "
+{{- define \"contextual_serialization\"}}{{if .}}@file:UseContextualSerialization(
+    {{- range $index, $type := .}}{{if $index}}, {{end}}{{$type}}::class{{end}})
+
+{{end}}{{end}}
+{{- define \"serializers_module\"}}
+{{- if eq .serialization \"kotlinx\"}}
+{{- $suffix := .suffix}}
+
+        fun registerSerializers(builder: PolymorphicModuleBuilder<{{.java_name}}{{$suffix}}>) {
+{{- if not .is_abstract}}
+            builder.subclass({{.java_name}}Impl{{$suffix}}::class)
+{{- end}}
+{{- range .subclasses}}
+            {{.}}{{$suffix}}.registerSerializers(builder)
+{{- end}}
+        }
+
+        val serializersModule = SerializersModule {
+            polymorphic({{.java_name}}{{$suffix}}::class) { registerSerializers(this) }
+        }
+{{- end}}
+{{- end}}";
+
+/// The `DatatypeFactory` of the files that convert calendars, at the end of the templates of the
+/// classes
+const DATATYPE_FACTORY_TEMPLATE: &str = "
+{{- define \"datatype_factory\"}}
+{{- if .}}
+
+/** Creates the calendars of the Java classes. Looking the factory up is slow, so it is done once */
+private val datatypeFactory = javax.xml.datatype.DatatypeFactory.newInstance()
+{{- end}}
+{{- end}}";
 
-fn write_data_class<W: Write>(interface: KotlinClass, writer: &mut W) {
-    let output = gtmpl::template(
-        "package {{.package}}
+/// A data class, with its inner classes
+const DATA_CLASS_TEMPLATE: &str = "{{template \"contextual_serialization\" .contextual_types}}package {{.package}}
 {{range .imports}}
 import {{.package}}.{{.class}}
 {{- end}}
+{{- $suffix := .suffix}}
+{{- $serialization := .serialization}}
 
-/**
- * This file is GENERATED. Please don't change
- */
+{{template \"header\" .kdoc}}
 @Suppress(\"unused\", \"useless_cast\")
-{{if .fields}}data {{end}}class {{.kotlin_name}}(
+{{if eq $serialization \"kotlinx\"}}@Serializable
+{{end}}{{if .fields}}data {{end}}class {{.kotlin_name}}(
 	{{- range .fields}}
-    val {{.name}}: {{ .get_type_with_default }},
+	{{- template \"kdoc\" .kdoc}}
+    {{.annotation}}val {{.name}}: {{ .get_type_with_default }},
 	{{- end}}
 ) {
+{{- template \"validation\" .}}
 
     fun toJava(): {{.java_name}} = {{.java_name}}().also {
 			{{- range .fields}}
@@ -259,7 +1026,7 @@ import {{.package}}.{{.class}}
     }
 
     companion object {
-        internal val factory = ObjectFactory()
+        internal val factory = ObjectFactory(){{template \"patterns\" .patterns}}
 
         fun fromJava(javaClass: {{.java_name}}): {{.kotlin_name}} = {{.kotlin_name}}(
 			{{- range .fields}}
@@ -270,12 +1037,15 @@ import {{.package}}.{{.class}}
     }
 {{- $main_class_java_name:=.java_name}}
 {{- range .inner_classes}}
-
-    data class {{.name}}Kt(
+{{template \"kdoc\" .kdoc}}
+    {{if eq $serialization \"kotlinx\"}}@Serializable
+    {{end}}data class {{.name}}{{$suffix}}(
         {{- range .fields}}
-        val {{.name}}: {{ .get_type_with_default }},
+        {{- template \"inner_kdoc\" .kdoc}}
+        {{.annotation}}val {{.name}}: {{ .get_type_with_default }},
         {{- end}}
     ) {
+        {{- template \"inner_validation\" .}}
 
         fun toJava(): {{$main_class_java_name}}.{{.name}} = {{$main_class_java_name}}.{{.name}}().also {
                 {{- range .fields}}
@@ -284,26 +1054,32 @@ import {{.package}}.{{.class}}
         }
 
         companion object {
-            internal val factory = ObjectFactory()
+            internal val factory = ObjectFactory(){{template \"inner_patterns\" .patterns}}
 
-            fun fromJava(javaClass: {{$main_class_java_name}}.{{.name}}): {{.name}}Kt = {{.name}}Kt(
+            fun fromJava(javaClass: {{$main_class_java_name}}.{{.name}}): {{.name}}{{$suffix}} = {{.name}}{{$suffix}}(
                 {{- range .fields}}
                 {{.name}} = javaClass.{{ .from_java }},
                 {{- end}}
             )
 
         }
+        {{- template \"inner_builder\" .builder}}
     }
     {{- end}}
-}",
-        interface,
-    );
-    writer.write_all(output.unwrap().as_bytes()).unwrap();
+{{- template \"builder\" .builder}}
+}{{template \"builder_function\" .builder}}{{template \"datatype_factory\" .datatype_factory}}{{template \"choices\" .}}";
+
+fn write_data_class<W: Write>(
+    interface: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    write_template(interface, templates, "data_class", writer)
 }
 
-fn write_large_data_class<W: Write>(interface: KotlinClass, writer: &mut W) {
-    let output = gtmpl::template(
-        "package {{.package}}
+/// A data class with more fields than `fields_chunking_limit`, delegating to data classes with
+/// parts of them
+const LARGE_DATA_CLASS_TEMPLATE: &str = "package {{.package}}
 {{range .imports}}
 import {{.package}}.{{.class}}
 {{- end}}
@@ -312,20 +1088,20 @@ import {{.package}}.{{.class}}
 {{ range $index, $part := .parts }}
 private sealed interface {{$kotlin_name}}Part{{$index}} {
     {{- range .fields}}
+    {{- template \"kdoc\" .kdoc}}
     val {{.name}}: {{ .get_type }}
     {{- end}}
 }
 {{- end}}
 
-/**
- * This file is GENERATED. Please don't change
- */
+{{template \"header\" .kdoc}}
 @Suppress(\"unused\", \"useless_cast\")
 data class {{.kotlin_name}} private constructor(
 {{- range $index, $part := .parts}}
     private val part{{$index}}: Part{{$index}},
 {{- end}}
 ): {{ range $index, $part := .parts}}{{ if $index}}, {{end}}{{$kotlin_name}}Part{{$index}} by part{{$index}}{{end}} {
+{{- template \"validation\" .}}
 
     constructor(
         {{- range .parts}}
@@ -364,7 +1140,7 @@ data class {{.kotlin_name}} private constructor(
     )
 
     companion object {
-        internal val factory = ObjectFactory()
+        internal val factory = ObjectFactory(){{template \"patterns\" .patterns}}
 
         fun fromJava(javaClass: {{.java_name}}): {{.kotlin_name}} = {{.kotlin_name}}(
             {{- range $index, $part := .parts}}
@@ -400,88 +1176,285 @@ data class {{.kotlin_name}} private constructor(
         }
     }
 {{- end}}
-}",
-        interface,
-    );
-    writer.write_all(output.unwrap().as_bytes()).unwrap();
+{{- template \"builder\" .builder}}
+}{{template \"builder_function\" .builder}}{{template \"datatype_factory\" .datatype_factory}}{{template \"choices\" .}}";
+
+fn write_large_data_class<W: Write>(
+    interface: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    write_template(interface, templates, "large_data_class", writer)
 }
 
-fn write_interface<W: Write>(interface: KotlinClass, writer: &mut W) {
-    let output = gtmpl::template(
-        "package {{.package}}
+/// A service class wrapping a port type
+const INTERFACE_TEMPLATE: &str = "{{- define \"java_arguments\"}}
+		{{- range .arguments}}
+		{{if ne .mode \"IN\"}}{{.name}}Holder{{else if .header}}headers.{{.name}}{{if .convert}}?.toJava(){{end}}{{else}}{{.name}}{{if .convert}}{{if .nullable}}?{{end}}.toJava(){{end}}{{end}},
+		{{- end }}
+	)
+{{- end}}
+{{- define \"result\"}}{{.result_class}}(
+		{{- if ne .return_type \"Unit\"}}
+            returnValue = {{.return_prefix}}returnValue{{.return_suffix}},
+		{{- end}}
+		{{- range .arguments}}{{if ne .mode \"IN\"}}
+            {{.name}} = {{.name}}Holder.value{{if .convert}}?.let { {{.t}}.fromJava(it) }{{end}},
+		{{- end}}{{end}}
+        )
+{{- end}}
+{{- define \"open\"}}{{if .fault_wrapper}}{{.fault_wrapper}} { {{end}}{{end}}
+{{- define \"close\"}}{{if .fault_wrapper}} }{{end}}{{end}}package {{.package}}
 {{range .imports}}
 import {{.package}}.{{.class}}
 {{- end}}
 
-/**
- * This file is GENERATED. Please don't change
- */
+{{template \"header\" .kdoc}}
  class {{.kotlin_name}}(val portType: {{.java_name}}) {
+{{- $coroutines := .coroutines}}
+{{- $dispatcher := .coroutine_dispatcher}}
+{{- $fault_handling := .fault_handling}}
+{{- if .fault_handling}}
+{{- if eq .fault_handling \"result\"}}
+
+    private inline fun <S, reified F : Exception> result(block: () -> S): OperationResult<S, F> = try {
+        OperationResult.Success(block())
+    } catch (e: Exception) {
+        OperationResult.Fault(fault(e) as? F ?: throw e)
+    }
+{{- if .sealed_faults}}
+
+    private inline fun <S, F : Exception> result(fault: (Exception) -> F?, block: () -> S): OperationResult<S, F> = try {
+        OperationResult.Success(block())
+    } catch (e: Exception) {
+        OperationResult.Fault(fault(e) ?: throw e)
+    }
+{{- end}}
+{{- else}}
+
+    private inline fun <T> faults(block: () -> T): T = try {
+        block()
+    } catch (e: Exception) {
+        throw fault(e) ?: e
+    }
+{{- end}}
+
+    private fun fault(e: Throwable): Exception? = when (e) {
+{{- range .faults}}
+        is {{.java_name}} -> {{.kotlin_name}}.fromJava(e)
+        is {{.kotlin_name}} -> e
+{{- end}}
+        else -> null
+    }
+{{end}}
 		{{- range .functions}}
-    fun {{.name}}(
-		{{- range .arguments}}
+		{{- if .fault_class}}
+		{{- $fault_class := .fault_class}}
+
+    sealed class {{.fault_class}}(cause: Exception) : Exception(cause.message, cause) {
+		{{- range .faults}}
+        class {{.java_name}}(val fault: {{.kotlin_name}}) : {{$fault_class}}(fault)
+		{{- end}}
+    }
+
+    private fun {{.name}}Fault(e: Exception): {{.fault_class}}? = when (val fault = fault(e)) {
+		{{- range .faults}}
+        is {{.kotlin_name}} -> {{$fault_class}}.{{.java_name}}(fault)
+		{{- end}}
+        else -> null
+    }
+{{end}}
+		{{- if .headers_class}}
+
+    data class {{.headers_class}}(
+		{{- range .arguments}}{{if .header}}
+        val {{.name}}: {{.t}}? = null,
+		{{- end}}{{end}}
+    )
+		{{- end}}
+		{{- if .result_class}}
+
+    data class {{.result_class}}(
+		{{- if ne .return_type \"Unit\"}}
+        val returnValue: {{.return_type}},
+		{{- end}}
+		{{- range .arguments}}{{if ne .mode \"IN\"}}
+        val {{.name}}: {{.t}}?,
+		{{- end}}{{end}}
+    )
+		{{- end}}
+		{{- if or .headers_class .result_class}}
+{{end}}
+		{{- template \"kdoc\" .kdoc}}
+    {{if $coroutines}}suspend {{end}}fun {{.name}}(
+		{{- range .arguments}}{{if not .header}}{{if ne .mode \"OUT\"}}
 		{{ .name}}: {{.t}}{{if .nullable}}? = null{{end}},
-		{{- end }}
-	) = {{.return_type}}.fromJava(portType.{{.name}}(
+		{{- end}}{{end}}{{end}}
+		{{- if .headers_class}}
+		headers: {{.headers_class}} = {{.headers_class}}(),
+		{{- end}}
+	{{- if .result_class}}
+	): {{.declared_type}}{{if .fault_wrapper}} = {{.fault_wrapper}}{{if $coroutines}} { withContext({{$dispatcher}}){{end}}{{else if $coroutines}} = withContext({{$dispatcher}}){{end}} {
 		{{- range .arguments}}
-		{{ .name}}{{if .nullable}}?{{end}}.toJava(),
-		{{- end }}
-	))
+		{{- if eq .mode \"OUT\"}}
+        val {{.name}}Holder = Holder<{{.java_type}}>()
+		{{- else if eq .mode \"INOUT\"}}
+        val {{.name}}Holder = Holder({{.name}}{{if .convert}}.toJava(){{end}})
+		{{- end}}
+		{{- end}}
+        {{if ne .return_type \"Unit\"}}val returnValue = {{end}}portType.{{.name}}({{template \"java_arguments\" .}}
+        {{if not (or $coroutines .fault_wrapper)}}return {{end}}{{template \"result\" .}}
+    }{{if and .fault_wrapper $coroutines}} }{{end}}
+	{{- else if not $coroutines}}
+	){{if .fault_wrapper}}: {{.declared_type}}{{end}} = {{template \"open\" .}}{{.return_prefix}}portType.{{.name}}({{template \"java_arguments\" .}}{{.return_suffix}}{{template \"close\" .}}
+	{{- else if .async_name}}
+	): {{.declared_type}} = {{template \"open\" .}}suspendCancellableCoroutine { continuation ->
+        val future = portType.{{.async_name}}({{template \"java_arguments\" .}} { response ->
+            try {
+                continuation.resume({{.return_prefix}}response.get(){{.return_suffix}})
+            } catch (e: ExecutionException) {
+                continuation.resumeWithException({{if $fault_handling}}(e.cause ?: e).let { fault(it) ?: it }{{else}}e.cause ?: e{{end}})
+            } catch (e: Throwable) {
+                continuation.resumeWithException({{if $fault_handling}}fault(e) ?: e{{else}}e{{end}})
+            }
+        }
+        continuation.invokeOnCancellation { future.cancel(true) }
+    }{{template \"close\" .}}
+	{{- else}}
+	): {{.declared_type}} = {{template \"open\" .}}{{.return_prefix}}withContext({{$dispatcher}}) {
+        portType.{{.name}}({{template \"java_arguments\" .}}
+    }{{.return_suffix}}{{template \"close\" .}}
+	{{- end}}
 		{{end}}
 }
-",
-        interface,
-    );
-    writer.write_all(output.unwrap().as_bytes()).unwrap();
+";
+
+fn write_interface<W: Write>(
+    interface: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    write_template(interface, templates, "interface", writer)
+}
+
+/// The Kotlin exception of a fault exception
+const FAULT_TEMPLATE: &str = "package {{.package}}
+{{range .imports}}
+import {{.package}}.{{.class}}
+{{- end}}
+
+{{template \"header\" .kdoc}}
+class {{.kotlin_name}}(
+    message: String?,
+    val faultInfo: {{.fault_info}}?,
+    cause: Throwable? = null,
+) : Exception(message, cause) {
+    companion object {
+        fun fromJava(javaException: {{.java_name}}) = {{.kotlin_name}}(
+            javaException.message,
+            javaException.faultInfo?.let { {{.fault_info}}.fromJava(it) },
+            javaException,
+        )
+    }
 }
+";
 
-fn write_enum<W: Write>(kotlin_enum: KotlinClass, writer: &mut W) {
-    let output = gtmpl::template(
-        "package {{.package}}
+fn write_fault<W: Write>(fault: KotlinClass, templates: &Templates, writer: &mut W) -> Result<()> {
+    write_template(fault, templates, "fault", writer)
+}
 
+/// The `QName` and creator of each root element of an `ObjectFactory`
+const OBJECT_FACTORY_TEMPLATE: &str = "package {{.package}}
 {{range .imports}}
 import {{.package}}.{{.class}}
 {{- end}}
-/**
- * This file is GENERATED. Please don't change
- */
-enum class {{.kotlin_name}}(val value: {{.java_name}}) {
+
+{{template \"header\" .kdoc}}
+object {{.kotlin_name}} {
+    private val factory = {{.java_name}}()
+{{range .element_factories}}
+    val {{.qname}} = QName(\"{{.namespace}}\", \"{{.xml_name}}\")
+
+    fun {{.method}}(value: {{.t}}): JAXBElement<{{.java_type}}> = factory.{{.method}}(value{{if .convert}}.toJava(){{end}})
+{{end}}}
+";
+
+fn write_object_factory<W: Write>(
+    factory: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    write_template(factory, templates, "object_factory", writer)
+}
+
+/// An enum class
+const ENUM_TEMPLATE: &str = "package {{.package}}
+
+{{range .imports}}
+import {{.package}}.{{.class}}
+{{- end}}
+{{template \"header\" .kdoc}}
+enum class {{.kotlin_name}}(val value: {{.java_name}}{{if .unknown_enum_constant}}?{{end}}, val xmlValue: String) {
 	{{- $javaname := .java_name}}
 		{{- range .enum_constants}}
-    {{.}}({{$javaname}}.{{.}}),
+		{{- template \"kdoc\" .kdoc}}
+    {{.name}}({{$javaname}}.{{.name}}, \"{{.xml_value}}\"),
 		{{- end}}
+{{- if .unknown_enum_constant}}
+    /** A value missing from the Java enum, like one the service added later */
+    UNKNOWN(null, \"\"),
+{{- end}}
     ;
 
-	fun toJava(): {{.java_name}} = {{.java_name}}.valueOf(this.name)
+	fun toJava(): {{.java_name}} = value{{if .unknown_enum_constant}} ?: throw IllegalStateException(\"{{.kotlin_name}}.$name has no Java value\"){{end}}
 
     companion object {
-        fun fromJava(value: {{.java_name}}) = values().find { it.value == value }!!
-    }
-}",
-        kotlin_enum,
-    );
+{{- if .unknown_enum_constant}}
+        fun fromJava(value: {{.java_name}}?): {{.kotlin_name}} = fromJavaOrNull(value) ?: UNKNOWN
 
-    writer.write_all(output.unwrap().as_bytes()).unwrap();
-}
+        fun fromJavaOrNull(value: {{.java_name}}?): {{.kotlin_name}}? = values().find { it != UNKNOWN && it.value == value }
+
+        fun fromXmlValue(value: String): {{.kotlin_name}} = values().find { it != UNKNOWN && it.xmlValue == value } ?: UNKNOWN
+{{- else}}
+        fun fromJava(value: {{.java_name}}): {{.kotlin_name}} =
+            fromJavaOrNull(value) ?: throw IllegalArgumentException(\"No {{.kotlin_name}} for $value\")
 
-fn write_abstract_class<W: Write>(kotlin_enum: KotlinClass, writer: &mut W) {
-    // gtmpl_fn!(
-    // fn add(a: u64, b: u64) -> Result<u64, FuncError> {
-    //     Ok(a + b)
-    // });
+        fun fromJavaOrNull(value: {{.java_name}}?): {{.kotlin_name}}? = values().find { it.value == value }
 
-    let output = gtmpl::template(
-        "package {{.package}}
+        fun fromXmlValue(value: String): {{.kotlin_name}}? = values().find { it.xmlValue == value }
+{{- end}}
+    }
+}";
+
+fn write_enum<W: Write>(
+    kotlin_enum: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    write_template(kotlin_enum, templates, "enum", writer)
+}
+
+/// The interface of an abstract class, implemented by the classes of its subclasses
+const ABSTRACT_CLASS_TEMPLATE: &str = "{{template \"contextual_serialization\" .contextual_types}}package {{.package}}
 {{ range .imports}}
 import {{.package}}.{{.class}}
 {{- end}}
-
-/**
- * This file is GENERATED. Please don't change
- */
-interface {{.java_name}}Kt {{if .java_superclass}}: {{.java_superclass.name}} {{end}}{
+{{- $suffix := .suffix}}
+{{- $serialization := .serialization}}
+
+{{template \"header\" .kdoc}}
+{{- if eq $serialization \"jackson\"}}
+@JsonTypeInfo(use = JsonTypeInfo.Id.NAME)
+@JsonSubTypes(
+	{{- range .subclasses}}
+    JsonSubTypes.Type({{.}}{{$suffix}}::class),
+	{{- end}}
+)
+{{- end}}
+{{if .is_sealed}}sealed {{end}}interface {{.java_name}}{{$suffix}} {{if .java_superclass}}: {{.java_superclass.name}} {{end}}{
 
 	{{- range .fields}}
+	{{- template \"kdoc\" .kdoc}}
     val {{.name}}: {{ .get_type }}
 	{{- end}}
 
@@ -491,22 +1464,26 @@ interface {{.java_name}}Kt {{if .java_superclass}}: {{.java_superclass.name}} {{
 
         internal val factory = ObjectFactory()
 
-        fun fromJava(javaClass: {{.java_name}}): {{.java_name}}Kt = when (javaClass) {
+        fun fromJava(javaClass: {{.java_name}}): {{.java_name}}{{$suffix}} = when (javaClass) {
 			{{- range .subclasses}}
-            is {{.}} -> {{.}}Kt.fromJava(javaClass)
+            is {{.}} -> {{.}}{{$suffix}}.fromJava(javaClass)
 			{{- end}}
             else -> throw IllegalStateException(\"Not able to find implementation for class '${javaClass.javaClass.name}'\")
         }
+{{- template \"serializers_module\" .}}
 
     }
 {{- $main_class_java_name:=.java_name}}
 {{- range .inner_classes}}
-
-    data class {{.name}}Kt(
+{{template \"kdoc\" .kdoc}}
+    {{if eq $serialization \"kotlinx\"}}@Serializable
+    {{end}}data class {{.name}}{{$suffix}}(
         {{- range .fields}}
-        val {{.name}}: {{ .get_type_with_default }},
+        {{- template \"inner_kdoc\" .kdoc}}
+        {{.annotation}}val {{.name}}: {{ .get_type_with_default }},
         {{- end}}
     ) {
+        {{- template \"inner_validation\" .}}
 
         fun toJava(): {{$main_class_java_name}}.{{.name}} = {{$main_class_java_name}}.{{.name}}().also {
                 {{- range .fields}}
@@ -515,37 +1492,51 @@ interface {{.java_name}}Kt {{if .java_superclass}}: {{.java_superclass.name}} {{
         }
 
         companion object {
-            internal val factory = ObjectFactory()
+            internal val factory = ObjectFactory(){{template \"inner_patterns\" .patterns}}
 
-            fun fromJava(javaClass: {{$main_class_java_name}}.{{.name}}): {{.name}}Kt = {{.name}}Kt(
+            fun fromJava(javaClass: {{$main_class_java_name}}.{{.name}}): {{.name}}{{$suffix}} = {{.name}}{{$suffix}}(
                 {{- range .fields}}
                 {{.name}} = javaClass.{{ .from_java }},
                 {{- end}}
             )
 
         }
+        {{- template \"inner_builder\" .builder}}
     }
     {{- end}}
-}",
-        kotlin_enum,
-    );
-
-    writer.write_all(output.unwrap().as_bytes()).unwrap();
+}{{template \"datatype_factory\" .datatype_factory}}{{template \"choices\" .}}";
+
+fn write_abstract_class<W: Write>(
+    kotlin_enum: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    write_template(kotlin_enum, templates, "abstract_class", writer)
 }
 
-fn write_open_superclass<W: Write>(kotlin_enum: KotlinClass, writer: &mut W) {
-    let output = gtmpl::template(
-        "package {{.package}}
+/// The interface of a superclass that is not abstract, and the `Impl` data class of its own
+/// instances
+const OPEN_SUPERCLASS_TEMPLATE: &str = "{{template \"contextual_serialization\" .contextual_types}}package {{.package}}
 {{ range .imports}}
 import {{.package}}.{{.class}}
 {{- end}}
-
-/**
- * This file is GENERATED. Please don't change
- */
-interface {{.java_name}}Kt {{if .java_superclass}}: {{.java_superclass.name}} {{end}}{
+{{- $suffix := .suffix}}
+{{- $serialization := .serialization}}
+
+{{template \"header\" .kdoc}}
+{{- if eq $serialization \"jackson\"}}
+@JsonTypeInfo(use = JsonTypeInfo.Id.NAME)
+@JsonSubTypes(
+    JsonSubTypes.Type({{.java_name}}Impl{{$suffix}}::class),
+	{{- range .subclasses}}
+    JsonSubTypes.Type({{.}}{{$suffix}}::class),
+	{{- end}}
+)
+{{- end}}
+interface {{.java_name}}{{$suffix}} {{if .java_superclass}}: {{.java_superclass.name}} {{end}}{
 
 	{{- range .fields}}
+	{{- template \"kdoc\" .kdoc}}
     val {{.name}}: {{ .get_type }}
 	{{- end}}
 
@@ -565,11 +1556,11 @@ interface {{.java_name}}Kt {{if .java_superclass}}: {{.java_superclass.name}} {{
 
         internal val factory = ObjectFactory()
 
-        fun fromJava(javaClass: {{.java_name}}): {{.java_name}}Kt = when (javaClass) {
+        fun fromJava(javaClass: {{.java_name}}): {{.java_name}}{{$suffix}} = when (javaClass) {
 			{{- range .subclasses}}
-            is {{.}} -> {{.}}Kt.fromJava(javaClass)
+            is {{.}} -> {{.}}{{$suffix}}.fromJava(javaClass)
 			{{- end}}
-            else -> {{.java_name}}ImplKt(
+            else -> {{.java_name}}Impl{{$suffix}}(
 			    {{- if .java_superclass}}
 				{{- range .java_superclass.fields}}
                 {{.name}} = javaClass.{{ .from_java }},
@@ -580,46 +1571,65 @@ interface {{.java_name}}Kt {{if .java_superclass}}: {{.java_superclass.name}} {{
 				{{- end}}
             )
         }
+{{- template \"serializers_module\" .}}
 
     }
 }
 
 @Suppress(\"unused\", \"useless_cast\")
-{{if .fields}}data {{end}}class {{.java_name}}ImplKt(
+{{if eq $serialization \"kotlinx\"}}@Serializable
+@SerialName(\"{{.java_name}}\")
+{{end}}{{if eq $serialization \"jackson\"}}@JsonTypeName(\"{{.java_name}}\")
+{{end}}{{if .fields}}data {{end}}class {{.java_name}}Impl{{$suffix}}(
 	{{- if .java_superclass}}
 	{{- range .java_superclass.fields}}
-    override val {{.name}}: {{ .get_type_with_default }},
+    {{.annotation}}override val {{.name}}: {{ .get_type_with_default }},
 	{{- end}}
 	{{- end}}
 	{{- range .fields}}
-    override val {{.name}}: {{ .get_type_with_default }},
+    {{.annotation}}override val {{.name}}: {{ .get_type_with_default }},
 	{{- end}}
-) : {{.java_name}}Kt",
-        kotlin_enum,
-    );
+) : {{.java_name}}{{$suffix}}{{if or .validation .builder.class}} {
+{{- template \"validation\" .}}
+{{- if .patterns}}
 
-    writer.write_all(output.unwrap().as_bytes()).unwrap();
+    companion object {
+{{- template \"patterns\" .patterns}}
+    }
+{{- end}}
+{{- template \"builder\" .builder}}
+}{{end}}{{template \"builder_function\" .builder}}{{template \"datatype_factory\" .datatype_factory}}{{template \"choices\" .}}";
+
+fn write_open_superclass<W: Write>(
+    kotlin_enum: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    write_template(kotlin_enum, templates, "open_superclass", writer)
 }
 
-fn write_subclass<W: Write>(kotlin_enum: KotlinClass, writer: &mut W) {
-    let output = gtmpl::template(
-        "package {{.package}}
+/// A data class implementing the interface of its superclass
+const SUBCLASS_TEMPLATE: &str = "{{template \"contextual_serialization\" .contextual_types}}package {{.package}}
 {{ range .imports}}
 import {{.package}}.{{.class}}
 {{- end}}
+{{- $serialization := .serialization}}
 
-/**
- * This file is GENERATED. Please don't change
- */
+{{template \"header\" .kdoc}}
 @Suppress(\"unused\", \"useless_cast\")
-data class {{.kotlin_name}}(
+{{if eq $serialization \"kotlinx\"}}@Serializable
+@SerialName(\"{{.java_name}}\")
+{{end}}{{if eq $serialization \"jackson\"}}@JsonTypeName(\"{{.java_name}}\")
+{{end}}data class {{.kotlin_name}}(
 	{{- range .java_superclass.fields}}
-    override val {{.name}}: {{ .get_type_with_default }},
+    {{.annotation}}override val {{.name}}: {{ .get_type_with_default }},
 	{{- end}}
 	{{- range .fields}}
-    val {{.name}}: {{ .get_type_with_default }},
+	{{- template \"kdoc\" .kdoc}}
+    {{.annotation}}val {{.name}}: {{ .get_type_with_default }},
 	{{- end}}
 ) : {{.java_superclass.name}} {
+{{- template \"validation\" .}}
 
     override fun toJava(): {{.java_name}} = {{.java_name}}()
         .also {
@@ -633,7 +1643,7 @@ data class {{.kotlin_name}}(
 
     companion object {
 
-        internal val factory = ObjectFactory()
+        internal val factory = ObjectFactory(){{template \"patterns\" .patterns}}
 
         fun fromJava(javaClass: {{.java_name}}): {{.kotlin_name}} = {{.kotlin_name}}(
 			{{- range .java_superclass.fields}}
@@ -643,18 +1653,212 @@ data class {{.kotlin_name}}(
             {{.name}} = javaClass.{{ .from_java }},
 			{{- end}}
         )
+{{- if eq $serialization \"kotlinx\"}}
+
+        fun registerSerializers(builder: PolymorphicModuleBuilder<{{.kotlin_name}}>) {
+            builder.subclass({{.kotlin_name}}::class)
+        }
+{{- end}}
 
     }
-}",
-        kotlin_enum,
-    );
+{{- template \"builder\" .builder}}
+}{{template \"builder_function\" .builder}}{{template \"datatype_factory\" .datatype_factory}}{{template \"choices\" .}}";
+
+fn write_subclass<W: Write>(
+    kotlin_enum: KotlinClass,
+    templates: &Templates,
+    writer: &mut W,
+) -> Result<()> {
+    write_template(kotlin_enum, templates, "subclass", writer)
+}
 
-    writer.write_all(output.unwrap().as_bytes()).unwrap();
+/// The embedded templates, by the name of the file in the `templates` folder that replaces them.
+/// The first ones only define the templates that the others use
+const DEFAULT_TEMPLATES: [(&str, &str); 15] = [
+    ("kdoc", KDOC_TEMPLATE),
+    ("validation", VALIDATION_TEMPLATE),
+    ("builder", BUILDER_TEMPLATE),
+    ("choices", CHOICES_TEMPLATE),
+    ("serialization", SERIALIZATION_TEMPLATE),
+    ("datatype_factory", DATATYPE_FACTORY_TEMPLATE),
+    ("data_class", DATA_CLASS_TEMPLATE),
+    ("large_data_class", LARGE_DATA_CLASS_TEMPLATE),
+    ("interface", INTERFACE_TEMPLATE),
+    ("fault", FAULT_TEMPLATE),
+    ("object_factory", OBJECT_FACTORY_TEMPLATE),
+    ("enum", ENUM_TEMPLATE),
+    ("abstract_class", ABSTRACT_CLASS_TEMPLATE),
+    ("open_superclass", OPEN_SUPERCLASS_TEMPLATE),
+    ("subclass", SUBCLASS_TEMPLATE),
+];
+
+/// The templates that are parsed before the template of each class
+const PARTIALS: [&str; 6] = [
+    "kdoc",
+    "validation",
+    "builder",
+    "choices",
+    "serialization",
+    "datatype_factory",
+];
+
+/// The functions that templates can call, like `{{to_java .}}` for a field
+const TEMPLATE_FUNCS: [(&str, Func); 6] = [
+    ("to_java", to_java as Func),
+    ("from_java", from_java as Func),
+    ("get_type", get_type as Func),
+    ("get_type_with_default", get_type_with_default as Func),
+    ("boolean_getter", boolean_getter_func as Func),
+    ("kdoc_text", kdoc_text as Func),
+];
+
+/// The names of the templates, partials first
+pub fn template_names() -> impl Iterator<Item = &'static str> {
+    DEFAULT_TEMPLATES.iter().map(|(name, _)| *name)
 }
 
-fn to_java(args: &[Value]) -> Result<Value, FuncError> {
+/// Parses a template from the `templates` folder, with the partials if it is the template of a class
+pub fn check_template(templates: &Templates, name: &str) -> Result<()> {
+    let parsed = if PARTIALS.contains(&name) {
+        parse_templates(templates, &[name])
+    } else {
+        parse_template(templates, name)
+    };
+    parsed
+        .map(|_| ())
+        .map_err(|error| Error::config(format!("template {} failed: {}", name, error.message)))
+}
+
+fn write_template<W: Write>(
+    kotlin_class: KotlinClass,
+    templates: &Templates,
+    name: &str,
+    writer: &mut W,
+) -> Result<()> {
+    let template = parse_template(templates, name)?;
+    let output = template
+        .render(&Context::from(kotlin_class))
+        .map_err(TemplateError::from);
+    write_output(output, writer)
+}
+
+/// The template of a class after the partials, each of them from `templates` or else embedded
+fn parse_template(templates: &Templates, name: &str) -> Result<Template> {
+    let names: Vec<&str> = PARTIALS.iter().copied().chain([name]).collect();
+    parse_templates(templates, &names)
+}
+
+fn parse_templates(templates: &Templates, names: &[&str]) -> Result<Template> {
+    let text = |name: &str| match templates.get(name) {
+        Some(template) => template.clone(),
+        None => DEFAULT_TEMPLATES
+            .iter()
+            .find(|(default_name, _)| *default_name == name)
+            .map_or(String::new(), |(_, template)| template.to_string()),
+    };
+    let parse_error = |why: String| Error::write(why);
+
+    // Parsed apart, so that the errors tell the file and the line in it
+    let (name, partials) = names.split_last().unwrap();
+    let mut template = Template::with_name(format!("{}.tmpl", name));
+    template.add_funcs(&TEMPLATE_FUNCS);
+    for partial in partials {
+        template
+            .add_template(format!("{}.tmpl", partial), text(partial))
+            .map_err(|why| parse_error(why.to_string()))?;
+    }
+    template
+        .parse(text(name))
+        .map_err(|why| parse_error(why.to_string()))?;
+    Ok(template)
+}
+
+fn write_output<W: Write>(
+    output: std::result::Result<String, TemplateError>,
+    writer: &mut W,
+) -> Result<()> {
+    let output = output.map_err(|why| Error::write(format!("template failed: {}", why)))?;
+    writer
+        .write_all(output.as_bytes())
+        .map_err(|why| Error::write(why.to_string()))
+}
+
+fn to_java(args: &[Value]) -> std::result::Result<Value, FuncError> {
     let field = to_field(args);
 
+    if !field.map_entry.is_empty() {
+        let entry = format!(
+            "{n}.map {{ (key, value) -> {}().also {{ it.{}; it.{} }} }}",
+            field.map_entry,
+            call(to_java, &field.map_fields[0]),
+            call(to_java, &field.map_fields[1]),
+            n = field.name
+        );
+        if field.use_getter {
+            return Ok(format!("{}.addAll({})", field.name, entry).into());
+        } else {
+            return Ok(format!("{} = {}", field.name, entry).into());
+        }
+    }
+
+    if !field.type_arguments.is_empty() && field.convert {
+        let value = type_to_java(&field, &field.name, 0);
+        if field.use_getter && field.generic_type == "Map" {
+            return Ok(format!("{}.putAll({})", field.name, value).into());
+        } else if field.use_getter && field.generic_type != "JAXBElement" {
+            return Ok(format!("{}.addAll({})", field.name, value).into());
+        } else {
+            return Ok(format!("{} = {}", field.name, value).into());
+        }
+    }
+
+    // The other attributes of @XmlAnyAttribute can only be put into the map of the getter
+    if field.use_getter && field.generic_type == "Map" {
+        return Ok(format!("{n}.putAll({n})", n = field.name).into());
+    }
+
+    if !field.time_type.is_empty() {
+        let to_calendar = calendar_to_java(&field.time_type, "elem");
+        if field.generic_type == "JAXBElement" {
+            return Ok(format!(
+                "{n} = {n}?.let {{ elem -> {}.factory.{}({}) }}",
+                field.object_factory_class,
+                field.factory_func,
+                to_calendar,
+                n = field.name
+            )
+            .into());
+        } else if !field.generic_type.is_empty() && field.use_getter {
+            return Ok(format!(
+                "{n}.addAll({n}.map {{ elem -> {} }})",
+                to_calendar,
+                n = field.name
+            )
+            .into());
+        } else if !field.generic_type.is_empty() {
+            return Ok(format!(
+                "{n} = {n}.map {{ elem -> {} }}",
+                to_calendar,
+                n = field.name
+            )
+            .into());
+        } else if field.nullable {
+            return Ok(format!(
+                "{n} = {n}?.let {{ elem -> {} }}",
+                to_calendar,
+                n = field.name
+            )
+            .into());
+        } else {
+            return Ok(format!(
+                "{n} = {}",
+                calendar_to_java(&field.time_type, &field.name),
+                n = field.name
+            )
+            .into());
+        }
+    }
+
     if field.generic_type == "JAXBElement" {
         if field.convert {
             return Ok(format!(
@@ -712,9 +1916,68 @@ fn boolean_getter(variable_name: &str) -> String {
     )
 }
 
-fn from_java(args: &[Value]) -> Result<Value, FuncError> {
+/// `boolean_getter` for templates, taking a field or the name of a property
+fn boolean_getter_func(args: &[Value]) -> std::result::Result<Value, FuncError> {
+    let name = match args.first() {
+        Some(Value::Object(field)) => field.get("name"),
+        value => value,
+    };
+    match name {
+        Some(Value::String(name)) if !name.is_empty() => Ok(boolean_getter(name).into()),
+        _ => Err(FuncError::Generic(
+            "boolean_getter requires a field or a property name".to_string(),
+        )),
+    }
+}
+
+/// `kdoc_text` for templates, escaping a line of documentation for a KDoc comment. Kotlin block
+/// comments nest, so `/*` in the text would need a `*/` of its own, and `*/` would end the KDoc
+fn kdoc_text(args: &[Value]) -> std::result::Result<Value, FuncError> {
+    match args.first() {
+        Some(Value::String(line)) => {
+            Ok(line.replace("/*", "/&#42;").replace("*/", "*&#47;").into())
+        }
+        _ => Err(FuncError::Generic(
+            "kdoc_text requires a line of text".to_string(),
+        )),
+    }
+}
+
+fn from_java(args: &[Value]) -> std::result::Result<Value, FuncError> {
     let field = to_field(args);
 
+    // A key that is missing or repeated fails, rather than losing entries
+    if !field.map_entry.is_empty() {
+        return Ok(format!(
+            "{n}?.let {{ entries -> entries.associate {{ entry -> requireNotNull(entry.{}) {{ \"{n} has an entry without a key\" }} to entry.{} }}.also {{ require(it.size == entries.size) {{ \"{n} has entries with the same key\" }} }} }} ?: emptyMap()",
+            call(from_java, &field.map_fields[0]),
+            call(from_java, &field.map_fields[1]),
+            n = field.name
+        )
+        .into());
+    }
+
+    if !field.type_arguments.is_empty() && field.convert {
+        return Ok(type_from_java(&field, &field.name, 0).into());
+    }
+
+    if !field.time_type.is_empty() {
+        let from_calendar = calendar_from_java(&field.time_type, "it");
+        if field.generic_type == "JAXBElement" {
+            return Ok(format!("{}?.value?.let {{ {} }}", field.name, from_calendar).into());
+        } else if !field.generic_type.is_empty() {
+            return Ok(format!(
+                "{}?.map {{ {} }} ?: empty{}()",
+                field.name, from_calendar, field.generic_type
+            )
+            .into());
+        } else if field.nullable {
+            return Ok(format!("{}?.let {{ {} }}", field.name, from_calendar).into());
+        } else {
+            return Ok(format!("{}.let {{ {} }}", field.name, from_calendar).into());
+        }
+    }
+
     if field.generic_type == "JAXBElement" {
         if field.convert {
             return Ok(format!(
@@ -763,7 +2026,156 @@ fn from_java(args: &[Value]) -> Result<Value, FuncError> {
     }
 }
 
-fn get_type(args: &[Value]) -> Result<Value, FuncError> {
+/// The message of the check that converting the keys of a map kept them apart
+const SAME_KEYS: &str = "Two keys of the map convert to the same key";
+
+/// Converts `value` of a nested generic type, or of one of its type arguments, to Java.
+/// The lambda parameters are numbered by the `depth` of the type argument they convert
+fn type_to_java(field: &Field, value: &str, depth: usize) -> String {
+    let parameter = |name: &str| match depth {
+        0 => name.to_string(),
+        _ => format!("{}{}", name, depth),
+    };
+    let argument = |index: usize, value: &str| {
+        field
+            .type_arguments
+            .get(index)
+            .map_or(value.to_string(), |argument| {
+                type_to_java(argument, value, depth + 1)
+            })
+    };
+    match field.generic_type.as_str() {
+        "" if field.convert => format!("{}.toJava()", value),
+        "" => value.to_string(),
+        "JAXBElement" => {
+            let elem = parameter("elem");
+            let factory = format!(
+                "{}.factory.{}",
+                field.object_factory_class, field.factory_func
+            );
+            match argument(0, &elem) {
+                converted if converted == elem => format!("{}({})", factory, value),
+                converted => format!(
+                    "{}?.let {{ {} -> {}({}) }}",
+                    value, elem, factory, converted
+                ),
+            }
+        }
+        "Map" => {
+            let (key, val) = (parameter("key"), parameter("value"));
+            match (argument(0, &key), argument(1, &val)) {
+                (k, v) if k == key && v == val => value.to_string(),
+                (k, v) if k == key => format!("{}.mapValues {{ (_, {}) -> {} }}", value, val, v),
+                (k, v) => format!(
+                    "{}.entries.associate {{ ({}, {}) -> {} to {} }}.also {{ require(it.size == {}.size) {{ \"{}\" }} }}",
+                    value, key, val, k, v, value, SAME_KEYS
+                ),
+            }
+        }
+        _ => {
+            let elem = parameter("elem");
+            match argument(0, &elem) {
+                converted if converted == elem => value.to_string(),
+                converted => format!("{}.map {{ {} -> {} }}", value, elem, converted),
+            }
+        }
+    }
+}
+
+/// Converts `value` of a nested generic type, or of one of its type arguments, from Java
+fn type_from_java(field: &Field, value: &str, depth: usize) -> String {
+    let parameter = |name: &str| match depth {
+        0 => name.to_string(),
+        _ => format!("{}{}", name, depth),
+    };
+    let argument = |index: usize, value: &str| {
+        field
+            .type_arguments
+            .get(index)
+            .map_or(value.to_string(), |argument| {
+                type_from_java(argument, value, depth + 1)
+            })
+    };
+    match field.generic_type.as_str() {
+        "" if field.convert => format!("{t}.fromJava({}) as {t}", value, t = field.r#type),
+        "" => value.to_string(),
+        "JAXBElement" => {
+            let elem = parameter("elem");
+            match argument(0, &elem) {
+                converted if converted == elem => format!("{}?.value", value),
+                converted => format!("{}?.value?.let {{ {} -> {} }}", value, elem, converted),
+            }
+        }
+        "Map" => {
+            let (key, val) = (parameter("key"), parameter("value"));
+            match (argument(0, &key), argument(1, &val)) {
+                (k, v) if k == key && v == val => format!("{} ?: emptyMap()", value),
+                (k, v) if k == key => format!(
+                    "{}?.mapValues {{ (_, {}) -> {} }} ?: emptyMap()",
+                    value, val, v
+                ),
+                (k, v) => format!(
+                    "{}?.let {{ {m} -> {m}.entries.associate {{ ({}, {}) -> {} to {} }}.also {{ require(it.size == {m}.size) {{ \"{}\" }} }} }} ?: emptyMap()",
+                    value,
+                    key,
+                    val,
+                    k,
+                    v,
+                    SAME_KEYS,
+                    m = parameter("map")
+                ),
+            }
+        }
+        _ => {
+            let elem = parameter("elem");
+            match argument(0, &elem) {
+                converted if converted == elem => {
+                    format!("{} ?: empty{}()", value, field.generic_type)
+                }
+                converted => format!(
+                    "{}?.map {{ {} -> {} }} ?: empty{}()",
+                    value, elem, converted, field.generic_type
+                ),
+            }
+        }
+    }
+}
+
+/// Converts the `XMLGregorianCalendar` in `value` to the java.time type. A `dateTime` without a
+/// timezone gets the default timezone of the JVM as `OffsetDateTime` or `Instant`
+fn calendar_from_java(time_type: &str, value: &str) -> String {
+    match time_type {
+        "LocalDate" => format!("LocalDate.of({v}.year, {v}.month, {v}.day)", v = value),
+        "LocalDateTime" => format!(
+            "{}.toGregorianCalendar().toZonedDateTime().toLocalDateTime()",
+            value
+        ),
+        "OffsetDateTime" => format!(
+            "{}.toGregorianCalendar().toZonedDateTime().toOffsetDateTime()",
+            value
+        ),
+        _ => format!("{}.toGregorianCalendar().toInstant()", value),
+    }
+}
+
+/// Converts the java.time value in `value` to an `XMLGregorianCalendar`, through its XSD lexical form
+fn calendar_to_java(time_type: &str, value: &str) -> String {
+    let lexical = match time_type {
+        "LocalDateTime" => format!(
+            "java.time.format.DateTimeFormatter.ISO_LOCAL_DATE_TIME.format({})",
+            value
+        ),
+        "OffsetDateTime" => format!(
+            "java.time.format.DateTimeFormatter.ISO_OFFSET_DATE_TIME.format({})",
+            value
+        ),
+        // LocalDate and Instant always print all of their fields
+        _ => format!("{}.toString()", value),
+    };
+    format!("datatypeFactory.newXMLGregorianCalendar({})", lexical)
+}
+
+fn get_type(args: &[Value]) -> std::result::Result<Value, FuncError> {
     let field = to_field(args);
     if field.generic_type == "JAXBElement" || (field.nullable && field.generic_type == "") {
         return Ok(format!("{}?", field.r#type).into());
@@ -773,7 +2185,7 @@ fn get_type(args: &[Value]) -> Result<Value, FuncError> {
     return Ok(field.r#type.into());
 }
 
-fn get_type_with_default(args: &[Value]) -> Result<Value, FuncError> {
+fn get_type_with_default(args: &[Value]) -> std::result::Result<Value, FuncError> {
     let field = to_field(args);
     if field.generic_type == "JAXBElement" || (field.nullable && field.generic_type == "") {
         return Ok(format!("{}? = null", field.r#type).into());
@@ -788,6 +2200,22 @@ fn get_type_with_default(args: &[Value]) -> Result<Value, FuncError> {
     return Ok(field.r#type.into());
 }
 
+/// The field itself, or the innermost type arguments of a nested generic type
+fn type_leaves(field: &Field) -> Vec<&Field> {
+    if field.type_arguments.is_empty() {
+        return vec![field];
+    }
+    field.type_arguments.iter().flat_map(type_leaves).collect()
+}
+
+/// The output of a template function for a field, for expressions built from other fields
+fn call(func: Func, field: &Field) -> String {
+    match func(&[field.clone().into()]) {
+        Ok(Value::String(output)) => output,
+        _ => String::new(),
+    }
+}
+
 fn to_field(args: &[Value]) -> Field {
     if let Value::Object(ref field) = &args[0] {
         if let Some(Value::String(ref name)) = field.get("name") {
@@ -805,23 +2233,43 @@ fn to_field(args: &[Value]) -> Field {
                                         if let Some(Value::String(ref factory_func)) =
                                             field.get("factory_func")
                                         {
-                                            return Field {
-                                                name: name.to_string(),
-                                                r#type: t.to_string(),
-                                                generic_type: generic_type.to_string(),
-                                                convert: *convert,
-                                                nullable: *nullable,
-                                                factory_func: factory_func.to_string(),
-                                                object_factory_class: object_factory_class
-                                                    .to_string(),
-                                                object_factory_package: object_factory_package
-                                                    .to_string(),
-                                                use_getter: *use_getter,
-                                                get_type,
-                                                get_type_with_default,
-                                                to_java,
-                                                from_java,
-                                            };
+                                            if let Some(Value::String(ref time_type)) =
+                                                field.get("time_type")
+                                            {
+                                                if let Some(Value::String(ref map_entry)) =
+                                                    field.get("map_entry")
+                                                {
+                                                    return Field {
+                                                        name: name.to_string(),
+                                                        r#type: t.to_string(),
+                                                        generic_type: generic_type.to_string(),
+                                                        convert: *convert,
+                                                        nullable: *nullable,
+                                                        factory_func: factory_func.to_string(),
+                                                        object_factory_class: object_factory_class
+                                                            .to_string(),
+                                                        object_factory_package:
+                                                            object_factory_package.to_string(),
+                                                        use_getter: *use_getter,
+                                                        time_type: time_type.to_string(),
+                                                        map_entry: map_entry.to_string(),
+                                                        map_fields: map_fields(
+                                                            field.get("map_fields"),
+                                                        ),
+                                                        type_arguments: map_fields(
+                                                            field.get("type_arguments"),
+                                                        ),
+                                                        xml_name: "".to_string(),
+                                                        kdoc: vec![],
+                                                        checks: vec![],
+                                                        annotation: "".to_string(),
+                                                        get_type,
+                                                        get_type_with_default,
+                                                        to_java,
+                                                        from_java,
+                                                    };
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -843,86 +2291,512 @@ fn to_field(args: &[Value]) -> Field {
         object_factory_package: "".to_string(),
         nullable: false,
         use_getter: false,
+        time_type: "".to_string(),
+        map_entry: "".to_string(),
+        map_fields: vec![],
+        type_arguments: vec![],
+        xml_name: "".to_string(),
+        kdoc: vec![],
+        checks: vec![],
+        annotation: "".to_string(),
         get_type,
         get_type_with_default,
         to_java,
         from_java,
     }
-    // Err(anyhow!("integer required, got: {:?}", args))
-}
+    // Err(anyhow!("integer required, got: {:?}", args))
+}
+
+fn map_fields(value: Option<&Value>) -> Vec<Field> {
+    match value {
+        Some(Value::Array(fields)) => fields
+            .iter()
+            .map(|field| to_field(std::slice::from_ref(field)))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::Cursor,
+        path::{Path, PathBuf},
+    };
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn read_file(path: &Path) -> String {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("test-resources/src/main/kotlin/");
+        d.push(path);
+
+        fs::read_to_string(d).unwrap()
+        // println!("{}", d.display());
+    }
+
+    #[test]
+    fn test_write_interface() {
+        let class = KotlinClass {
+            package: "mypack".to_string(),
+            kotlin_name: "MyClass.kt".to_string(),
+            java_name: "MyClass".to_string(),
+            suffix: "Kt".to_string(),
+            functions: vec![Function {
+                name: "getCar".to_string(),
+                arguments: vec![
+                    Arg {
+                        name: "carBrand".to_string(),
+                        t: "CarBrandKt".to_string(),
+                        nullable: false,
+                        convert: true,
+                        header: false,
+                        mode: "IN".to_string(),
+                        java_type: "CarBrand".to_string(),
+                    },
+                    Arg {
+                        name: "carBrand".to_string(),
+                        t: "CarBrandKt".to_string(),
+                        nullable: false,
+                        convert: true,
+                        header: false,
+                        mode: "IN".to_string(),
+                        java_type: "CarBrand".to_string(),
+                    },
+                ],
+                return_type: "CarKt".to_string(),
+                return_prefix: "CarKt.fromJava(".to_string(),
+                return_suffix: ")".to_string(),
+                async_name: "".to_string(),
+                headers_class: "".to_string(),
+                result_class: "".to_string(),
+                fault_wrapper: "".to_string(),
+                declared_type: "CarKt".to_string(),
+                fault_class: "".to_string(),
+                faults: vec![],
+                kdoc: vec![],
+            }],
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+
+        write_interface(class, &Templates::new(), &mut buffer).unwrap();
+
+        assert_eq!(
+            "package mypack\n\n\n/**\n * This file is GENERATED. Please don't change\n */\n class MyClass.kt(val portType: MyClass) {\n    fun getCar(\n\t\tcarBrand: CarBrandKt,\n\t\tcarBrand: CarBrandKt,\n\t) = CarKt.fromJava(portType.getCar(\n\t\tcarBrand.toJava(),\n\t\tcarBrand.toJava(),\n\t))\n\t\t\n}\n",
+            String::from_utf8_lossy(&buffer.get_ref())
+        )
+    }
+
+    #[test]
+    fn test_write_enum() {
+        let constant = |name: &str, xml_value: &str| EnumConstant {
+            name: name.to_string(),
+            xml_value: xml_value.to_string(),
+            kdoc: vec![],
+        };
+        let class = KotlinClass {
+            kotlin_name: "CarBrandKt".to_string(),
+            java_name: "CarBrand".to_string(),
+            suffix: "Kt".to_string(),
+            enum_constants: vec![
+                constant("VW", "VW"),
+                constant("MERCEDES_BENZ", "mercedes-benz"),
+            ],
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+
+        write_enum(class, &Templates::new(), &mut buffer).unwrap();
+
+        assert_eq!(
+            "package \n\n\n/**\n * This file is GENERATED. Please don't change\n */\nenum class CarBrandKt(val value: CarBrand, val xmlValue: String) {\n    VW(CarBrand.VW, \"VW\"),\n    MERCEDES_BENZ(CarBrand.MERCEDES_BENZ, \"mercedes-benz\"),\n    ;\n\n\tfun toJava(): CarBrand = value\n\n    companion object {\n        fun fromJava(value: CarBrand): CarBrandKt =\n            fromJavaOrNull(value) ?: throw IllegalArgumentException(\"No CarBrandKt for $value\")\n\n        fun fromJavaOrNull(value: CarBrand?): CarBrandKt? = values().find { it.value == value }\n\n        fun fromXmlValue(value: String): CarBrandKt? = values().find { it.xmlValue == value }\n    }\n}",
+            String::from_utf8_lossy(buffer.get_ref())
+        )
+    }
+
+    #[test]
+    fn test_write_kdoc() {
+        let class = KotlinClass {
+            kotlin_name: "CarBrandKt".to_string(),
+            java_name: "CarBrand".to_string(),
+            enum_constants: vec![EnumConstant {
+                name: "MERCEDES_BENZ".to_string(),
+                xml_value: "mercedes-benz".to_string(),
+                kdoc: vec![
+                    "Mercedes-Benz".to_string(),
+                    "".to_string(),
+                    "The XML value `mercedes-benz`, not `mercedes/*`*/".to_string(),
+                ],
+            }],
+            kdoc: vec!["The brands".to_string()],
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+
+        write_enum(class, &Templates::new(), &mut buffer).unwrap();
+
+        assert_eq!(
+            "package \n\n\n/**\n * The brands\n *\n * This file is GENERATED. Please don't change\n */\nenum class CarBrandKt(val value: CarBrand, val xmlValue: String) {\n    /**\n     * Mercedes-Benz\n     *\n     * The XML value `mercedes-benz`, not `mercedes/&#42;`*&#47;\n     */\n    MERCEDES_BENZ(CarBrand.MERCEDES_BENZ, \"mercedes-benz\"),\n    ;\n\n\tfun toJava(): CarBrand = value\n\n    companion object {\n        fun fromJava(value: CarBrand): CarBrandKt =\n            fromJavaOrNull(value) ?: throw IllegalArgumentException(\"No CarBrandKt for $value\")\n\n        fun fromJavaOrNull(value: CarBrand?): CarBrandKt? = values().find { it.value == value }\n\n        fun fromXmlValue(value: String): CarBrandKt? = values().find { it.xmlValue == value }\n    }\n}",
+            String::from_utf8_lossy(buffer.get_ref())
+        );
+
+        let class = KotlinClass {
+            kotlin_name: "CarsServiceKt".to_string(),
+            java_name: "CarsService".to_string(),
+            functions: vec![Function {
+                name: "getCars".to_string(),
+                return_type: "Unit".to_string(),
+                declared_type: "Unit".to_string(),
+                kdoc: vec!["Finds the cars of a brand, like /* or */ in a path".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+
+        write_interface(class, &Templates::new(), &mut buffer).unwrap();
+
+        assert_eq!(
+            "package \n\n\n/**\n * This file is GENERATED. Please don't change\n */\n class CarsServiceKt(val portType: CarsService) {\n    /** Finds the cars of a brand, like /&#42; or *&#47; in a path */\n    fun getCars(\n\t) = portType.getCars(\n\t)\n\t\t\n}\n",
+            String::from_utf8_lossy(buffer.get_ref())
+        )
+    }
+
+    #[test]
+    fn test_write_builder() {
+        let class = KotlinClass {
+            kotlin_name: "TyreKt".to_string(),
+            java_name: "Tyre".to_string(),
+            suffix: "Kt".to_string(),
+            package: "dataclasses".to_string(),
+            fields: vec![
+                Field {
+                    name: "brand".to_string(),
+                    r#type: "String".to_string(),
+                    ..Default::default()
+                },
+                Field {
+                    name: "tread".to_string(),
+                    r#type: "String".to_string(),
+                    generic_type: "List".to_string(),
+                    ..Default::default()
+                },
+                Field {
+                    name: "rating".to_string(),
+                    r#type: "RatingKt".to_string(),
+                    convert: true,
+                    nullable: true,
+                    ..Default::default()
+                },
+            ],
+            inner_classes: vec![InnerClass {
+                name: "Rating".to_string(),
+                fields: vec![Field {
+                    name: "grip".to_string(),
+                    r#type: "String".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            dsl_marker: "no.eika.cars.CarsDsl".to_string(),
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
+
+        assert_eq!(
+            String::from_utf8_lossy(buffer.get_ref()),
+            r#"package dataclasses
+
+import no.eika.cars.CarsDsl
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class TyreKt(
+    val brand: String,
+    val tread: List<String> = emptyList(),
+    val rating: RatingKt? = null,
+) {
+
+    fun toJava(): Tyre = Tyre().also {
+        it.brand = brand
+        it.tread = tread
+        it.rating = rating?.toJava()
+    }
 
-#[cfg(test)]
-mod tests {
-    use std::{
-        fs,
-        io::Cursor,
-        path::{Path, PathBuf},
-    };
+    companion object {
+        internal val factory = ObjectFactory()
 
-    use pretty_assertions::assert_eq;
+        fun fromJava(javaClass: Tyre): TyreKt = TyreKt(
+            brand = javaClass.brand,
+            tread = javaClass.tread,
+            rating = javaClass.rating?.let { RatingKt.fromJava(it) as RatingKt },
+        )
 
-    use super::*;
+    }
 
-    fn read_file(path: &Path) -> String {
-        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        d.push("test-resources/src/main/kotlin/");
-        d.push(path);
+    data class RatingKt(
+        val grip: String,
+    ) {
 
-        fs::read_to_string(d).unwrap()
-        // println!("{}", d.display());
+        fun toJava(): Tyre.Rating = Tyre.Rating().also {
+            it.grip = grip
+        }
+
+        companion object {
+            internal val factory = ObjectFactory()
+
+            fun fromJava(javaClass: Tyre.Rating): RatingKt = RatingKt(
+                grip = javaClass.grip,
+            )
+
+        }
+
+        /** Builds [RatingKt] instances, failing when a required property is not set */
+        @CarsDsl
+        class Builder {
+            var grip: String? = null
+
+            fun build(): RatingKt = RatingKt(
+                grip = checkNotNull(grip) { "grip is required" },
+            )
+        }
+    }
+
+    /** Builds [TyreKt] instances, failing when a required property is not set */
+    @CarsDsl
+    class Builder {
+        var brand: String? = null
+        var tread: List<String> = emptyList()
+        var rating: RatingKt? = null
+
+        fun rating(block: RatingKt.Builder.() -> Unit) {
+            rating = RatingKt.Builder().apply(block).build()
+        }
+
+        fun build(): TyreKt = TyreKt(
+            brand = checkNotNull(brand) { "brand is required" },
+            tread = tread,
+            rating = rating,
+        )
+    }
+}
+
+/** Builds [TyreKt] in the DSL of its [TyreKt.Builder] */
+fun tyre(block: TyreKt.Builder.() -> Unit): TyreKt = TyreKt.Builder().apply(block).build()"#
+        )
     }
 
     #[test]
-    fn test_write_interface() {
+    fn test_write_validation() {
         let class = KotlinClass {
-            package: "mypack".to_string(),
-            kotlin_name: "MyClass.kt".to_string(),
-            java_name: "MyClass".to_string(),
-            functions: vec![Function {
-                name: "getCar".to_string(),
-                arguments: vec![
-                    Arg {
-                        name: "carBrand".to_string(),
-                        t: "CarBrandKt".to_string(),
-                        nullable: false,
+            kotlin_name: "CarKt".to_string(),
+            java_name: "Car".to_string(),
+            java_superclass: Some(Superclass {
+                name: "VehicleTypeKt".to_string(),
+                fields: vec![Field {
+                    name: "doors".to_string(),
+                    r#type: "Int".to_string(),
+                    checks: vec![Check {
+                        property: "doors".to_string(),
+                        facet: "totalDigits".to_string(),
+                        condition: "doors.toString().trimStart('-').length <= 2".to_string(),
+                        message: "doors has more than 2 digits".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+            }),
+            fields: vec![Field {
+                name: "registrationNumber".to_string(),
+                r#type: "String".to_string(),
+                checks: vec![
+                    Check {
+                        property: "registrationNumber".to_string(),
+                        facet: "maxLength".to_string(),
+                        condition: "registrationNumber.length <= 7".to_string(),
+                        message: "registrationNumber is longer than 7 characters".to_string(),
+                        ..Default::default()
                     },
-                    Arg {
-                        name: "carBrand".to_string(),
-                        t: "CarBrandKt".to_string(),
-                        nullable: false,
+                    Check {
+                        property: "registrationNumber".to_string(),
+                        facet: "pattern".to_string(),
+                        condition: "REGISTRATION_NUMBER_PATTERN.matches(registrationNumber)"
+                            .to_string(),
+                        message: "registrationNumber does not match the pattern [A-Z]{2}\\\\d{5}"
+                            .to_string(),
+                        pattern: "REGISTRATION_NUMBER_PATTERN".to_string(),
+                        regex: "[A-Z]{2}\\\\p{Nd}{5}".to_string(),
                     },
                 ],
-                return_type: "CarKt".to_string(),
+                ..Default::default()
             }],
+            validation: "method".to_string(),
             ..Default::default()
         };
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_interface(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(
-            "package mypack\n\n\n/**\n * This file is GENERATED. Please don't change\n */\n class MyClass.kt(val portType: MyClass) {\n    fun getCar(\n\t\tcarBrand: CarBrandKt,\n\t\tcarBrand: CarBrandKt,\n\t) = CarKt.fromJava(portType.getCar(\n\t\tcarBrand.toJava(),\n\t\tcarBrand.toJava(),\n\t))\n\t\t\n}\n",
-            String::from_utf8_lossy(&buffer.get_ref())
+            String::from_utf8_lossy(buffer.get_ref()),
+            r#"package 
+
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class CarKt(
+    override val doors: Int,
+    val registrationNumber: String,
+) : VehicleTypeKt {
+
+    /** The violations of the XSD facets by the properties */
+    fun validate(): List<Violation> {
+        val violations = mutableListOf<Violation>()
+        if (!(doors.toString().trimStart('-').length <= 2)) violations += Violation("doors", "doors has more than 2 digits")
+        if (!(registrationNumber.length <= 7)) violations += Violation("registrationNumber", "registrationNumber is longer than 7 characters")
+        if (!(REGISTRATION_NUMBER_PATTERN.matches(registrationNumber))) violations += Violation("registrationNumber", "registrationNumber does not match the pattern [A-Z]{2}\\d{5}")
+        return violations
+    }
+
+    /** A property that violates an XSD facet */
+    data class Violation(val property: String, val message: String)
+
+    override fun toJava(): Car = Car()
+        .also {
+            it.doors = doors
+            it.registrationNumber = registrationNumber
+        }
+
+    companion object {
+
+        internal val factory = ObjectFactory()
+        private val REGISTRATION_NUMBER_PATTERN = Regex("[A-Z]{2}\\p{Nd}{5}")
+
+        fun fromJava(javaClass: Car): CarKt = CarKt(
+            doors = javaClass.doors,
+            registrationNumber = javaClass.registrationNumber,
+        )
+
+    }
+}"#
         )
     }
 
     #[test]
-    fn test_write_enum() {
+    fn test_write_custom_templates() {
+        let templates = Templates::from([
+            (
+                "kdoc".to_string(),
+                "{{- define \"header\"}}// {{index . 0}}{{end}}".to_string(),
+            ),
+            (
+                "data_class".to_string(),
+                "{{template \"header\" .kdoc}}
+@Audited
+data class {{.kotlin_name}}(
+{{- range .fields}}
+    val {{.name}}: {{get_type_with_default .}},
+{{- end}}
+) {
+    val {{boolean_getter \"removable\"}}: Boolean get() = removable == true
+
+    fun toJava(): {{.java_name}} = {{.java_name}}().also {
+{{- range .fields}}
+        it.{{to_java .}} // {{get_type .}}
+{{- end}}
+    }
+
+    fun fromJava(javaClass: {{.java_name}}) = {{.kotlin_name}}(
+{{- range .fields}}
+        {{.name}} = javaClass.{{from_java .}},
+{{- end}}
+    )
+}"
+                .to_string(),
+            ),
+        ]);
+        let class = || KotlinClass {
+            package: "dataclasses".to_string(),
+            kotlin_name: "TowbarKt".to_string(),
+            java_name: "Towbar".to_string(),
+            kdoc: vec!["A towbar".to_string()],
+            fields: vec![
+                Field {
+                    name: "removable".to_string(),
+                    r#type: "Boolean".to_string(),
+                    nullable: true,
+                    ..Default::default()
+                },
+                Field {
+                    name: "weights".to_string(),
+                    r#type: "Int".to_string(),
+                    generic_type: "List".to_string(),
+                    use_getter: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_class(class(), &templates, &mut buffer).unwrap();
+
+        assert_eq!(String::from_utf8_lossy(buffer.get_ref()), "// A towbar\n@Audited\ndata class TowbarKt(\n    val removable: Boolean? = null,\n    val weights: List<Int> = emptyList(),\n) {\n    val isRemovable: Boolean get() = removable == true\n\n    fun toJava(): Towbar = Towbar().also {\n        it.removable = removable // Boolean?\n        it.weights.addAll(weights) // List<Int>\n    }\n\n    fun fromJava(javaClass: Towbar) = TowbarKt(\n        removable = javaClass.removable,\n        weights = javaClass.weights,\n    )\n}");
+        assert!(check_template(&templates, "data_class").is_ok());
+
+        let broken = Templates::from([("enum".to_string(), "{{if .package}}".to_string())]);
+        assert!(check_template(&broken, "enum")
+            .unwrap_err()
+            .to_string()
+            .starts_with("config failed: template enum failed: "));
+        let missing = Templates::from([(
+            "data_class".to_string(),
+            "{{template \"missing\" .}}".to_string(),
+        )]);
+        let error = write_class(class(), &missing, &mut Cursor::new(Vec::new())).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("write failed: template failed: "));
+    }
+
+    #[test]
+    fn test_write_enum_with_unknown_constant() {
         let class = KotlinClass {
             kotlin_name: "CarBrandKt".to_string(),
             java_name: "CarBrand".to_string(),
-            enum_constants: vec!["VW".to_string(), "BMW".to_string()],
+            suffix: "Kt".to_string(),
+            enum_constants: vec![EnumConstant {
+                name: "VW".to_string(),
+                xml_value: "VW".to_string(),
+                kdoc: vec![],
+            }],
+            unknown_enum_constant: true,
             ..Default::default()
         };
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_enum(class, &mut buffer);
+        write_enum(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(
-            "package \n\n\n/**\n * This file is GENERATED. Please don't change\n */\nenum class CarBrandKt(val value: CarBrand) {\n    VW(CarBrand.VW),\n    BMW(CarBrand.BMW),\n    ;\n\n\tfun toJava(): CarBrand = CarBrand.valueOf(this.name)\n\n    companion object {\n        fun fromJava(value: CarBrand) = values().find { it.value == value }!!\n    }\n}",
-            String::from_utf8_lossy(&buffer.get_ref())
+            "package \n\n\n/**\n * This file is GENERATED. Please don't change\n */\nenum class CarBrandKt(val value: CarBrand?, val xmlValue: String) {\n    VW(CarBrand.VW, \"VW\"),\n    /** A value missing from the Java enum, like one the service added later */\n    UNKNOWN(null, \"\"),\n    ;\n\n\tfun toJava(): CarBrand = value ?: throw IllegalStateException(\"CarBrandKt.$name has no Java value\")\n\n    companion object {\n        fun fromJava(value: CarBrand?): CarBrandKt = fromJavaOrNull(value) ?: UNKNOWN\n\n        fun fromJavaOrNull(value: CarBrand?): CarBrandKt? = values().find { it != UNKNOWN && it.value == value }\n\n        fun fromXmlValue(value: String): CarBrandKt = values().find { it != UNKNOWN && it.xmlValue == value } ?: UNKNOWN\n    }\n}",
+            String::from_utf8_lossy(buffer.get_ref())
         )
     }
 
@@ -937,13 +2811,14 @@ mod tests {
             fields: vec![a_field()],
             kotlin_name: "ALevelKt".to_string(),
             java_name: "ALevel".to_string(),
+            suffix: "Kt".to_string(),
             subclasses: vec!["BLevel".to_string()],
             ..Default::default()
         };
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_abstract_class(class, &mut buffer);
+        write_abstract_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1083,6 +2958,7 @@ mod tests {
             package: "inheritance.abstractclasses".to_string(),
             kotlin_name: "BLevelKt".to_string(),
             java_name: "BLevel".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![b_field(), bb_field()],
             imports: vec![bd_import()],
             java_superclass: Some(Superclass {
@@ -1096,11 +2972,40 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
 
+    #[test]
+    fn test_sealed_abstract_class() {
+        let expected = read_file(Path::new("inheritance/abstractclasses/BLevelKt.kt"))
+            .replace("interface BLevelKt", "sealed interface BLevelKt");
+
+        let class = KotlinClass {
+            package: "inheritance.abstractclasses".to_string(),
+            kotlin_name: "BLevelKt".to_string(),
+            java_name: "BLevel".to_string(),
+            suffix: "Kt".to_string(),
+            fields: vec![b_field(), bb_field()],
+            imports: vec![bd_import()],
+            java_superclass: Some(Superclass {
+                name: "ALevelKt".to_string(),
+                fields: vec![a_field()],
+            }),
+            is_abstract: true,
+            is_sealed: true,
+            subclasses: vec!["CLevel".to_string()],
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
+
+        assert_eq!(String::from_utf8_lossy(buffer.get_ref()), expected)
+    }
+
     #[test]
     fn test_subclass_implementing_abstract_class() {
         let expected = read_file(Path::new("inheritance/abstractclasses/CLevelKt.kt"));
@@ -1109,6 +3014,7 @@ mod tests {
             package: "inheritance.abstractclasses".to_string(),
             kotlin_name: "CLevelKt".to_string(),
             java_name: "CLevel".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![c_field()],
             imports: vec![bd_import()],
             java_superclass: Some(Superclass {
@@ -1120,7 +3026,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1133,6 +3039,7 @@ mod tests {
             package: "inheritance.openclasses".to_string(),
             kotlin_name: "ALevelKt".to_string(),
             java_name: "ALevel".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![a_field(), aa_field()],
             subclasses: vec!["BLevel".to_string()],
             ..Default::default()
@@ -1140,7 +3047,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1153,6 +3060,7 @@ mod tests {
             package: "inheritance.openclasses".to_string(),
             kotlin_name: "ALevelEmptyKt".to_string(),
             java_name: "ALevelEmpty".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![],
             subclasses: vec!["BLevelEmpty".to_string()],
             ..Default::default()
@@ -1160,7 +3068,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1173,6 +3081,7 @@ mod tests {
             package: "inheritance.openclasses".to_string(),
             kotlin_name: "BLevelKt".to_string(),
             java_name: "BLevel".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![
                 b_field(),
                 bb_field(),
@@ -1196,7 +3105,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1209,6 +3118,7 @@ mod tests {
             package: "inheritance.openclasses".to_string(),
             kotlin_name: "CLevelKt".to_string(),
             java_name: "CLevel".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![c_field()],
             java_superclass: Some(Superclass {
                 name: "BLevelKt".to_string(),
@@ -1228,7 +3138,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1241,6 +3151,7 @@ mod tests {
             package: "inheritance.otherpackage".to_string(),
             kotlin_name: "CLevel2Kt".to_string(),
             java_name: "CLevel2".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![c_field()],
             java_superclass: Some(Superclass {
                 name: "BLevelKt".to_string(),
@@ -1266,7 +3177,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1279,6 +3190,7 @@ mod tests {
             package: "inheritance.openclasses".to_string(),
             kotlin_name: "ClassWithBLevelKt".to_string(),
             java_name: "ClassWithBLevel".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![Field {
                 name: "bLevel".to_string(),
                 r#type: "BLevelKt".to_string(),
@@ -1291,7 +3203,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1304,6 +3216,7 @@ mod tests {
             package: "dataclasses".to_string(),
             kotlin_name: "CarKt".to_string(),
             java_name: "Car".to_string(),
+            suffix: "Kt".to_string(),
             fields: vec![
                 Field {
                     name: "requiredInteger".to_string(),
@@ -1351,7 +3264,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1364,6 +3277,7 @@ mod tests {
             package: "dataclasses".to_string(),
             kotlin_name: "CarKtParted".to_string(),
             java_name: "Car".to_string(),
+            suffix: "Kt".to_string(),
             parts: vec![
                 Part {
                     fields: vec![
@@ -1422,7 +3336,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         let res = String::from_utf8_lossy(&buffer.get_ref());
         println!("{}", res);
@@ -1431,45 +3345,40 @@ mod tests {
     }
 
     #[test]
-    fn test_class_with_inner_class() {
+    fn test_class_with_map() {
         let expected = read_file(Path::new("dataclasses/CxfMapKt.kt"));
 
         let class = KotlinClass {
             package: "dataclasses".to_string(),
             kotlin_name: "CxfMapKt".to_string(),
             java_name: "CxfMap".to_string(),
-            fields: vec![Field {
-                name: "entry".to_string(),
-                r#type: "EntryKt".to_string(),
-                generic_type: "List".to_string(),
-                convert: true,
-                ..Default::default()
-            }],
-            inner_classes: vec![InnerClass {
-                name: "Entry".to_string(),
-                fields: vec![
-                    Field {
-                        name: "key".to_string(),
-                        r#type: "String".to_string(),
-                        convert: false,
-                        nullable: true,
-                        ..Default::default()
-                    },
-                    Field {
-                        name: "value".to_string(),
-                        r#type: "DoorKt".to_string(),
-                        convert: true,
-                        nullable: true,
-                        ..Default::default()
-                    },
-                ],
-            }],
+            suffix: "Kt".to_string(),
+            imports: vec![doorkt_import()],
+            fields: vec![Field::map(
+                "entry",
+                "CxfMap.Entry".to_string(),
+                Field {
+                    name: "key".to_string(),
+                    r#type: "String".to_string(),
+                    convert: false,
+                    nullable: true,
+                    ..Default::default()
+                },
+                Field {
+                    name: "value".to_string(),
+                    r#type: "DoorKt".to_string(),
+                    convert: true,
+                    nullable: true,
+                    ..Default::default()
+                },
+                false,
+            )],
             ..Default::default()
         };
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
@@ -1484,6 +3393,7 @@ mod tests {
             package: "inheritance.abstractclasses".to_string(),
             kotlin_name: "ALevelWithInnerKt".to_string(),
             java_name: "ALevelWithInner".to_string(),
+            suffix: "Kt".to_string(),
             is_abstract: true,
             fields: vec![
                 Field {
@@ -1504,6 +3414,9 @@ mod tests {
             subclasses: vec!["BLevelWithInner".to_string()],
             inner_classes: vec![InnerClass {
                 name: "Inner".to_string(),
+                validation: String::new(),
+                builder: Builder::default(),
+                patterns: vec![],
                 fields: vec![Field {
                     name: "ia".to_string(),
                     r#type: "String".to_string(),
@@ -1511,14 +3424,155 @@ mod tests {
                     nullable: true,
                     ..Default::default()
                 }],
+                kdoc: vec![],
             }],
             ..Default::default()
         };
 
         let mut buffer = Cursor::new(Vec::new());
 
-        write_class(class, &mut buffer);
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
 
         assert_eq!(String::from_utf8_lossy(&buffer.get_ref()), expected)
     }
+
+    #[test]
+    fn test_kotlinx_data_class() {
+        let class = KotlinClass {
+            package: "dataclasses".to_string(),
+            kotlin_name: "DoorKt".to_string(),
+            java_name: "Door".to_string(),
+            suffix: "Kt".to_string(),
+            fields: vec![
+                Field {
+                    name: "height".to_string(),
+                    xml_name: "Height".to_string(),
+                    r#type: "Int".to_string(),
+                    ..Default::default()
+                },
+                Field {
+                    name: "price".to_string(),
+                    xml_name: "Price".to_string(),
+                    r#type: "BigDecimal".to_string(),
+                    nullable: true,
+                    ..Default::default()
+                },
+            ],
+            imports: vec![Import {
+                package: "java.math".to_string(),
+                class: "BigDecimal".to_string(),
+            }],
+            serialization: "kotlinx".to_string(),
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
+
+        assert_eq!(
+            String::from_utf8_lossy(buffer.get_ref()),
+            r#"@file:UseContextualSerialization(BigDecimal::class)
+
+package dataclasses
+
+import java.math.BigDecimal
+import kotlinx.serialization.SerialName
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.UseContextualSerialization
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+@Serializable
+data class DoorKt(
+    @SerialName("Height") val height: Int,
+    @SerialName("Price") val price: BigDecimal? = null,
+) {
+
+    fun toJava(): Door = Door().also {
+        it.height = height
+        it.price = price
+    }
+
+    companion object {
+        internal val factory = ObjectFactory()
+
+        fun fromJava(javaClass: Door): DoorKt = DoorKt(
+            height = javaClass.height,
+            price = javaClass.price,
+        )
+
+    }
+}"#
+        )
+    }
+
+    #[test]
+    fn test_jackson_open_superclass() {
+        let class = KotlinClass {
+            package: "inheritance.openclasses".to_string(),
+            kotlin_name: "ALevelKt".to_string(),
+            java_name: "ALevel".to_string(),
+            suffix: "Kt".to_string(),
+            fields: vec![Field {
+                xml_name: "A".to_string(),
+                ..a_field()
+            }],
+            subclasses: vec!["BLevel".to_string()],
+            serialization: "jackson".to_string(),
+            ..Default::default()
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+
+        write_class(class, &Templates::new(), &mut buffer).unwrap();
+
+        assert_eq!(
+            String::from_utf8_lossy(buffer.get_ref()),
+            r#"package inheritance.openclasses
+
+import com.fasterxml.jackson.annotation.JsonProperty
+import com.fasterxml.jackson.annotation.JsonSubTypes
+import com.fasterxml.jackson.annotation.JsonTypeInfo
+import com.fasterxml.jackson.annotation.JsonTypeName
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@JsonTypeInfo(use = JsonTypeInfo.Id.NAME)
+@JsonSubTypes(
+    JsonSubTypes.Type(ALevelImplKt::class),
+    JsonSubTypes.Type(BLevelKt::class),
+)
+interface ALevelKt {
+    val a: Int
+
+    fun toJava(): ALevel = ALevel()
+        .also {
+            it.a = a
+        }
+
+    companion object {
+
+        internal val factory = ObjectFactory()
+
+        fun fromJava(javaClass: ALevel): ALevelKt = when (javaClass) {
+            is BLevel -> BLevelKt.fromJava(javaClass)
+            else -> ALevelImplKt(
+                a = javaClass.a,
+            )
+        }
+
+    }
+}
+
+@Suppress("unused", "useless_cast")
+@JsonTypeName("ALevel")
+data class ALevelImplKt(
+    @JsonProperty("A") override val a: Int,
+) : ALevelKt"#
+        )
+    }
 }
@@ -1,18 +1,36 @@
+use rayon::prelude::*;
 use regex::Regex;
 use std::{
     fs::{self, File},
     io::BufWriter,
     path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
 };
 
 use javaparser::Class;
 
+use javaparser::Argument as JavaArgument;
+use javaparser::Binding;
+use javaparser::ChoiceType;
 use javaparser::Class as JavaClass;
+use javaparser::ElementDecl;
+use javaparser::EnumConstant as JavaEnumConstant;
+use javaparser::Facets;
 use javaparser::Field as JavaField;
+use javaparser::Function as JavaFunction;
 use javaparser::Import as JavaImport;
+use javaparser::InnerClass as JavaInnerClass;
+use javaparser::Mode;
 use javaparser::Superclass as JavaSuperclass;
+use javaparser::Type as JavaType;
 
 use kotlinprinter::Arg as KotlinArgument;
+use kotlinprinter::Check as KotlinCheck;
+use kotlinprinter::Choice as KotlinChoice;
+use kotlinprinter::ChoiceCase as KotlinChoiceCase;
+use kotlinprinter::ElementFactory as KotlinElementFactory;
+use kotlinprinter::EnumConstant as KotlinEnumConstant;
+use kotlinprinter::Fault as KotlinFault;
 use kotlinprinter::Field as KotlinField;
 use kotlinprinter::Function as KotlinFunction;
 use kotlinprinter::Import as KotlinImport;
@@ -21,19 +39,496 @@ use kotlinprinter::KotlinClass;
 use kotlinprinter::Part;
 use kotlinprinter::Superclass as KotlinSuperclass;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use cli::{Command, CommonArgs, GenerateArgs, RenderArgs, Source};
+use config::{Config, FaultHandling, TimeType};
+use error::{Error, Result};
+use manifest::{Entry, Manifest};
+use model::{Model, ModelClass};
+
+mod cli;
+mod config;
+mod error;
 mod files;
 mod javaparser;
 mod kotlinprinter;
+mod manifest;
+mod model;
+mod xsdparser;
 
-const FIELDS_CHUNKING_LIMIT: usize = 50;
+const OBJECT_FACTORY: &str = "ObjectFactory";
+/// Returned by service methods with faults, with `faults = "result"`
+const OPERATION_RESULT: &str = "OperationResult";
+/// Added to enums with `unknown_enum_constants`
+const UNKNOWN_ENUM_CONSTANT: &str = "UNKNOWN";
 
-fn generate(generated_path: &Path) {
-    let file_paths = files::find_classes(generated_path);
-    file_paths
-        .iter()
-        .for_each(|path| write_kotlin_file(generated_path, path));
+/// The path and `@XmlElementDecl` methods of the `ObjectFactory` of each package
+type ObjectFactories = HashMap<String, (PathBuf, Vec<ElementDecl>)>;
+
+/// Classes parsed with their superclass chain, keyed by path and shared by the workers,
+/// so that a superclass is parsed once no matter how many subclasses it has
+#[derive(Default)]
+struct ClassCache {
+    classes: Mutex<HashMap<PathBuf, Arc<OnceLock<Result<Class>>>>>,
+    superclass_paths: Mutex<HashMap<PathBuf, PathBuf>>,
+    /// The `ObjectFactory` of each package, with its path
+    object_factories: ObjectFactories,
+    /// The `ObjectFactory` that the `JAXBElement` fields of a class were resolved with
+    factory_paths: Mutex<HashMap<PathBuf, PathBuf>>,
+    /// The fault classes that the methods of a service were resolved with
+    fault_paths: Mutex<HashMap<PathBuf, Vec<PathBuf>>>,
+}
+
+impl ClassCache {
+    fn get_or_parse(&self, config: &Config, base_path: &Path, file_path: &Path) -> Result<Class> {
+        let class = self
+            .classes
+            .lock()
+            .unwrap()
+            .entry(file_path.to_path_buf())
+            .or_default()
+            .clone();
+        class
+            .get_or_init(|| parse_class_with_inheritance(self, config, base_path, file_path))
+            .clone()
+    }
+
+    /// The parsed file followed by the files of its superclass chain, the object factories
+    /// their `JAXBElement` fields were resolved with, and the fault classes of a service
+    fn sources(&self, file_path: &Path) -> Vec<PathBuf> {
+        let superclass_paths = self.superclass_paths.lock().unwrap();
+        let mut sources = vec![file_path.to_path_buf()];
+        while let Some(superclass_path) = superclass_paths.get(sources.last().unwrap()) {
+            sources.push(superclass_path.to_owned());
+        }
+        let factory_paths = self.factory_paths.lock().unwrap();
+        let factories: Vec<PathBuf> = sources
+            .iter()
+            .filter_map(|source| factory_paths.get(source))
+            .cloned()
+            .collect();
+        let fault_paths = self.fault_paths.lock().unwrap();
+        let faults = fault_paths.get(file_path).into_iter().flatten().cloned();
+        for source in factories.into_iter().chain(faults) {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        sources
+    }
+}
+
+fn generate(args: &GenerateArgs, config: &Config) -> Vec<Error> {
+    let kotlin_files = args.emit_model.is_none();
+    run(
+        &args.common,
+        config,
+        args.output(),
+        kotlin_files,
+        || match (args.emit_model, args.source) {
+            (Some(_), _) => emit_model(args, config),
+            (None, Source::Java) => generate_from_java(args, config),
+            (None, Source::Wsdl) => generate_from_schemas(args, config),
+        },
+    )
+}
+
+fn render(args: &RenderArgs, config: &Config) -> Vec<Error> {
+    run(&args.common, config, &args.output, true, || {
+        render_model(args, config)
+    })
+}
+
+/// Runs the jobs of a command on the worker threads, and writes the classes the Kotlin files
+/// share when the command writes Kotlin files
+fn run<F>(
+    common: &CommonArgs,
+    config: &Config,
+    output: &Path,
+    kotlin_files: bool,
+    jobs: F,
+) -> Vec<Error>
+where
+    F: FnOnce() -> Vec<Result<Option<PathBuf>>> + Send,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(common.jobs.unwrap_or(0))
+        .build()
+        .expect("Couldn't start the worker threads");
+
+    let mut results = pool.install(jobs);
+    if let (true, Some(dsl_marker)) = (kotlin_files, &config.dsl_marker) {
+        results.push(write_dsl_marker(common, output, dsl_marker));
+    }
+    if let (true, Some(FaultHandling::Result), Some(package)) = (
+        kotlin_files,
+        config.faults,
+        &config.operation_result_package,
+    ) {
+        results.push(write_operation_result(common, output, package));
+    }
+
+    // The workers finish in any order, so the output is reported afterwards, in input order
+    results
+        .into_iter()
+        .filter_map(|result| match result {
+            Ok(Some(path)) if common.dry_run => {
+                println!("{}", path.display());
+                None
+            }
+            Ok(_) => None,
+            Err(error) => Some(error),
+        })
+        .collect()
+}
+
+fn generate_from_java(args: &GenerateArgs, config: &Config) -> Vec<Result<Option<PathBuf>>> {
+    let file_paths = match files::find_classes(&args.input, config) {
+        Ok(file_paths) => file_paths,
+        Err(error) => return vec![Err(error)],
+    };
+    let old_manifest = Manifest::load(args.output());
+    let (object_factories, factory_errors) = load_object_factories(args, config);
+    let cache = ClassCache {
+        object_factories,
+        ..Default::default()
+    };
+    let updates: Vec<Result<(Option<Entry>, Option<PathBuf>)>> = file_paths
+        .par_iter()
+        .map(|path| {
+            update_kotlin_file(args, config, &cache, &old_manifest, path)
+                .map_err(|error| error.in_file(path))
+        })
+        .collect();
+
+    let mut manifest = Manifest::default();
+    let mut results: Vec<Result<Option<PathBuf>>> = factory_errors.into_iter().map(Err).collect();
+    for (path, update) in file_paths.iter().zip(updates) {
+        match update {
+            Ok((entry, kotlin_file)) => {
+                if let Some(entry) = entry {
+                    manifest.entries.insert(entry.sources[0].to_owned(), entry);
+                }
+                results.push(Ok(kotlin_file));
+            }
+            Err(error) => {
+                // Keep the Kotlin file from the last good run until the class can be generated
+                let java_file = relative_path(path, &args.input);
+                if let Some(entry) = old_manifest.entries.get(&java_file) {
+                    manifest.entries.insert(java_file, entry.to_owned());
+                }
+                results.push(Err(error));
+            }
+        }
+    }
+
+    if !args.common.dry_run {
+        let stale_errors = remove_stale_files(args, &old_manifest, &manifest);
+        results.extend(stale_errors.into_iter().map(Err));
+        results.push(manifest.save(args.output()).map(|_| None));
+    }
+    results
+}
+
+/// The `@XmlElementDecl` methods of the `ObjectFactory` of each package, read even when the
+/// factories themselves are skipped
+fn load_object_factories(args: &GenerateArgs, config: &Config) -> (ObjectFactories, Vec<Error>) {
+    let mut object_factories = HashMap::new();
+    let mut errors = vec![];
+    let paths = match files::find_object_factories(&args.input) {
+        Ok(paths) => paths,
+        Err(error) => return (object_factories, vec![error]),
+    };
+    for path in paths {
+        let parsed = fs::read_to_string(&path)
+            .map_err(|why| Error::read(why.to_string()))
+            .and_then(|content| javaparser::parse_class(&content, config));
+        match parsed {
+            Ok(factory) => {
+                object_factories.insert(factory.package, (path, factory.element_decls));
+            }
+            Err(error) => errors.push(error.in_file(&path)),
+        }
+    }
+    (object_factories, errors)
+}
+
+/// Writes the Kotlin file for a Java file, unless neither it nor its superclasses changed since
+/// the last run. Returns the manifest entry for the Java file, and the Kotlin file if written.
+/// A class outside the package filter keeps its entry from the last run
+fn update_kotlin_file(
+    args: &GenerateArgs,
+    config: &Config,
+    cache: &ClassCache,
+    manifest: &Manifest,
+    file_path: &Path,
+) -> Result<(Option<Entry>, Option<PathBuf>)> {
+    let java_file = relative_path(file_path, &args.input);
+    if !args.common.package_filters.is_empty() {
+        let java_class = cache.get_or_parse(config, &args.input, file_path)?;
+        if !args.common.includes_package(&java_class.package) {
+            trace!(
+                "Skipping {}.{}, filtered by package\n",
+                java_class.package,
+                java_class.name
+            );
+            return Ok((manifest.entries.get(&java_file).cloned(), None));
+        }
+    }
+    if let Some(entry) = manifest.unchanged(&args.input, &java_file, config) {
+        if args.output().join(&entry.kotlin_file).exists() {
+            trace!("{:?} is unchanged\n", file_path);
+            return Ok((Some(entry.to_owned()), None));
+        }
+    }
+
+    let kotlin_file = write_kotlin_file(args, config, cache, file_path)?;
+    let entry = match &kotlin_file {
+        Some(kotlin_file) => {
+            let sources: Vec<PathBuf> = cache
+                .sources(file_path)
+                .iter()
+                .map(|source| relative_path(source, &args.input))
+                .collect();
+            Some(Entry {
+                hash: manifest::hash_sources(&args.input, &sources, config)?,
+                kotlin_file: relative_path(kotlin_file, args.output()),
+                sources,
+            })
+        }
+        None => None,
+    };
+    Ok((entry, kotlin_file))
+}
+
+/// Deletes the Kotlin files of the last run whose Java file is gone, unless another Java file
+/// generated them this time
+fn remove_stale_files(
+    args: &GenerateArgs,
+    old_manifest: &Manifest,
+    manifest: &Manifest,
+) -> Vec<Error> {
+    let kotlin_files: HashSet<&PathBuf> = manifest
+        .entries
+        .values()
+        .map(|entry| &entry.kotlin_file)
+        .collect();
+    old_manifest
+        .entries
+        .values()
+        .filter(|entry| !args.input.join(&entry.sources[0]).exists())
+        .filter(|entry| !kotlin_files.contains(&entry.kotlin_file))
+        .map(|entry| args.output().join(&entry.kotlin_file))
+        .filter(|path| path.exists())
+        .filter_map(|path| {
+            debug!("Removing stale {:?}\n", path);
+            fs::remove_file(&path)
+                .map_err(|why| Error::write(why.to_string()).in_file(&path))
+                .err()
+        })
+        .collect()
+}
+
+fn relative_path(path: &Path, root: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_path_buf()
+}
+
+fn generate_from_schemas(args: &GenerateArgs, config: &Config) -> Vec<Result<Option<PathBuf>>> {
+    parse_schemas(args)
+        .into_par_iter()
+        .map(|java_class| {
+            java_class.and_then(|java_class| {
+                let path = kotlin_package_path(args.output(), config, &java_class);
+                write_kotlin_class(&args.common, config, java_class, &path)
+            })
+        })
+        .collect()
+}
+
+/// The classes of the WSDL and XSD files in the input folder, after the errors reading them
+fn parse_schemas(args: &GenerateArgs) -> Vec<Result<JavaClass>> {
+    let file_paths = match files::find_schemas(&args.input) {
+        Ok(file_paths) => file_paths,
+        Err(error) => return vec![Err(error)],
+    };
+
+    let mut results = vec![];
+    let sources: Vec<(PathBuf, String)> = file_paths
+        .into_iter()
+        .filter_map(|path| match fs::read_to_string(&path) {
+            Ok(content) => Some((path, content)),
+            Err(why) => {
+                results.push(Err(Error::read(why.to_string()).in_file(&path)));
+                None
+            }
+        })
+        .collect();
+    results.extend(xsdparser::parse_schemas(&sources));
+    results
+}
+
+/// The Java classes in the input folder with their superclasses and factory methods resolved,
+/// and their files, after the errors reading the object factories
+fn parse_java_classes(args: &GenerateArgs, config: &Config) -> Vec<Result<ModelClass>> {
+    let file_paths = match files::find_classes(&args.input, config) {
+        Ok(file_paths) => file_paths,
+        Err(error) => return vec![Err(error)],
+    };
+    let (object_factories, factory_errors) = load_object_factories(args, config);
+    let cache = ClassCache {
+        object_factories,
+        ..Default::default()
+    };
+
+    let mut results: Vec<Result<ModelClass>> = factory_errors.into_iter().map(Err).collect();
+    results.par_extend(file_paths.par_iter().map(|path| {
+        cache
+            .get_or_parse(config, &args.input, path)
+            .map(|java_class| ModelClass {
+                source: Some(relative_path(path, &args.input)),
+                class: java_class,
+            })
+            .map_err(|error| error.in_file(path))
+    }));
+    results
+}
+
+/// Writes the classes that would be converted to Kotlin to the model file in the output folder,
+/// for `render` or other generators to read
+fn emit_model(args: &GenerateArgs, config: &Config) -> Vec<Result<Option<PathBuf>>> {
+    let parsed = match args.source {
+        Source::Java => parse_java_classes(args, config),
+        Source::Wsdl => parse_schemas(args)
+            .into_iter()
+            .map(|java_class| {
+                java_class.map(|java_class| ModelClass {
+                    source: None,
+                    class: java_class,
+                })
+            })
+            .collect(),
+    };
+
+    let mut results = vec![];
+    let classes: Vec<ModelClass> = parsed
+        .into_iter()
+        .filter_map(|model_class| match model_class {
+            Ok(model_class) => Some(model_class),
+            Err(error) => {
+                results.push(Err(error));
+                None
+            }
+        })
+        .filter(|model_class| {
+            !model_class.class.skip && args.common.includes_package(&model_class.class.package)
+        })
+        .collect();
+
+    let path = args.output().join(model::MODEL_FILE);
+    debug!("Writing {} classes to {:?}\n", classes.len(), path);
+    if args.common.dry_run {
+        results.push(Ok(Some(path)));
+    } else {
+        results.push(Model::new(classes).save(&path).map(|_| Some(path)));
+    }
+    results
+}
+
+/// Writes the Kotlin files of the classes in a model file, where `generate` would have written them
+fn render_model(args: &RenderArgs, config: &Config) -> Vec<Result<Option<PathBuf>>> {
+    let model = match Model::load(&args.input) {
+        Ok(model) => model,
+        Err(error) => return vec![Err(error)],
+    };
+    model
+        .classes
+        .into_par_iter()
+        .map(|model_class| {
+            let path = match &model_class.source {
+                Some(source) => kotlin_output_path(&args.output, config, source),
+                None => kotlin_package_path(&args.output, config, &model_class.class),
+            };
+            write_kotlin_class(&args.common, config, model_class.class, &path)
+        })
+        .collect()
+}
+
+/// Writes the `@DslMarker` annotation of the builders, which all data classes share
+fn write_dsl_marker(
+    common: &CommonArgs,
+    output: &Path,
+    dsl_marker: &str,
+) -> Result<Option<PathBuf>> {
+    let (package, name) = dsl_marker.rsplit_once('.').unwrap_or(("", dsl_marker));
+    let declaration = format!("@DslMarker\nannotation class {}\n", name);
+    write_shared_class(
+        common,
+        output,
+        package,
+        name,
+        "Marks the builder DSLs of the data classes",
+        &declaration,
+    )
+}
+
+/// Writes the `OperationResult` that the service methods with faults return with
+/// `faults = "result"`, which all services share
+fn write_operation_result(
+    common: &CommonArgs,
+    output: &Path,
+    package: &str,
+) -> Result<Option<PathBuf>> {
+    let declaration = "sealed class OperationResult<out S, out F : Exception> {
+    data class Success<S>(val value: S) : OperationResult<S, Nothing>()
+    data class Fault<F : Exception>(val fault: F) : OperationResult<Nothing, F>()
+}
+";
+    write_shared_class(
+        common,
+        output,
+        package,
+        OPERATION_RESULT,
+        "The value of a service method, or the SOAP fault it declares that the service returned",
+        declaration,
+    )
+}
+
+/// Writes a class that is not generated from a Java class, in the file of its name in the folder
+/// of its package. The file is left as it is when it has not changed
+fn write_shared_class(
+    common: &CommonArgs,
+    output: &Path,
+    package: &str,
+    name: &str,
+    kdoc: &str,
+    declaration: &str,
+) -> Result<Option<PathBuf>> {
+    let mut path = output.to_owned();
+    path.extend(package.split('.').filter(|folder| !folder.is_empty()));
+    path.push(format!("{}.kt", name));
+    if common.dry_run {
+        return Ok(Some(path));
+    }
+
+    let package_line = if package.is_empty() {
+        String::new()
+    } else {
+        format!("package {}\n\n", package)
+    };
+    let content = format!(
+        "{}/**\n * {}\n *\n * This file is GENERATED. Please don't change\n */\n{}",
+        package_line, kdoc, declaration
+    );
+    if fs::read_to_string(&path).is_ok_and(|old| old == content) {
+        return Ok(None);
+    }
+    let write_error = |why: std::io::Error| Error::write(why.to_string()).in_file(&path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    fs::write(&path, content).map_err(write_error)?;
+    Ok(Some(path))
 }
 
 fn title(s: &str) -> String {
@@ -49,81 +544,49 @@ fn convert_fields(
     java_class_package: &str,
     java_class_fields: &Vec<JavaField>,
     subclass_package: Option<&str>,
-) -> Vec<KotlinField> {
+    config: &Config,
+) -> Result<Vec<KotlinField>> {
     java_class_fields
         .iter()
         .map(|java_field| {
-            let mut convert = false;
-            let mut kotlin_type = java_field.r#type.class.clone();
-
-            if java_field.builtin {
-                let builin_types: HashMap<&str, &str> = [
-                    ("String", "String"),
-                    ("Integer", "Int"),
-                    ("int", "Int"),
-                    ("Double", "Double"),
-                    ("double", "Double"),
-                    ("Float", "Float"),
-                    ("float", "Float"),
-                    ("Short", "Short"),
-                    ("short", "Short"),
-                    ("Boolean", "Boolean"),
-                    ("boolean", "Boolean"),
-                    ("Long", "Long"),
-                    ("long", "Long"),
-                    ("byte[]", "ByteArray"),
-                    ("Object", "Any"),
-                ]
-                .iter()
-                .cloned()
-                .collect();
+            let time_type = calendar_type(java_field, config);
 
-                kotlin_type = builin_types
-                    .get(java_field.r#type.class.as_str())
-                    .unwrap()
-                    .to_owned()
-                    .to_owned();
+            let (kotlin_type, convert) = if let Some(choice_type) = &java_field.choice {
+                (kotlin_class_name(choice_type.name.clone(), config), true)
+            } else if let Some(time_type) = time_type {
+                (time_type.name().to_string(), false)
             } else {
-                //Assume this is an project internal package-level class
-                if java_field.r#type.package.is_some() {
-                    if java_field.r#type.stdlib {
-                        convert = false
-                    } else {
-                        kotlin_type = kotlin_type
-                            .strip_prefix(format!("{}.", java_class_name).as_str())
-                            .unwrap_or(kotlin_type.as_str())
-                            .to_string();
-
-                        kotlin_type = kotlin_class_name(kotlin_type);
-                        convert = true
-                    }
-                } else {
-                    convert = true
-                }
-            }
+                convert_type(
+                    &java_field.r#type,
+                    java_field.builtin,
+                    java_field,
+                    java_class_name,
+                    config,
+                )?
+            };
             let mut factory_func = String::from("");
             let mut object_factory_class = "";
             let mut object_factory_package = "";
 
             // TODO not pretty
-            let kotlin_class_name = kotlin_class_name(java_class_name.to_string());
+            let kotlin_class_name = kotlin_class_name(java_class_name.to_string(), config);
 
-            if java_field.generic_type == Some(String::from("JAXBElement")) {
-                factory_func = format!(
-                    "create{}{}",
-                    java_class_name,
-                    title(java_field.xml_name.as_ref().unwrap_or(&java_field.name))
-                );
+            // A choice of JAXBElements is converted by its sealed type
+            let is_choice_element = java_field.choice.is_some()
+                && java_field.generic_type.as_deref() == Some("JAXBElement");
+            if java_field.generic_type == Some(String::from("JAXBElement")) && !is_choice_element {
+                factory_func = factory_method(java_field, java_class_name);
                 object_factory_class = kotlin_class_name.as_str();
                 object_factory_package = java_class_package;
             }
 
-            KotlinField::new(
+            let mut kotlin_field = KotlinField::new(
                 java_field.name.as_str(),
                 kotlin_type,
                 java_field
                     .generic_type
                     .as_ref()
+                    .filter(|_| !is_choice_element)
                     .map(|gt| gt.as_str())
                     .unwrap_or(""),
                 factory_func,
@@ -132,33 +595,458 @@ fn convert_fields(
                 convert,
                 java_field.nullable,
                 subclass_package.map_or(false, |sub_pack| java_field.package != sub_pack),
-            )
+            );
+            kotlin_field.time_type = time_type.map_or(String::new(), |t| t.name().to_string());
+            if is_nested_generic(java_field) {
+                kotlin_field = kotlin_field.with_type_arguments(convert_type_arguments(
+                    &java_field.type_arguments,
+                    java_field,
+                    java_class_name,
+                    java_class_package,
+                    config,
+                )?);
+            }
+            // JAXB only reads these into the collections of the getters
+            kotlin_field.use_getter |= java_field.binding == Binding::Any;
+            if let Some(xml_name) = &java_field.xml_name {
+                kotlin_field.xml_name = xml_name.clone();
+            }
+            kotlin_field.kdoc = field_kdoc(java_field, &kotlin_field);
+            if config.validation.is_some() {
+                kotlin_field.checks = field_checks(java_field, &kotlin_field, java_class_name)?;
+            }
+            Ok(kotlin_field)
+        })
+        .collect()
+}
+
+/// The KDoc of a property: the documentation of the field, followed by the XML the field is
+/// bound to and its constraints. Elements get no KDoc without documentation
+fn field_kdoc(java_field: &JavaField, kotlin_field: &KotlinField) -> Vec<String> {
+    let binding = match java_field.binding {
+        Binding::Attribute => format!("The XML attribute `{}`", kotlin_field.xml_name),
+        Binding::Value => "The text content of the XML element".to_string(),
+        Binding::Any if kotlin_field.generic_type == "Map" => {
+            "Any other XML attributes".to_string()
+        }
+        Binding::Any => "Any other XML elements".to_string(),
+        Binding::Element if java_field.doc.is_empty() => return vec![],
+        Binding::Element => format!("The XML element `{}`", kotlin_field.xml_name),
+    };
+    if java_field.doc.is_empty() {
+        return vec![binding];
+    }
+
+    let mut constraints = vec![binding];
+    let is_xml_node = matches!(java_field.binding, Binding::Element | Binding::Attribute);
+    if is_xml_node && !java_field.nullable && java_field.generic_type.is_none() {
+        constraints.push("required".to_string());
+    }
+    if let Some(schema_type) = &java_field.schema_type {
+        constraints.push(format!("of XSD type `{}`", schema_type));
+    }
+    let mut kdoc = java_field.doc.clone();
+    kdoc.push(String::new());
+    kdoc.push(constraints.join(", "));
+    kdoc
+}
+
+/// The checks of the XSD facets of a field. List fields check every element, and fields of
+/// converted classes, time types or other generic types have none. The regex of a pattern is
+/// compiled once, in a `private val` of the companion object
+fn field_checks(
+    java_field: &JavaField,
+    kotlin_field: &KotlinField,
+    java_class_name: &str,
+) -> Result<Vec<KotlinCheck>> {
+    let is_list = kotlin_field.generic_type == "List";
+    if kotlin_field.convert
+        || !kotlin_field.time_type.is_empty()
+        || !(is_list || kotlin_field.generic_type.is_empty())
+    {
+        return Ok(vec![]);
+    }
+    let name = kotlin_field.name.as_str();
+    let value = if is_list { "it" } else { name };
+    let pattern = format!("{}_PATTERN", constant_name(name));
+    let regex = match &java_field.facets.pattern {
+        Some(xsd_pattern) => java_regex(xsd_pattern).map_err(|why| {
+            Error::convert(format!(
+                "pattern '{}' of field '{}' in {} {}",
+                xsd_pattern, java_field.name, java_class_name, why
+            ))
+        })?,
+        None => String::new(),
+    };
+    let checks = facet_checks(&java_field.facets, &kotlin_field.r#type, value, &pattern)
+        .into_iter()
+        .map(|(facet, condition, message)| KotlinCheck {
+            property: name.to_string(),
+            facet: facet.to_string(),
+            pattern: if facet == "pattern" {
+                pattern.clone()
+            } else {
+                String::new()
+            },
+            regex: if facet == "pattern" {
+                kotlin_string(&regex)
+            } else {
+                String::new()
+            },
+            condition: if is_list {
+                format!("{}.all {{ {} }}", name, condition)
+            } else if kotlin_field.nullable {
+                format!("{} == null || {}", name, condition)
+            } else {
+                condition
+            },
+            message: kotlin_string(&format!("{} {}", name, message)),
+        })
+        .collect();
+    Ok(checks)
+}
+
+/// The Java regex of an XSD pattern. XSD has the name character escapes `\i` and `\c`, its `\d`
+/// and `\w` are not only ASCII, its Unicode blocks are named like `IsBasicLatin` where Java has
+/// `InBasicLatin`, and `^` and `$` are plain characters. Character class subtraction is not
+/// supported
+fn java_regex(pattern: &str) -> std::result::Result<String, String> {
+    let mut regex = String::new();
+    let mut in_class = false;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('i') => regex.push_str(r"[_:\p{L}]"),
+                Some('I') => regex.push_str(r"[^_:\p{L}]"),
+                Some('c') => regex.push_str(r"[-._:\p{L}\p{M}\p{Nd}]"),
+                Some('C') => regex.push_str(r"[^-._:\p{L}\p{M}\p{Nd}]"),
+                Some('d') => regex.push_str(r"\p{Nd}"),
+                Some('D') => regex.push_str(r"\P{Nd}"),
+                Some('w') => regex.push_str(r"[^\p{P}\p{Z}\p{C}]"),
+                Some('W') => regex.push_str(r"[\p{P}\p{Z}\p{C}]"),
+                Some(escape @ ('p' | 'P')) => {
+                    let rest = chars.as_str();
+                    let name = match rest.strip_prefix('{').and_then(|rest| rest.split_once('}')) {
+                        Some((name, _)) if !name.is_empty() => name.to_string(),
+                        _ => return Err(format!("has a \\{} without a {{name}}", escape)),
+                    };
+                    chars.nth(name.chars().count() + 1);
+                    let name = match name.strip_prefix("Is") {
+                        Some(block) => format!("In{}", block),
+                        None => name,
+                    };
+                    regex.push_str(&format!("\\{}{{{}}}", escape, name));
+                }
+                Some(escape) if "nrtsS\\|.-^?*+{}()[]".contains(escape) => {
+                    regex.push('\\');
+                    regex.push(escape);
+                }
+                Some(escape) => return Err(format!("has an unknown escape \\{}", escape)),
+                None => return Err("ends with a backslash".to_string()),
+            },
+            '[' if in_class => {
+                return Err("subtracts a character class, which is not supported".to_string())
+            }
+            '[' => {
+                in_class = true;
+                regex.push(c);
+            }
+            ']' => {
+                in_class = false;
+                regex.push(c);
+            }
+            // `&&` is an intersection in a Java character class
+            '&' if in_class => regex.push_str("\\&"),
+            '^' | '$' if !in_class => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            _ => regex.push(c),
+        }
+    }
+    Ok(regex)
+}
+
+/// The name of a Kotlin constant for a property, like `REGISTRATION_NUMBER` for
+/// `registrationNumber`
+fn constant_name(property: &str) -> String {
+    let mut name = String::new();
+    for c in property.chars() {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push('_');
+        }
+        name.push(c.to_ascii_uppercase());
+    }
+    name
+}
+
+/// The facet, the Kotlin condition on `value` and the violation message of each facet. The
+/// lengths only apply to strings, and the other facets to the text of the value. A pattern is
+/// matched with the `Regex` in the val `pattern`
+fn facet_checks(
+    facets: &Facets,
+    kotlin_type: &str,
+    value: &str,
+    pattern: &str,
+) -> Vec<(&'static str, String, String)> {
+    let is_string = kotlin_type == "String";
+    let text = if is_string {
+        value.to_string()
+    } else {
+        format!("{}.toString()", value)
+    };
+    let mut checks = vec![];
+    if let (true, Some(min_length)) = (is_string, facets.min_length) {
+        checks.push((
+            "minLength",
+            format!("{}.length >= {}", value, min_length),
+            format!("is shorter than {}", count_of(min_length, "character")),
+        ));
+    }
+    if let (true, Some(max_length)) = (is_string, facets.max_length) {
+        checks.push((
+            "maxLength",
+            format!("{}.length <= {}", value, max_length),
+            format!("is longer than {}", count_of(max_length, "character")),
+        ));
+    }
+    if let Some(xsd_pattern) = &facets.pattern {
+        checks.push((
+            "pattern",
+            format!("{}.matches({})", pattern, text),
+            format!("does not match the pattern {}", xsd_pattern),
+        ));
+    }
+    if let (false, Some(total_digits)) = (is_string, facets.total_digits) {
+        let digits = if kotlin_type == "BigDecimal" {
+            format!("{}.stripTrailingZeros().precision()", value)
+        } else {
+            format!("{}.trimStart('-').length", text)
+        };
+        checks.push((
+            "totalDigits",
+            format!("{} <= {}", digits, total_digits),
+            format!("has more than {}", count_of(total_digits, "digit")),
+        ));
+    }
+    if !facets.enumeration.is_empty() {
+        let values: Vec<String> = facets
+            .enumeration
+            .iter()
+            .map(|value| format!("\"{}\"", kotlin_string(value)))
+            .collect();
+        checks.push((
+            "enumeration",
+            format!("{} in setOf({})", text, values.join(", ")),
+            format!("is not one of {}", facets.enumeration.join(", ")),
+        ));
+    }
+    checks
+}
+
+fn count_of(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {}", noun),
+        _ => format!("{} {}s", count, noun),
+    }
+}
+
+/// The Kotlin type of a Java type that is not generic, and whether it is converted with
+/// `toJava()` and `fromJava()`
+fn convert_type(
+    java_type: &JavaType,
+    builtin: bool,
+    java_field: &JavaField,
+    java_class_name: &str,
+    config: &Config,
+) -> Result<(String, bool)> {
+    if builtin {
+        let kotlin_type = config
+            .builtin_types
+            .get(java_type.class.as_str())
+            .ok_or_else(|| {
+                Error::convert(format!(
+                    "no Kotlin type for builtin type '{}' of field '{}' in {}",
+                    java_type.class, java_field.name, java_class_name
+                ))
+            })?;
+        return Ok((kotlin_type.to_string(), false));
+    }
+    //Assume this is an project internal package-level class
+    if java_type.package.is_none() {
+        return Ok((java_type.class.clone(), true));
+    }
+    // The wildcard content is DOM elements, or any JAXB object when lax
+    if java_type.stdlib || java_field.binding == Binding::Any {
+        return Ok((java_type.class.clone(), false));
+    }
+    let class = java_type
+        .class
+        .strip_prefix(format!("{}.", java_class_name).as_str())
+        .unwrap_or(java_type.class.as_str());
+    Ok((kotlin_class_name(class.to_string(), config), true))
+}
+
+/// Whether the type arguments of the field are generic themselves, like `List<JAXBElement<Car>>`,
+/// or there are several of them, like `Map<String, Car>`
+fn is_nested_generic(java_field: &JavaField) -> bool {
+    java_field.choice.is_none()
+        && (java_field.type_arguments.len() > 1
+            || java_field
+                .type_arguments
+                .iter()
+                .any(|argument| !argument.arguments.is_empty()))
+}
+
+/// The type arguments of a nested generic field as fields without names, at any depth
+fn convert_type_arguments(
+    arguments: &[JavaType],
+    java_field: &JavaField,
+    java_class_name: &str,
+    java_class_package: &str,
+    config: &Config,
+) -> Result<Vec<KotlinField>> {
+    arguments
+        .iter()
+        .map(|argument| {
+            let generic = !argument.arguments.is_empty();
+            let builtin = config.builtin_types.contains_key(&argument.class);
+            let (kotlin_type, convert) = match generic {
+                true => (String::new(), false),
+                false => convert_type(argument, builtin, java_field, java_class_name, config)?,
+            };
+            let mut kotlin_field = KotlinField::new(
+                "",
+                kotlin_type,
+                if generic { &argument.class } else { "" },
+                String::new(),
+                "",
+                "",
+                convert,
+                false,
+                false,
+            );
+            if argument.class == "JAXBElement" {
+                kotlin_field.factory_func = factory_method(java_field, java_class_name);
+                kotlin_field.object_factory_class =
+                    kotlin_class_name(java_class_name.to_string(), config);
+                kotlin_field.object_factory_package = java_class_package.to_string();
+            }
+            if !generic {
+                return Ok(kotlin_field);
+            }
+            Ok(kotlin_field.with_type_arguments(convert_type_arguments(
+                &argument.arguments,
+                java_field,
+                java_class_name,
+                java_class_package,
+                config,
+            )?))
         })
         .collect()
 }
 
-fn convert(java_class: JavaClass) -> KotlinClass {
+/// The `ObjectFactory` method creating the `JAXBElement` of the field.
+/// Classes read from schemas have no ObjectFactory, and are named like CXF does
+fn factory_method(java_field: &JavaField, java_class_name: &str) -> String {
+    java_field.factory_method.clone().unwrap_or_else(|| {
+        format!(
+            "create{}{}",
+            java_class_name,
+            title(java_field.xml_name.as_ref().unwrap_or(&java_field.name))
+        )
+    })
+}
+
+fn convert(java_class: JavaClass, config: &Config) -> Result<KotlinClass> {
+    if java_class.name == OBJECT_FACTORY {
+        return Ok(convert_object_factory(&java_class, config));
+    }
+    if let Some(fault_info) = &java_class.fault_info {
+        return Ok(convert_fault(&java_class, fault_info, config));
+    }
+    if config.unknown_enum_constants
+        && java_class
+            .enum_constants
+            .iter()
+            .any(|constant| constant.name == UNKNOWN_ENUM_CONSTANT)
+    {
+        return Err(Error::convert(format!(
+            "enum {} has a constant {} already, which unknown_enum_constants would add",
+            java_class.name, UNKNOWN_ENUM_CONSTANT
+        )));
+    }
+
+    // The superclass fields of a subclass are converted on their own, so the fields
+    // of classes in a hierarchy stay lists of entries
+    let map_entries: Vec<&JavaInnerClass> =
+        if java_class.is_abstract || !java_class.subclasses.is_empty() {
+            vec![]
+        } else {
+            java_class
+                .inner_classes
+                .iter()
+                .filter(|inner_class| is_map_entry(&java_class, inner_class))
+                .collect()
+        };
+
     let own_fields: Vec<KotlinField> = convert_fields(
         java_class.name.as_str(),
         java_class.package.as_str(),
         &java_class.fields,
         None,
-    );
+        config,
+    )?
+    .into_iter()
+    .zip(&java_class.fields)
+    .map(|(kotlin_field, java_field)| {
+        match map_entries
+            .iter()
+            .find(|entry| java_field.r#type.class == inner_class_name(&java_class, entry))
+        {
+            Some(entry) => map_field(&java_class, entry, kotlin_field, config),
+            None => Ok(kotlin_field),
+        }
+    })
+    .collect::<Result<_>>()?;
 
     let inner_classes: Vec<KotlinInnerClass> = java_class
         .inner_classes
         .iter()
+        .filter(|inner_class| !map_entries.contains(inner_class))
         .map(|inner_class| {
             let name = inner_class.name.as_str();
-            KotlinInnerClass {
+            let fields = convert_fields(
+                name,
+                java_class.package.as_str(),
+                &inner_class.fields,
+                None,
+                config,
+            )?;
+            Ok(KotlinInnerClass {
                 name: name.to_string(),
-                fields: convert_fields(
-                    name,
-                    java_class.package.as_str(),
-                    &inner_class.fields,
-                    None,
-                ),
-            }
+                validation: validation(&[&fields], config),
+                fields,
+                kdoc: inner_class.doc.clone(),
+                ..Default::default()
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    let kotlin_name = kotlin_class_name(java_class.name.to_owned(), config);
+    let choices: Vec<KotlinChoice> = java_class
+        .fields
+        .iter()
+        .chain(
+            java_class
+                .inner_classes
+                .iter()
+                .flat_map(|inner_class| &inner_class.fields),
+        )
+        .filter_map(|field| {
+            let choice_type = field.choice.as_ref()?;
+            Some(convert_choice(choice_type, field, &kotlin_name, config))
         })
         .collect();
 
@@ -169,18 +1057,7 @@ fn convert(java_class: JavaClass) -> KotlinClass {
             superclass
                 .fields
                 .iter()
-                .filter(|field| field.r#type.package.is_some())
-                .map(|field| {
-                    let class_name = if field.r#type.stdlib {
-                        field.r#type.class.clone()
-                    } else {
-                        kotlin_class_name(field.r#type.class.clone())
-                    };
-                    KotlinImport {
-                        package: field.r#type.package.as_ref().unwrap().to_owned(),
-                        class: class_name,
-                    }
-                })
+                .flat_map(|field| kotlin_imports(field, config))
                 .collect()
         })
         .unwrap_or(vec![]);
@@ -191,7 +1068,7 @@ fn convert(java_class: JavaClass) -> KotlinClass {
         .filter(|superclass| superclass.package != java_class.package)
         .map(|superclass| KotlinImport {
             package: superclass.package.clone(),
-            class: kotlin_class_name(superclass.name.clone()),
+            class: kotlin_class_name(superclass.name.clone(), config),
         })
         .map(|superclass_as_import| inherited_imports.push(superclass_as_import));
 
@@ -209,7 +1086,7 @@ fn convert(java_class: JavaClass) -> KotlinClass {
         .iter()
         .map(|import| KotlinImport {
             package: import.package.clone(),
-            class: kotlin_class_name(import.class.clone()),
+            class: kotlin_class_name(import.class.clone(), config),
         })
         .collect();
 
@@ -217,18 +1094,7 @@ fn convert(java_class: JavaClass) -> KotlinClass {
         .inner_classes
         .iter()
         .flat_map(|inner_class| &inner_class.fields)
-        .filter(|field| field.r#type.package.is_some())
-        .map(|field| {
-            let class_name = if field.r#type.stdlib {
-                field.r#type.class.clone()
-            } else {
-                kotlin_class_name(field.r#type.class.clone())
-            };
-            KotlinImport {
-                package: field.r#type.package.as_ref().unwrap().to_owned(),
-                class: class_name,
-            }
-        })
+        .flat_map(|field| kotlin_imports(field, config))
         .collect();
 
     let java_class_package = java_class.package.as_str();
@@ -236,19 +1102,8 @@ fn convert(java_class: JavaClass) -> KotlinClass {
     let own_imports: Vec<KotlinImport> = java_class
         .fields
         .iter()
-        .filter(|field| field.r#type.package.is_some())
-        .filter(|field| field.r#type.package != Some(java_class_package.to_string()))
-        .map(|field| {
-            let class_name = if field.r#type.stdlib {
-                field.r#type.class.clone()
-            } else {
-                kotlin_class_name(field.r#type.class.clone())
-            };
-            KotlinImport {
-                package: field.r#type.package.as_ref().unwrap().to_owned(),
-                class: class_name,
-            }
-        })
+        .flat_map(|field| kotlin_imports(field, config))
+        .filter(|import| import.package != java_class_package)
         .collect();
 
     let mut imports = [
@@ -259,126 +1114,937 @@ fn convert(java_class: JavaClass) -> KotlinClass {
         inner_class_imports,
     ]
     .concat();
-    imports.sort();
-    imports.dedup();
 
-    let functions = java_class
+    let functions: Vec<KotlinFunction> = java_class
         .functions
         .iter()
-        .map(|func| KotlinFunction {
-            name: func.name.to_owned(),
-            arguments: func
+        .filter(|func| !is_async(func))
+        .map(|func| {
+            let (return_type, return_prefix, return_suffix) = convert_return_type(func, config)?;
+            let arguments: Vec<KotlinArgument> = func
                 .arguments
                 .iter()
-                .map(|arg| KotlinArgument {
-                    name: arg.name.to_owned(),
-                    t: kotlin_class_name(arg.r#type.to_owned()),
-                    nullable: arg.nullable,
-                })
-                .collect(),
-            return_type: kotlin_class_name(func.return_type.to_owned()),
+                .map(|arg| convert_argument(arg, config))
+                .collect();
+            let class_name = |suffix: &str| {
+                let mut name = func.name.clone();
+                name[..1].make_ascii_uppercase();
+                name + suffix
+            };
+            let headers_class = if arguments.iter().any(|arg| arg.header) {
+                class_name("Headers")
+            } else {
+                String::new()
+            };
+            let result_class = if arguments.iter().any(|arg| arg.mode != "IN") {
+                class_name("Result")
+            } else {
+                String::new()
+            };
+            let value_type = if result_class.is_empty() {
+                return_type.clone()
+            } else {
+                result_class.clone()
+            };
+            // The faults of a method with several are the cases of a sealed class
+            let faults: Vec<KotlinFault> = match func.fault_classes.as_slice() {
+                [_, _, ..] if config.faults == Some(FaultHandling::Result) => func
+                    .fault_classes
+                    .iter()
+                    .map(|fault| KotlinFault {
+                        java_name: fault.class.clone(),
+                        kotlin_name: kotlin_class_name(fault.class.clone(), config),
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+            let fault_class = if faults.is_empty() {
+                String::new()
+            } else {
+                class_name("Fault")
+            };
+            let (fault_wrapper, declared_type) = match config.faults {
+                Some(_) if func.fault_classes.is_empty() => (String::new(), value_type),
+                Some(FaultHandling::Exceptions) => ("faults".to_string(), value_type),
+                Some(FaultHandling::Result) if !fault_class.is_empty() => (
+                    format!("result(this::{}Fault)", func.name),
+                    format!("{}<{}, {}>", OPERATION_RESULT, value_type, fault_class),
+                ),
+                Some(FaultHandling::Result) => {
+                    let fault_type = kotlin_class_name(func.fault_classes[0].class.clone(), config);
+                    (
+                        format!("result<{}, {}>", value_type, fault_type),
+                        format!("{}<{}, {}>", OPERATION_RESULT, value_type, fault_type),
+                    )
+                }
+                None => (String::new(), value_type),
+            };
+            let async_name = match async_with_handler(&java_class, func) {
+                Some(async_func) if result_class.is_empty() && func.return_type.is_some() => {
+                    async_func.name.clone()
+                }
+                _ => String::new(),
+            };
+            Ok(KotlinFunction {
+                name: func.name.to_owned(),
+                arguments,
+                return_type,
+                return_prefix,
+                return_suffix,
+                async_name,
+                headers_class,
+                result_class,
+                fault_wrapper,
+                declared_type,
+                fault_class,
+                faults,
+                kdoc: func.doc.clone(),
+            })
         })
-        .collect();
+        .collect::<Result<_>>()?;
+
+    let mut faults: Vec<KotlinFault> = vec![];
+    if config.faults.is_some() {
+        let fault_classes = java_class.functions.iter().flat_map(|f| &f.fault_classes);
+        for fault in fault_classes {
+            if faults.iter().any(|known| known.java_name == fault.class) {
+                continue;
+            }
+            let kotlin_name = kotlin_class_name(fault.class.clone(), config);
+            if fault.package != java_class.package {
+                imports.push(KotlinImport {
+                    package: fault.package.clone(),
+                    class: fault.class.clone(),
+                });
+                imports.push(KotlinImport {
+                    package: fault.package.clone(),
+                    class: kotlin_name.clone(),
+                });
+            }
+            faults.push(KotlinFault {
+                java_name: fault.class.clone(),
+                kotlin_name,
+            });
+        }
+    }
+
+    if let (Some(FaultHandling::Result), false, Some(package)) = (
+        config.faults,
+        faults.is_empty(),
+        &config.operation_result_package,
+    ) {
+        if *package != java_class.package {
+            imports.push(KotlinImport {
+                package: package.clone(),
+                class: OPERATION_RESULT.to_string(),
+            });
+        }
+    }
+
+    let sealed_faults = functions.iter().any(|func| !func.fault_class.is_empty());
+    let has_holders = functions.iter().any(|func| !func.result_class.is_empty());
+    if has_holders {
+        imports.push(KotlinImport {
+            package: "javax.xml.ws".to_string(),
+            class: "Holder".to_string(),
+        });
+    }
+    if config.coroutines && !functions.is_empty() {
+        imports.extend(coroutine_imports(&functions));
+    }
+    imports.sort();
+    imports.dedup();
 
     let class_package = java_class.package.clone();
 
-    let (fields, parts) = if own_fields.len() > FIELDS_CHUNKING_LIMIT {
+    let java_superclass = match java_class.superclass.as_ref() {
+        Some(superclass) => Some(KotlinSuperclass {
+            name: kotlin_class_name(superclass.name.to_owned(), config),
+            fields: convert_fields(
+                superclass.name.as_str(),
+                superclass.package.as_str(),
+                &superclass.fields,
+                Some(&java_class.package),
+                config,
+            )?,
+        }),
+        None => None,
+    };
+    let validation = validation(
+        &[
+            &own_fields,
+            java_superclass
+                .as_ref()
+                .map_or(&[], |superclass| &superclass.fields),
+        ],
+        config,
+    );
+
+    let (fields, parts) = if own_fields.len() > config.fields_chunking_limit {
         let parts: Vec<Part> = own_fields
-            .chunks(FIELDS_CHUNKING_LIMIT)
+            .chunks(config.fields_chunking_limit)
             .map(|chunk| Part {
                 fields: chunk.to_vec(),
             })
             .collect();
         (vec![], parts)
     } else {
-        (own_fields, vec![])
+        (own_fields, vec![])
+    };
+
+    Ok(KotlinClass {
+        kotlin_name,
+        java_name: java_class.name.to_owned(),
+        suffix: config.class_suffix.to_owned(),
+        package: class_package,
+        imports,
+        fields,
+        parts,
+        functions,
+        enum_constants: java_class
+            .enum_constants
+            .iter()
+            .map(|constant| KotlinEnumConstant {
+                name: constant.name.clone(),
+                xml_value: kotlin_string(&constant.xml_value),
+                kdoc: enum_constant_kdoc(constant),
+            })
+            .collect(),
+        unknown_enum_constant: config.unknown_enum_constants
+            && !java_class.enum_constants.is_empty(),
+        subclasses: java_class
+            .subclasses
+            .iter()
+            .map(|subclass| subclass.name.clone())
+            .collect(),
+        java_superclass,
+        inner_classes,
+        is_abstract: java_class.is_abstract,
+        is_sealed: config.sealed_hierarchies
+            && java_class.is_abstract
+            && subclasses_in_package(&java_class),
+        coroutines: config.coroutines,
+        coroutine_dispatcher: config.coroutine_dispatcher.clone(),
+        serialization: config.serialization.map_or(String::new(), |serialization| {
+            serialization.name().to_string()
+        }),
+        contextual_types: vec![],
+        fault_handling: match config.faults {
+            Some(fault_handling) if !faults.is_empty() => fault_handling.name().to_string(),
+            _ => String::new(),
+        },
+        sealed_faults,
+        faults,
+        fault_info: String::new(),
+        element_factories: vec![],
+        choices,
+        kdoc: java_class.doc.clone(),
+        validation,
+        dsl_marker: config.dsl_marker.clone().unwrap_or_default(),
+        ..Default::default()
+    })
+}
+
+/// The `validation` of a class whose properties, in any of the field lists, have checks
+fn validation(field_lists: &[&[KotlinField]], config: &Config) -> String {
+    let has_checks = field_lists
+        .iter()
+        .flat_map(|fields| fields.iter())
+        .any(|field| !field.checks.is_empty());
+    match config.validation {
+        Some(validation) if has_checks => validation.name().to_string(),
+        _ => String::new(),
+    }
+}
+
+/// The documentation of an enum constant, followed by its XML value
+fn enum_constant_kdoc(constant: &JavaEnumConstant) -> Vec<String> {
+    if constant.doc.is_empty() {
+        return vec![];
+    }
+    let mut kdoc = constant.doc.clone();
+    kdoc.push(String::new());
+    kdoc.push(format!("The XML value `{}`", constant.xml_value));
+    kdoc
+}
+
+/// The root elements of an `ObjectFactory` get typed `JAXBElement` builders, and their `QName`
+fn convert_object_factory(java_class: &JavaClass, config: &Config) -> KotlinClass {
+    let element_factories = java_class
+        .element_decls
+        .iter()
+        .filter(|decl| decl.scope.is_none())
+        .map(|decl| {
+            let builtin = config.builtin_types.get(&decl.r#type);
+            let mut qname = decl.method.trim_start_matches("create").to_string();
+            qname[..1].make_ascii_lowercase();
+            KotlinElementFactory {
+                method: decl.method.clone(),
+                qname: qname + "QName",
+                namespace: decl.namespace.clone(),
+                xml_name: decl.name.clone(),
+                t: builtin.map_or_else(
+                    || kotlin_class_name(decl.r#type.clone(), config),
+                    |kotlin| kotlin.clone(),
+                ),
+                java_type: decl.r#type.clone(),
+                convert: builtin.is_none(),
+            }
+        })
+        .collect();
+    let import = |package: &str, class: &str| KotlinImport {
+        package: package.to_string(),
+        class: class.to_string(),
+    };
+    KotlinClass {
+        kotlin_name: kotlin_class_name(java_class.name.to_owned(), config),
+        java_name: java_class.name.to_owned(),
+        suffix: config.class_suffix.to_owned(),
+        package: java_class.package.clone(),
+        imports: vec![
+            import("javax.xml.bind", "JAXBElement"),
+            import("javax.xml.namespace", "QName"),
+        ],
+        element_factories,
+        ..Default::default()
+    }
+}
+
+/// A CXF fault exception becomes a Kotlin exception carrying the converted fault bean
+fn convert_fault(java_class: &JavaClass, fault_info: &str, config: &Config) -> KotlinClass {
+    let imports = java_class
+        .imports
+        .iter()
+        .filter(|import| import.class == fault_info)
+        .map(|import| KotlinImport {
+            package: import.package.clone(),
+            class: kotlin_class_name(import.class.clone(), config),
+        })
+        .collect();
+    KotlinClass {
+        kotlin_name: kotlin_class_name(java_class.name.to_owned(), config),
+        java_name: java_class.name.to_owned(),
+        suffix: config.class_suffix.to_owned(),
+        package: java_class.package.clone(),
+        imports,
+        fault_info: kotlin_class_name(fault_info.to_string(), config),
+        ..Default::default()
+    }
+}
+
+/// The `*Async` methods CXF adds to port types, returning a `Response<T>` or a `Future<?>`
+fn is_async(func: &JavaFunction) -> bool {
+    let generic_type = func
+        .return_type
+        .as_ref()
+        .and_then(|return_type| return_type.generic_type.as_deref());
+    func.name.ends_with("Async") && matches!(generic_type, Some("Response") | Some("Future"))
+}
+
+/// The Kotlin return type of a port type method, and the code before and after the Java return value
+/// that converts it. Builtin types are returned as they are, and a `List` is converted per element
+fn convert_return_type(func: &JavaFunction, config: &Config) -> Result<(String, String, String)> {
+    let return_type = match &func.return_type {
+        None => return Ok(("Unit".to_string(), String::new(), String::new())),
+        Some(return_type) => return_type,
+    };
+    let class = &return_type.r#type.class;
+    let kotlin_type = if return_type.builtin {
+        config.builtin_types[class].clone()
+    } else {
+        kotlin_class_name(class.clone(), config)
+    };
+    match (return_type.generic_type.as_deref(), return_type.builtin) {
+        (None, true) if return_type.nullable => {
+            Ok((format!("{}?", kotlin_type), String::new(), String::new()))
+        }
+        (None, true) => Ok((kotlin_type, String::new(), String::new())),
+        (None, false) => Ok((
+            kotlin_type.clone(),
+            format!("{}.fromJava(", kotlin_type),
+            ")".to_string(),
+        )),
+        (Some("List"), true) => Ok((
+            format!("List<{}>", kotlin_type),
+            String::new(),
+            ".orEmpty()".to_string(),
+        )),
+        (Some("List"), false) => Ok((
+            format!("List<{}>", kotlin_type),
+            String::new(),
+            format!(".orEmpty().map {{ {}.fromJava(it) }}", kotlin_type),
+        )),
+        (Some(generic_type), _) => Err(Error::convert(format!(
+            "unsupported return type {}<{}> of '{}'",
+            generic_type, class, func.name
+        ))),
+    }
+}
+
+/// The `*Async` variant of a port type method that takes an `AsyncHandler`
+fn async_with_handler<'a>(
+    java_class: &'a JavaClass,
+    func: &JavaFunction,
+) -> Option<&'a JavaFunction> {
+    java_class.functions.iter().find(|async_func| {
+        is_async(async_func)
+            && async_func.name == format!("{}Async", func.name)
+            && async_func
+                .arguments
+                .last()
+                .is_some_and(|handler| handler.r#type.starts_with("AsyncHandler<"))
+    })
+}
+
+/// Builtin types are passed to the port type as they are, other types are converted with `toJava()`.
+/// OUT and INOUT parameters are passed in a `Holder`, and keep the Java type of its value
+fn convert_argument(arg: &JavaArgument, config: &Config) -> KotlinArgument {
+    let java_type = match arg.mode {
+        Mode::In => arg.r#type.clone(),
+        Mode::Out | Mode::InOut => arg
+            .r#type
+            .trim_start_matches("javax.xml.ws.")
+            .trim_start_matches("Holder<")
+            .trim_end_matches('>')
+            .to_string(),
+    };
+    let builtin = config.builtin_types.get(&java_type);
+    KotlinArgument {
+        name: arg.name.clone(),
+        t: builtin.map_or_else(
+            || kotlin_class_name(java_type.clone(), config),
+            |kotlin| kotlin.clone(),
+        ),
+        nullable: arg.nullable,
+        convert: builtin.is_none(),
+        header: arg.header && arg.mode == Mode::In,
+        mode: match arg.mode {
+            Mode::In => "IN",
+            Mode::Out => "OUT",
+            Mode::InOut => "INOUT",
+        }
+        .to_string(),
+        java_type,
+    }
+}
+
+/// Imports for the suspending service methods. Methods without an `*Async` method use `withContext`
+fn coroutine_imports(functions: &[KotlinFunction]) -> Vec<KotlinImport> {
+    let import = |package: &str, class: &str| KotlinImport {
+        package: package.to_string(),
+        class: class.to_string(),
+    };
+    let mut imports = vec![];
+    if functions.iter().any(|func| !func.async_name.is_empty()) {
+        imports.push(import("java.util.concurrent", "ExecutionException"));
+        imports.push(import("kotlin.coroutines", "resume"));
+        imports.push(import("kotlin.coroutines", "resumeWithException"));
+        imports.push(import("kotlinx.coroutines", "suspendCancellableCoroutine"));
+    }
+    if functions.iter().any(|func| func.async_name.is_empty()) {
+        imports.push(import("kotlinx.coroutines", "withContext"));
+    }
+    imports
+}
+
+/// Whether the subclasses from `@XmlSeeAlso` are all in the package of the class.
+/// Subclasses in other packages are imported, or written with their package
+fn subclasses_in_package(java_class: &JavaClass) -> bool {
+    java_class
+        .subclasses
+        .iter()
+        .all(|subclass| match &subclass.package {
+            Some(package) => *package == java_class.package,
+            None => !subclass.name.contains('.'),
+        })
+}
+
+fn inner_class_name(java_class: &JavaClass, inner_class: &JavaInnerClass) -> String {
+    format!("{}.{}", java_class.name, inner_class.name)
+}
+
+/// CXF writes maps as a list of an inner class with only a key and a value
+fn is_map_entry(java_class: &JavaClass, inner_class: &JavaInnerClass) -> bool {
+    let class_name = inner_class_name(java_class, inner_class);
+    let mut uses = java_class
+        .fields
+        .iter()
+        .filter(|field| field.r#type.class == class_name)
+        .peekable();
+
+    inner_class
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .eq(["key", "value"])
+        && uses.peek().is_some()
+        && uses.all(|field| field.generic_type.as_deref() == Some("List"))
+}
+
+/// The `Map` field for a list of map entries
+fn map_field(
+    java_class: &JavaClass,
+    entry: &JavaInnerClass,
+    list_field: KotlinField,
+    config: &Config,
+) -> Result<KotlinField> {
+    let mut entry_fields = convert_fields(
+        entry.name.as_str(),
+        java_class.package.as_str(),
+        &entry.fields,
+        None,
+        config,
+    )?
+    .into_iter();
+    match (entry_fields.next(), entry_fields.next()) {
+        (Some(key), Some(value)) => {
+            let mut map_field = KotlinField::map(
+                &list_field.name,
+                inner_class_name(java_class, entry),
+                key,
+                value,
+                list_field.use_getter,
+            );
+            map_field.xml_name = list_field.xml_name;
+            Ok(map_field)
+        }
+        _ => Err(Error::convert(format!(
+            "map entry {} needs a key and a value",
+            inner_class_name(java_class, entry)
+        ))),
+    }
+}
+
+/// The sealed type of a choice field. The `JAXBElement`s of the elements are created by the
+/// factory of the Kotlin class `factory_class`
+fn convert_choice(
+    choice_type: &ChoiceType,
+    field: &JavaField,
+    factory_class: &str,
+    config: &Config,
+) -> KotlinChoice {
+    let element_type = field.r#type.class.as_str();
+    let jaxb_elements = field.generic_type.as_deref() == Some("JAXBElement")
+        || element_type.starts_with("JAXBElement");
+    let java_type = if jaxb_elements {
+        "javax.xml.bind.JAXBElement<*>".to_string()
+    } else if element_type == "Object" {
+        "Any".to_string()
+    } else {
+        qualified_name(field.r#type.package.as_deref(), element_type)
+    };
+    let cases = choice_type
+        .choices
+        .iter()
+        .map(|choice| {
+            let package = choice.r#type.package.as_deref();
+            let class = choice.r#type.class.clone();
+            let convert = !choice.builtin && !choice.r#type.stdlib;
+            let (t, java_type) = if choice.builtin {
+                let t = config.builtin_types[&class].clone();
+                (t.clone(), t)
+            } else if convert {
+                (
+                    qualified_name(package, &kotlin_class_name(class.clone(), config)),
+                    qualified_name(package, &class),
+                )
+            } else {
+                let java_type = qualified_name(package, &class);
+                (java_type.clone(), java_type)
+            };
+            KotlinChoiceCase {
+                name: title(&choice.xml_name),
+                xml_name: choice.xml_name.clone(),
+                t,
+                java_type,
+                convert,
+                factory_func: choice.factory_method.clone().unwrap_or_default(),
+                object_factory_class: factory_class.to_string(),
+            }
+        })
+        .collect();
+    KotlinChoice {
+        name: kotlin_class_name(choice_type.name.clone(), config),
+        java_type,
+        jaxb_elements,
+        cases,
+    }
+}
+
+fn qualified_name(package: Option<&str>, class: &str) -> String {
+    match package {
+        Some(package) => format!("{}.{}", package, class),
+        None => class.to_string(),
+    }
+}
+
+/// The imports of the type of the field, or of the types in the type arguments of a nested generic
+fn kotlin_imports(field: &JavaField, config: &Config) -> Vec<KotlinImport> {
+    if !is_nested_generic(field) {
+        return field
+            .r#type
+            .package
+            .iter()
+            .map(|_| kotlin_import(field, config))
+            .collect();
+    }
+    let mut types: Vec<&JavaType> = field.type_arguments.iter().collect();
+    let mut imports = vec![];
+    while let Some(java_type) = types.pop() {
+        types.extend(&java_type.arguments);
+        match &java_type.package {
+            Some(package) if java_type.arguments.is_empty() => imports.push(KotlinImport {
+                package: package.clone(),
+                class: if java_type.stdlib || field.binding == Binding::Any {
+                    java_type.class.clone()
+                } else {
+                    kotlin_class_name(java_type.class.clone(), config)
+                },
+            }),
+            _ => {}
+        }
+    }
+    imports
+}
+
+fn kotlin_import(field: &JavaField, config: &Config) -> KotlinImport {
+    if let Some(choice_type) = &field.choice {
+        return KotlinImport {
+            package: field.package.clone(),
+            class: kotlin_class_name(choice_type.name.clone(), config),
+        };
+    }
+    if let Some(time_type) = calendar_type(field, config) {
+        return KotlinImport {
+            package: "java.time".to_string(),
+            class: time_type.name().to_string(),
+        };
+    }
+    let class_name = if field.r#type.stdlib || field.binding == Binding::Any {
+        field.r#type.class.clone()
+    } else {
+        kotlin_class_name(field.r#type.class.clone(), config)
     };
+    KotlinImport {
+        package: field.r#type.package.as_ref().unwrap().to_owned(),
+        class: class_name,
+    }
+}
 
-    KotlinClass {
-        kotlin_name: kotlin_class_name(java_class.name.to_owned()),
-        java_name: java_class.name.to_owned(),
-        package: class_package,
-        imports,
-        fields,
-        parts,
-        functions,
-        enum_constants: java_class.enum_constants.clone(),
-        subclasses: java_class
-            .subclasses
-            .iter()
-            .map(|subclass| subclass.name.clone())
-            .collect(),
-        java_superclass: java_class
-            .superclass
-            .as_ref()
-            .map(|superclass| KotlinSuperclass {
-                name: kotlin_class_name(superclass.name.to_owned()),
-                fields: convert_fields(
-                    superclass.name.as_str(),
-                    superclass.package.as_str(),
-                    &superclass.fields,
-                    Some(&java_class.package),
-                ),
-            }),
-        inner_classes,
-        is_abstract: java_class.is_abstract,
+/// The java.time type for an `XMLGregorianCalendar` field, if the config maps it
+fn calendar_type(field: &JavaField, config: &Config) -> Option<TimeType> {
+    if field.r#type.class == "XMLGregorianCalendar" {
+        config.calendar_type(field.schema_type.as_deref())
+    } else {
+        None
     }
 }
 
-fn kotlin_class_name(java_class_name: String) -> String {
+/// The content of a Kotlin string literal with the text
+fn kotlin_string(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+}
+
+fn kotlin_class_name(java_class_name: String, config: &Config) -> String {
     let re = Regex::new(r"([A-Z]+[^\.]*)").unwrap(); // Match inner class names, but not packages
-    re.replace_all(&java_class_name, "${1}Kt").to_string()
+    re.replace_all(&java_class_name, format!("${{1}}{}", config.class_suffix))
+        .to_string()
 }
 
-fn write_kotlin_file(base_path: &Path, file_path: &Path) {
+fn write_kotlin_file(
+    args: &GenerateArgs,
+    config: &Config,
+    cache: &ClassCache,
+    file_path: &Path,
+) -> Result<Option<PathBuf>> {
     trace!("Will parse {:?}\n", file_path);
 
-    let java_class = parse_class_with_inheritance(base_path, file_path);
+    let java_class = cache.get_or_parse(config, &args.input, file_path)?;
+    write_kotlin_class(
+        &args.common,
+        config,
+        java_class,
+        &kotlin_file_path(args, config, file_path),
+    )
+}
+
+/// Returns the path of the Kotlin file, or None when the class is skipped
+fn write_kotlin_class(
+    common: &CommonArgs,
+    config: &Config,
+    java_class: JavaClass,
+    absolute_path: &Path,
+) -> Result<Option<PathBuf>> {
     if java_class.skip {
-        return;
+        return Ok(None);
+    }
+    if !common.includes_package(&java_class.package) {
+        trace!("Skipping {}.{}, filtered by package\n", java_class.package, java_class.name);
+        return Ok(None);
     }
     debug!("Converting {}.{}\n", java_class.package, java_class.name);
 
-    let kotlin_class = convert(java_class);
+    let kotlin_class = convert(java_class, config)?;
     trace!("Convert done\n");
 
-    let mut absolute_path = base_path.to_owned();
-    absolute_path.push(file_path);
-    let filename = file_path.file_stem().unwrap();
-    absolute_path.set_file_name(kotlin_class_name(filename.to_str().unwrap().to_owned()));
-    absolute_path.set_extension("kt");
+    if common.dry_run {
+        return Ok(Some(absolute_path.to_path_buf()));
+    }
 
-    let file = match File::create(&absolute_path) {
-        Err(why) => panic!("couldn't create {}: {}", absolute_path.display(), why),
-        Ok(file) => file,
+    let write_error = |why: std::io::Error| {
+        Error::write(format!("couldn't create {}: {}", absolute_path.display(), why))
     };
 
+    if let Some(parent) = absolute_path.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+
+    let file = File::create(absolute_path).map_err(write_error)?;
+
     let mut f = BufWriter::new(file);
 
     trace!("Will write file\n");
-    kotlinprinter::write_class(kotlin_class, f.get_mut());
+    kotlinprinter::write_class(kotlin_class, &config.templates, f.get_mut())?;
     trace!("File written\n");
+    Ok(Some(absolute_path.to_path_buf()))
+}
+
+fn kotlin_file_path(args: &GenerateArgs, config: &Config, file_path: &Path) -> PathBuf {
+    let relative_path = file_path.strip_prefix(&args.input).unwrap_or(file_path);
+    kotlin_output_path(args.output(), config, relative_path)
+}
+
+/// Path in the output folder for the Java file at `relative_path` in the input folder
+fn kotlin_output_path(output: &Path, config: &Config, relative_path: &Path) -> PathBuf {
+    let mut absolute_path = output.to_owned();
+    absolute_path.push(relative_path);
+    let filename = relative_path.file_stem().unwrap();
+    absolute_path.set_file_name(kotlin_class_name(
+        filename.to_str().unwrap().to_owned(),
+        config,
+    ));
+    absolute_path.set_extension("kt");
+    absolute_path
 }
 
-fn parse_class_with_inheritance(base_path: &Path, file_path: &Path) -> Class {
-    let content = fs::read_to_string(file_path).unwrap();
+/// Path for a class that has no Java file, in the folder of its package
+fn kotlin_package_path(output: &Path, config: &Config, java_class: &JavaClass) -> PathBuf {
+    let mut absolute_path = output.to_owned();
+    absolute_path.extend(java_class.package.split('.'));
+    absolute_path.push(kotlin_class_name(java_class.name.to_owned(), config));
+    absolute_path.set_extension("kt");
+    absolute_path
+}
+
+fn parse_class_with_inheritance(
+    cache: &ClassCache,
+    config: &Config,
+    base_path: &Path,
+    file_path: &Path,
+) -> Result<Class> {
+    let content = fs::read_to_string(file_path)
+        .map_err(|why| Error::read(why.to_string()).in_file(file_path))?;
+
+    let mut java_class =
+        javaparser::parse_class(&content, config).map_err(|error| error.in_file(file_path))?;
 
-    let mut java_class = javaparser::parse_class(&content);
+    if java_class.name == OBJECT_FACTORY {
+        // Only root elements get a builder in the Kotlin factory
+        java_class.skip = !java_class
+            .element_decls
+            .iter()
+            .any(|decl| decl.scope.is_none());
+        return Ok(java_class);
+    }
+    resolve_factory_methods(cache, config, file_path, &mut java_class)?;
+    if config.faults.is_some() {
+        resolve_faults(cache, config, base_path, file_path, &mut java_class)?;
+    }
 
     let superclass_name: Option<&str> = java_class.superclass.as_ref().map(|s| s.name.as_ref());
 
-    if superclass_name == Some("Exception") || superclass_name == Some("Service") {
+    let is_fault = superclass_name == Some("Exception")
+        && config.faults.is_some()
+        && java_class.fault_info.is_some();
+    if is_fault {
+        java_class.superclass = None;
+    } else if superclass_name == Some("Exception") || superclass_name == Some("Service") {
         java_class.skip = true;
-    } else if superclass_name.is_some() {
-        warn!("Parsing superclass {}", superclass_name.unwrap());
-        let superclass = fields_from_superclass(base_path, file_path, &java_class);
+    } else if let Some(superclass_name) = superclass_name {
+        warn!("Parsing superclass {}", superclass_name);
+        let superclass = fields_from_superclass(cache, config, base_path, file_path, &java_class)?;
         debug!("Done parsing superclass");
         let mut imports = java_class.imports;
         imports.extend(superclass.imports.clone());
         java_class.superclass = Some(superclass);
         java_class.imports = imports;
     }
-    java_class
+    Ok(java_class)
+}
+
+/// Sets the `ObjectFactory` method of the `JAXBElement` fields, from the `@XmlElementDecl` of the
+/// element in the class, or else of the root element with the same name. Without a factory in the
+/// package, `convert_fields` names the methods like CXF does. The `JAXBElement` elements of choice
+/// fields also get their value type from the factory, so they need one
+fn resolve_factory_methods(
+    cache: &ClassCache,
+    config: &Config,
+    file_path: &Path,
+    java_class: &mut JavaClass,
+) -> Result<()> {
+    let class_name = java_class.name.clone();
+    let package = java_class.package.clone();
+    let mut fields: Vec<(String, &mut JavaField)> = java_class
+        .fields
+        .iter_mut()
+        .map(|field| (class_name.clone(), field))
+        .collect();
+    for inner_class in java_class.inner_classes.iter_mut() {
+        let scope = format!("{}.{}", class_name, inner_class.name);
+        fields.extend(
+            inner_class
+                .fields
+                .iter_mut()
+                .map(|field| (scope.clone(), field)),
+        );
+    }
+    fields.retain(|(_, field)| match &field.choice {
+        Some(choice_type) => choice_type.choices.iter().any(|choice| choice.jaxb_element),
+        None => {
+            field.generic_type.as_deref() == Some("JAXBElement")
+                || field
+                    .type_arguments
+                    .iter()
+                    .any(|argument| argument.contains("JAXBElement"))
+        }
+    });
+    if fields.is_empty() {
+        return Ok(());
+    }
+
+    let (factory_path, element_decls) = match cache.object_factories.get(&package) {
+        Some(object_factory) => object_factory,
+        None => {
+            if let Some((_, field)) = fields.iter().find(|(_, field)| field.choice.is_some()) {
+                return Err(Error::convert(format!(
+                    "no {}.java in package {} for the element types of choice field '{}' in {}",
+                    OBJECT_FACTORY, package, field.name, class_name
+                )));
+            }
+            warn!(
+                "No {}.java in package {}, the factory methods of {} are named like CXF does",
+                OBJECT_FACTORY, package, class_name
+            );
+            return Ok(());
+        }
+    };
+    let element_decl = |scope: &str, xml_name: &str, field_name: &str| {
+        let candidates = || element_decls.iter().filter(|decl| decl.name == xml_name);
+        candidates()
+            .find(|decl| decl.scope.as_deref() == Some(scope))
+            .or_else(|| candidates().find(|decl| decl.scope.is_none()))
+            .ok_or_else(|| {
+                Error::convert(format!(
+                    "no @XmlElementDecl for element '{}' of JAXBElement field '{}' in {}.{}",
+                    xml_name, field_name, package, OBJECT_FACTORY
+                ))
+            })
+    };
+    for (scope, field) in fields {
+        match &mut field.choice {
+            Some(choice_type) => {
+                for choice in choice_type.choices.iter_mut() {
+                    if !choice.jaxb_element {
+                        continue;
+                    }
+                    let decl = element_decl(&scope, &choice.xml_name, &field.name)?;
+                    // Like the root elements of the factory, the value types are in its package
+                    choice.builtin = config.builtin_types.contains_key(&decl.r#type);
+                    choice.r#type = JavaType {
+                        package: (!choice.builtin).then(|| package.clone()),
+                        class: decl.r#type.clone(),
+                        stdlib: choice.builtin,
+                        arguments: vec![],
+                    };
+                    choice.factory_method = Some(decl.method.clone());
+                }
+            }
+            None => {
+                let xml_name = field.xml_name.as_ref().unwrap_or(&field.name);
+                let decl = element_decl(&scope, xml_name, &field.name)?;
+                field.factory_method = Some(decl.method.clone());
+            }
+        }
+    }
+    cache
+        .factory_paths
+        .lock()
+        .unwrap()
+        .insert(file_path.to_path_buf(), factory_path.clone());
+    Ok(())
+}
+
+/// Sets the fault classes of the service methods, from the exceptions in their `throws` clause
+/// that are CXF fault exceptions. Other exceptions are left to the caller
+fn resolve_faults(
+    cache: &ClassCache,
+    config: &Config,
+    base_path: &Path,
+    file_path: &Path,
+    java_class: &mut JavaClass,
+) -> Result<()> {
+    let mut fault_paths: Vec<PathBuf> = vec![];
+    for func in java_class.functions.iter_mut() {
+        for fault in &func.faults {
+            let fault_path = class_path(base_path, file_path, &java_class.imports, fault);
+            if !fault_path.exists() {
+                warn!(
+                    "No {:?} for the fault {} of {}.{}, it is not converted",
+                    fault_path, fault, java_class.name, func.name
+                );
+                continue;
+            }
+            let fault_class = cache.get_or_parse(config, base_path, &fault_path)?;
+            if fault_class.fault_info.is_none() {
+                warn!(
+                    "{} of {}.{} is not a fault exception, it is not converted",
+                    fault, java_class.name, func.name
+                );
+                continue;
+            }
+            func.fault_classes.push(JavaImport {
+                package: fault_class.package,
+                class: fault_class.name,
+            });
+            if !fault_paths.contains(&fault_path) {
+                fault_paths.push(fault_path);
+            }
+        }
+    }
+    if !fault_paths.is_empty() {
+        cache
+            .fault_paths
+            .lock()
+            .unwrap()
+            .insert(file_path.to_path_buf(), fault_paths);
+    }
+    Ok(())
+}
+
+/// The file of the class `name` used in the file `file_path`, from its import or else in the
+/// same folder
+fn class_path(base_path: &Path, file_path: &Path, imports: &[JavaImport], name: &str) -> PathBuf {
+    let mut path = imports
+        .iter()
+        .find(|i| i.class == name)
+        .map_or(file_path.parent().unwrap().to_owned(), |i| {
+            base_path.join(to_path(i))
+        });
+    path.push(name);
+    path.set_extension(file_path.extension().unwrap());
+    path
 }
 
 fn to_path(import: &JavaImport) -> PathBuf {
@@ -391,32 +2057,32 @@ fn to_path(import: &JavaImport) -> PathBuf {
 }
 
 fn fields_from_superclass(
+    cache: &ClassCache,
+    config: &Config,
     base_path: &Path,
     subclass_file_path: &Path,
     subclass: &Class,
-) -> JavaSuperclass {
+) -> Result<JavaSuperclass> {
     let superclass_name: &str = subclass
         .superclass
         .as_ref()
         .map(|s| s.name.as_str())
         .unwrap();
 
-    let mut superclass_path = subclass
-        .imports
-        .iter()
-        .find(|i| i.class == superclass_name)
-        .map_or(subclass_file_path.parent().unwrap().to_owned(), |i| {
-            to_path(i)
-        });
-    superclass_path.push(superclass_name);
-    superclass_path.set_extension(subclass_file_path.extension().unwrap());
-
-    let mut full_superclass_path = base_path.to_path_buf();
-    full_superclass_path.push(superclass_path);
+    let full_superclass_path = class_path(
+        base_path,
+        subclass_file_path,
+        &subclass.imports,
+        superclass_name,
+    );
 
     trace!("Superclass path: {:?}", full_superclass_path);
+    cache.superclass_paths.lock().unwrap().insert(
+        subclass_file_path.to_path_buf(),
+        full_superclass_path.to_owned(),
+    );
 
-    let superclass = parse_class_with_inheritance(base_path, full_superclass_path.as_path());
+    let superclass = cache.get_or_parse(config, base_path, full_superclass_path.as_path())?;
 
     let superclass_superclass = superclass.superclass;
 
@@ -430,13 +2096,13 @@ fn fields_from_superclass(
         .map_or(vec![], |ss| ss.imports.clone());
     superclass_own_imports.extend(superclass.imports);
 
-    return JavaSuperclass {
+    Ok(JavaSuperclass {
         name: superclass.name,
         package: superclass.package,
         imports: superclass_own_imports,
         fields: superclass_own_fields,
         is_abstract: superclass.is_abstract,
-    };
+    })
 }
 
 #[macro_use]
@@ -445,21 +2111,186 @@ extern crate log;
 fn main() {
     env_logger::init();
 
-    let path_from_arg = std::env::args().nth(1).expect("no path given");
+    let command = cli::parse();
     info!("Starting...");
 
-    let generated_path = Path::new(&path_from_arg);
-    generate(generated_path);
-    println!("Done!");
+    let common = command.common();
+    let config = Config::load(common.config.as_deref()).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    });
+
+    let errors = match &command {
+        Command::Generate(args) => generate(args, &config),
+        Command::Render(args) => render(args, &config),
+    };
+    if !errors.is_empty() {
+        eprintln!("\nFailed to generate {} file(s):", errors.len());
+        errors.iter().for_each(|error| eprintln!("  {}", error));
+        std::process::exit(1);
+    }
+    if !common.dry_run {
+        println!("Done!");
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use javaparser::InnerClass as JavaInnerClass;
-    use javaparser::Type as JavaType;
+    use config::Validation;
+    use javaparser::Subclass;
     use pretty_assertions::assert_eq;
 
+    fn read_file(path: &Path) -> String {
+        fs::read_to_string(Path::new("test-resources/src/main/kotlin").join(path)).unwrap()
+    }
+
+    #[test]
+    fn test_kotlin_file_path() {
+        let args = GenerateArgs {
+            input: PathBuf::from("generated/java"),
+            output: Some(PathBuf::from("generated/kotlin")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            kotlin_file_path(
+                &args,
+                &Config::default(),
+                Path::new("generated/java/dataclasses/Car.java")
+            ),
+            PathBuf::from("generated/kotlin/dataclasses/CarKt.kt")
+        );
+        assert_eq!(
+            kotlin_file_path(
+                &GenerateArgs::from_path(PathBuf::from("generated/java")),
+                &Config::default(),
+                Path::new("generated/java/dataclasses/Car.java")
+            ),
+            PathBuf::from("generated/java/dataclasses/CarKt.kt")
+        );
+    }
+
+    #[test]
+    fn test_emit_and_render_model() {
+        let output = std::env::temp_dir().join(format!("wsdl2kotlin-model-{}", std::process::id()));
+        let args = GenerateArgs {
+            input: PathBuf::from("test-resources/src/main/kotlin"),
+            output: Some(output.clone()),
+            common: CommonArgs {
+                package_filters: vec!["misc.a".to_string()],
+                ..Default::default()
+            },
+            emit_model: Some(cli::ModelFormat::Json),
+            ..Default::default()
+        };
+        let config = Config::default();
+
+        assert!(generate(&args, &config).is_empty());
+        let model = Model::load(&output.join(model::MODEL_FILE)).unwrap();
+        let vehicle_type = model
+            .classes
+            .iter()
+            .find(|model_class| model_class.class.name == "VehicleType")
+            .unwrap();
+        assert_eq!(
+            vehicle_type.source,
+            Some(PathBuf::from("misc/VehicleType.java"))
+        );
+        assert!(model
+            .classes
+            .iter()
+            .all(|model_class| model_class.class.package == "misc.a"));
+
+        let render_args = RenderArgs {
+            input: output.join(model::MODEL_FILE),
+            output: output.clone(),
+            ..Default::default()
+        };
+        assert!(render(&render_args, &config).is_empty());
+        let rendered = fs::read_to_string(output.join("misc/VehicleTypeKt.kt")).unwrap();
+        assert!(rendered.starts_with("package misc.a\n"));
+        fs::remove_dir_all(&output).unwrap();
+    }
+
+    #[test]
+    fn test_package_filter_keeps_manifest_entries() {
+        let root = std::env::temp_dir().join(format!("wsdl2kotlin-filter-{}", std::process::id()));
+        let input = root.join("java");
+        let output = root.join("kotlin");
+        let resources = Path::new("test-resources/src/main/kotlin");
+        for file in ["dataclasses/Door.java", "misc/VehicleType.java"] {
+            fs::create_dir_all(input.join(file).parent().unwrap()).unwrap();
+            fs::copy(resources.join(file), input.join(file)).unwrap();
+        }
+        let args = GenerateArgs {
+            input: input.clone(),
+            output: Some(output.clone()),
+            ..Default::default()
+        };
+        let filtered_args = GenerateArgs {
+            common: CommonArgs {
+                package_filters: vec!["misc.a".to_string()],
+                ..Default::default()
+            },
+            ..args.clone()
+        };
+        let config = Config::default();
+        let door = Path::new("dataclasses/Door.java");
+
+        assert!(generate(&args, &config).is_empty());
+        let door_file = input.join(door);
+        let content = fs::read_to_string(&door_file).unwrap();
+        fs::write(&door_file, content + "// changed\n").unwrap();
+        assert!(generate(&filtered_args, &config).is_empty());
+
+        assert!(output.join("dataclasses/DoorKt.kt").exists());
+        assert!(output.join("misc/VehicleTypeKt.kt").exists());
+        assert!(Manifest::load(&output).entries.contains_key(door));
+
+        fs::remove_file(input.join("misc/VehicleType.java")).unwrap();
+        let door_args = GenerateArgs {
+            common: CommonArgs {
+                package_filters: vec!["dataclasses".to_string()],
+                ..Default::default()
+            },
+            ..args
+        };
+        assert!(generate(&door_args, &config).is_empty());
+        assert!(!output.join("misc/VehicleTypeKt.kt").exists());
+        assert!(output.join("dataclasses/DoorKt.kt").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_write_shared_classes() {
+        let output =
+            std::env::temp_dir().join(format!("wsdl2kotlin-shared-{}", std::process::id()));
+        let common = CommonArgs::default();
+
+        let path = write_operation_result(&common, &output, "misc")
+            .unwrap()
+            .unwrap();
+        assert_eq!(path, output.join("misc/OperationResult.kt"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            read_file(Path::new("misc/OperationResult.kt"))
+        );
+        assert_eq!(
+            write_operation_result(&common, &output, "misc").unwrap(),
+            None
+        );
+
+        let path = write_dsl_marker(&common, &output, "dataclasses.CarsDsl")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            read_file(Path::new("dataclasses/CarsDsl.kt"))
+        );
+        fs::remove_dir_all(&output).unwrap();
+    }
+
     #[test]
     fn test_convert() {
         let java_class = JavaClass {
@@ -482,6 +2313,7 @@ mod tests {
                         package: None,
                         class: "Object".to_string(),
                         stdlib: true,
+                        arguments: vec![],
                     },
                     builtin: true,
                     nullable: true,
@@ -495,10 +2327,23 @@ mod tests {
                         package: Some("somepackage".to_string()),
                         class: "TheClass.InnerClass".to_string(),
                         stdlib: false,
+                        arguments: vec![],
                     },
                     generic_type: Some("List".to_string()),
+                    type_arguments: vec![JavaType {
+                        package: Some("somepackage".to_string()),
+                        class: "TheClass.InnerClass".to_string(),
+                        stdlib: false,
+                        arguments: vec![],
+                    }],
                     builtin: false,
                     nullable: true,
+                    binding: Binding::Element,
+                    schema_type: None,
+                    factory_method: None,
+                    choice: None,
+                    doc: vec![],
+                    facets: javaparser::Facets::default(),
                 },
             ],
             superclass: Some(JavaSuperclass {
@@ -519,16 +2364,18 @@ mod tests {
                             package: Some("javax.xml.datatype".to_string()),
                             class: "XMLGregorianCalendar".to_string(),
                             stdlib: true,
+                            arguments: vec![],
                         },
                         name: "date".to_string(),
                         ..Default::default()
                     },
                 ],
+                doc: vec![],
             }],
             ..Default::default()
         };
 
-        let converted = convert(java_class);
+        let converted = convert(java_class, &Config::default()).unwrap();
 
         // let expected_imports = vec![KotlinImport{package: "java.math".to_string(), class: "BigDecimal".to_string() }];
         let expected = KotlinClass {
@@ -544,6 +2391,7 @@ mod tests {
                 },
             ],
             java_name: "TheClass".to_string(),
+            suffix: "Kt".to_string(),
             kotlin_name: "TheClassKt".to_string(),
             fields: vec![
                 KotlinField::new(
@@ -575,6 +2423,9 @@ mod tests {
             }),
             inner_classes: vec![KotlinInnerClass {
                 name: "InnerClass".to_string(),
+                validation: String::new(),
+                builder: Default::default(),
+                patterns: vec![],
                 fields: vec![
                     KotlinField::new(
                         "innerclassfield",
@@ -599,10 +2450,506 @@ mod tests {
                         false,
                     ),
                 ],
+                kdoc: vec![],
             }],
+            coroutine_dispatcher: "kotlinx.coroutines.Dispatchers.IO".to_string(),
             ..Default::default()
         };
 
         assert_eq!(expected, converted)
     }
+
+    #[test]
+    fn test_subclasses_in_package() {
+        let mut java_class = JavaClass {
+            package: "misc".to_string(),
+            name: "BaseResponseType".to_string(),
+            subclasses: vec![Subclass {
+                name: "CarResponse".to_string(),
+                package: None,
+            }],
+            ..Default::default()
+        };
+        assert!(subclasses_in_package(&java_class));
+
+        java_class.subclasses.push(Subclass {
+            name: "misc.a.BaseResponseType".to_string(),
+            package: None,
+        });
+        assert!(!subclasses_in_package(&java_class));
+
+        java_class.subclasses[1] = Subclass {
+            name: "VehicleType".to_string(),
+            package: Some("misc.a".to_string()),
+        };
+        assert!(!subclasses_in_package(&java_class));
+    }
+
+    #[test]
+    fn test_class_cache() {
+        let base_path = Path::new("test-resources/src/main/kotlin");
+        let c_level = base_path.join("inheritance/abstractclasses/CLevel.java");
+        let b_level = base_path.join("inheritance/abstractclasses/BLevel.java");
+        let cache = ClassCache::default();
+        let config = Config::default();
+
+        let parsed = cache.get_or_parse(&config, base_path, &c_level).unwrap();
+        let superclass = parsed.superclass.as_ref().unwrap();
+
+        assert_eq!(superclass.name, "BLevel");
+        assert_eq!(cache.classes.lock().unwrap().len(), 3);
+        assert_eq!(
+            cache
+                .get_or_parse(&config, base_path, &b_level)
+                .unwrap()
+                .name,
+            "BLevel"
+        );
+        assert_eq!(cache.classes.lock().unwrap().len(), 3);
+        assert_eq!(
+            parse_class_with_inheritance(&ClassCache::default(), &config, base_path, &c_level)
+                .unwrap(),
+            parsed
+        );
+    }
+
+    #[test]
+    fn test_resolve_factory_methods() {
+        let base_path = Path::new("test-resources/src/main/kotlin");
+        let factory_path = base_path.join("dataclasses/ObjectFactory.java");
+        let car_path = base_path.join("dataclasses/Car.java");
+        let config = Config::default();
+        let source_code = fs::read_to_string(&factory_path).unwrap();
+        let factory = javaparser::parse_class(&source_code, &config).unwrap();
+        let cache = ClassCache {
+            object_factories: HashMap::from([(
+                factory.package.clone(),
+                (factory_path.clone(), factory.element_decls.clone()),
+            )]),
+            ..Default::default()
+        };
+
+        let car = parse_class_with_inheritance(&cache, &config, base_path, &car_path).unwrap();
+        let factory_methods: Vec<_> = car
+            .fields
+            .iter()
+            .filter_map(|field| field.factory_method.as_deref())
+            .collect();
+        assert_eq!(
+            factory_methods,
+            vec!["createStringJAXBElement", "createInternalClassJAXBElement"]
+        );
+        assert_eq!(
+            cache.sources(&car_path),
+            vec![car_path.clone(), factory_path.clone()]
+        );
+
+        let root_only = ClassCache {
+            object_factories: HashMap::from([(
+                factory.package.clone(),
+                (factory_path, factory.element_decls[..1].to_vec()),
+            )]),
+            ..Default::default()
+        };
+        let error =
+            parse_class_with_inheritance(&root_only, &config, base_path, &car_path).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("no @XmlElementDecl for element 'stringJAXBElement'"));
+
+        // Without a factory, convert_fields names the methods like CXF does
+        let guessed =
+            parse_class_with_inheritance(&ClassCache::default(), &config, base_path, &car_path)
+                .unwrap();
+        assert!(guessed
+            .fields
+            .iter()
+            .all(|field| field.factory_method.is_none()));
+    }
+
+    #[test]
+    fn test_convert_bindings() {
+        let config = Config::default();
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Price.java").unwrap();
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/PriceKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_enum() {
+        let config = Config::default();
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/CarBrand.java").unwrap();
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/CarBrandKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_unknown_enum_constants() {
+        let config = Config {
+            unknown_enum_constants: true,
+            ..Config::default()
+        };
+        let source_code = "package dataclasses;
+
+public enum Currency {
+    @XmlEnumValue(\"US$\")
+    USD(\"US$\"),
+    NOK(\"NOK\");
+}";
+        let java_class = javaparser::parse_class(source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+
+        assert!(kotlin_class.unknown_enum_constant);
+        assert_eq!(
+            kotlin_class.enum_constants,
+            vec![
+                KotlinEnumConstant {
+                    name: "USD".to_string(),
+                    xml_value: "US\\$".to_string(),
+                    kdoc: vec![],
+                },
+                KotlinEnumConstant {
+                    name: "NOK".to_string(),
+                    xml_value: "NOK".to_string(),
+                    kdoc: vec![],
+                },
+            ]
+        );
+
+        let source_code = "package dataclasses;
+
+public enum State {
+    KNOWN,
+    UNKNOWN;
+}";
+        let java_class = javaparser::parse_class(source_code, &config).unwrap();
+        assert_eq!(
+            convert(java_class, &config).unwrap_err().message,
+            "enum State has a constant UNKNOWN already, which unknown_enum_constants would add"
+        );
+    }
+
+    #[test]
+    fn test_convert_kdoc() {
+        let config = Config::default();
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Wheel.java").unwrap();
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/WheelKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_java_regex() {
+        assert_eq!(java_regex(r"[A-Z]{2}-\d+").unwrap(), r"[A-Z]{2}-\p{Nd}+");
+        assert_eq!(
+            java_regex(r"\i\c*").unwrap(),
+            r"[_:\p{L}][-._:\p{L}\p{M}\p{Nd}]*"
+        );
+        assert_eq!(
+            java_regex(r"\p{IsBasicLatin}+\P{Lu}").unwrap(),
+            r"\p{InBasicLatin}+\P{Lu}"
+        );
+        assert_eq!(java_regex(r"^$[^a&&b]$").unwrap(), r"\^\$[^a\&\&b]\$");
+        assert_eq!(
+            java_regex(r"[a-z-[aeiou]]").unwrap_err(),
+            "subtracts a character class, which is not supported"
+        );
+        assert_eq!(java_regex(r"\b").unwrap_err(), r"has an unknown escape \b");
+        assert_eq!(
+            java_regex(r"\p{").unwrap_err(),
+            r"has a \p without a {name}"
+        );
+    }
+
+    #[test]
+    fn test_convert_validation() {
+        let config = Config {
+            validation: Some(Validation::Init),
+            ..Default::default()
+        };
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Tyre.java").unwrap();
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/TyreKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_nested_generics() {
+        let config = Config::default();
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Parking.java").unwrap();
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/ParkingKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_wildcards() {
+        let config = Config::default();
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Extensible.java")
+                .unwrap();
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/ExtensibleKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_calendar() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/misc/Dates.java").unwrap();
+        let config = Config {
+            calendar_types: vec![
+                ("date".to_string(), TimeType::LocalDate),
+                ("dateTime".to_string(), TimeType::OffsetDateTime),
+            ]
+            .into_iter()
+            .collect(),
+            ..Config::default()
+        };
+
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("misc/DatesKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_map() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/CxfMap.java").unwrap();
+        let config = Config::default();
+
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/CxfMapKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_return_types() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/misc/CarsReturnsService.java")
+                .unwrap();
+        let config = Config::default();
+
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("misc/CarsReturnsServiceKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_faults() {
+        let config = Config {
+            faults: Some(FaultHandling::Result),
+            operation_result_package: Some("misc".to_string()),
+            ..Config::default()
+        };
+        let base_path = Path::new("test-resources/src/main/kotlin");
+        let cache = ClassCache::default();
+        let write = |path: &str| {
+            let java_class = cache
+                .get_or_parse(&config, base_path, &base_path.join(path))
+                .unwrap();
+            let kotlin_class = convert(java_class, &config).unwrap();
+            let mut output = Vec::new();
+            kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(
+            write("misc/CarNotFoundFault.java"),
+            read_file(Path::new("misc/CarNotFoundFaultKt.kt"))
+        );
+        assert_eq!(
+            write("misc/CarLockedFault.java"),
+            read_file(Path::new("misc/CarLockedFaultKt.kt"))
+        );
+        assert_eq!(
+            write("misc/CarsFaultService.java"),
+            read_file(Path::new("misc/CarsFaultServiceKt.kt"))
+        );
+        assert_eq!(
+            cache.sources(&base_path.join("misc/CarsFaultService.java")),
+            vec![
+                base_path.join("misc/CarsFaultService.java"),
+                base_path.join("misc/CarNotFoundFault.java"),
+                base_path.join("misc/CarLockedFault.java"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convert_object_factory() {
+        let config = Config::default();
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/ObjectFactory.java")
+                .unwrap();
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/ObjectFactoryKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_choices() {
+        let base_path = Path::new("test-resources/src/main/kotlin");
+        let factory_path = base_path.join("dataclasses/ObjectFactory.java");
+        let config = Config::default();
+        let source_code = fs::read_to_string(&factory_path).unwrap();
+        let factory = javaparser::parse_class(&source_code, &config).unwrap();
+        let cache = ClassCache {
+            object_factories: HashMap::from([(
+                factory.package.clone(),
+                (factory_path, factory.element_decls),
+            )]),
+            ..Default::default()
+        };
+        let garage_path = base_path.join("dataclasses/Garage.java");
+        let java_class =
+            parse_class_with_inheritance(&cache, &config, base_path, &garage_path).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("dataclasses/GarageKt.kt"))
+        );
+
+        let without_factory =
+            parse_class_with_inheritance(&ClassCache::default(), &config, base_path, &garage_path);
+        assert!(without_factory
+            .unwrap_err()
+            .to_string()
+            .contains("for the element types of choice field 'doorOrOwner' in Garage"));
+    }
+
+    #[test]
+    fn test_convert_holders() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/misc/CarsHolderService.java")
+                .unwrap();
+        let config = Config::default();
+
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("misc/CarsHolderServiceKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_coroutines() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/misc/CarsAsyncService.java")
+                .unwrap();
+        let config = Config {
+            coroutines: true,
+            ..Config::default()
+        };
+
+        let java_class = javaparser::parse_class(&source_code, &config).unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("misc/CarsAsyncServiceKt.kt"))
+        );
+    }
+
+    #[test]
+    fn test_convert_coroutines_with_faults() {
+        let base_path = Path::new("test-resources/src/main/kotlin");
+        let config = Config {
+            coroutines: true,
+            faults: Some(FaultHandling::Exceptions),
+            ..Config::default()
+        };
+
+        let java_class = parse_class_with_inheritance(
+            &ClassCache::default(),
+            &config,
+            base_path,
+            &base_path.join("misc/CarsAsyncFaultService.java"),
+        )
+        .unwrap();
+        let kotlin_class = convert(java_class, &config).unwrap();
+        let mut output = Vec::new();
+        kotlinprinter::write_class(kotlin_class, &config.templates, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            read_file(Path::new("misc/CarsAsyncFaultServiceKt.kt"))
+        );
+    }
 }
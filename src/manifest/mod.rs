@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+
+pub const MANIFEST_FILE: &str = ".wsdl2kotlin-manifest";
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A Kotlin file from an earlier run, and the Java files it was generated from
+#[derive(PartialEq, Debug, Clone)]
+pub struct Entry {
+    pub hash: String,
+    pub kotlin_file: PathBuf,
+    /// The Java file first, then its superclass chain. Relative to the input folder
+    pub sources: Vec<PathBuf>,
+}
+
+/// What was generated on the last run, keyed by the Java file relative to the input folder.
+///
+/// Stored in the output folder as one tab separated line per Java file,
+/// after a header line with the tool version
+#[derive(PartialEq, Debug, Default)]
+pub struct Manifest {
+    pub entries: BTreeMap<PathBuf, Entry>,
+}
+
+impl Manifest {
+    /// Reads the manifest in the output folder. A missing manifest, or one written by another
+    /// version of the tool, is empty so that everything is generated again
+    pub fn load(output: &Path) -> Manifest {
+        fs::read_to_string(output.join(MANIFEST_FILE))
+            .map(|content| Manifest::parse(&content))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, output: &Path) -> Result<()> {
+        let path = output.join(MANIFEST_FILE);
+        fs::create_dir_all(output)
+            .and_then(|_| fs::write(&path, self.to_string()))
+            .map_err(|why| Error::write(why.to_string()).in_file(&path))
+    }
+
+    fn parse(content: &str) -> Manifest {
+        let mut lines = content.lines();
+        if lines.next() != Some(&header()) {
+            return Manifest::default();
+        }
+
+        let entries = lines
+            .filter_map(|line| {
+                let mut columns = line.split('\t');
+                let hash = columns.next()?.to_string();
+                let kotlin_file = PathBuf::from(columns.next()?);
+                let sources: Vec<PathBuf> = columns.map(PathBuf::from).collect();
+                let java_file = sources.first()?.to_owned();
+                Some((
+                    java_file,
+                    Entry {
+                        hash,
+                        kotlin_file,
+                        sources,
+                    },
+                ))
+            })
+            .collect();
+        Manifest { entries }
+    }
+
+    /// The entry for a Java file, if none of its sources changed since it was generated
+    pub fn unchanged(&self, input: &Path, java_file: &Path, config: &Config) -> Option<&Entry> {
+        self.entries.get(java_file).filter(|entry| {
+            hash_sources(input, &entry.sources, config).is_ok_and(|hash| hash == entry.hash)
+        })
+    }
+}
+
+impl std::fmt::Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", header())?;
+        for entry in self.entries.values() {
+            write!(f, "{}\t{}", entry.hash, entry.kotlin_file.display())?;
+            for source in &entry.sources {
+                write!(f, "\t{}", source.display())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn header() -> String {
+    format!("wsdl2kotlin {}", VERSION)
+}
+
+/// Hash of the tool version, the config and the paths and contents of the sources
+pub fn hash_sources(input: &Path, sources: &[PathBuf], config: &Config) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(VERSION);
+    hasher.update(format!("{:?}", config));
+    for source in sources {
+        let path = input.join(source);
+        let content = fs::read(&path).map_err(|why| Error::read(why.to_string()).in_file(&path))?;
+        hasher.update([0]);
+        hasher.update(source.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        hasher.update(content);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let manifest = Manifest {
+            entries: vec![(
+                PathBuf::from("inheritance/abstractclasses/CLevel.java"),
+                Entry {
+                    hash: "abc123".to_string(),
+                    kotlin_file: PathBuf::from("inheritance/abstractclasses/CLevelKt.kt"),
+                    sources: vec![
+                        PathBuf::from("inheritance/abstractclasses/CLevel.java"),
+                        PathBuf::from("inheritance/abstractclasses/BLevel.java"),
+                    ],
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(Manifest::parse(&manifest.to_string()), manifest);
+        assert_eq!(
+            Manifest::parse("wsdl2kotlin 0.0.0\nabc123\tCarKt.kt\tCar.java\n"),
+            Manifest::default()
+        );
+    }
+
+    #[test]
+    fn test_hash_sources() {
+        let input = Path::new("test-resources/src/main/kotlin");
+        let c_level = PathBuf::from("inheritance/abstractclasses/CLevel.java");
+        let b_level = PathBuf::from("inheritance/abstractclasses/BLevel.java");
+        let config = Config::default();
+
+        let hash = hash_sources(input, &[c_level.clone(), b_level.clone()], &config).unwrap();
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(
+            hash_sources(input, &[c_level.clone(), b_level.clone()], &config).unwrap(),
+            hash
+        );
+        assert_ne!(
+            hash_sources(input, std::slice::from_ref(&c_level), &config).unwrap(),
+            hash
+        );
+        let other_config = Config {
+            class_suffix: "Dto".to_string(),
+            ..Config::default()
+        };
+        assert_ne!(
+            hash_sources(input, &[c_level.clone(), b_level.clone()], &other_config).unwrap(),
+            hash
+        );
+        assert!(hash_sources(input, &[c_level, PathBuf::from("Missing.java")], &config).is_err());
+    }
+}
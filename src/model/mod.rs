@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result, Stage};
+use crate::javaparser::Class;
+
+pub const MODEL_FILE: &str = "wsdl2kotlin-model.json";
+
+/// Bumped when the JSON changes in a way that older readers can't handle
+pub const MODEL_VERSION: u32 = 1;
+
+/// The parsed and resolved classes, before they are converted to Kotlin.
+///
+/// Written as JSON with `generate --emit-model json`, and read back by `render`, so that other
+/// generators can work from the same analysis. Superclasses are resolved with their fields, the
+/// subclasses of a class are listed, and `JAXBElement` fields have their `ObjectFactory` method
+#[derive(PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Model {
+    pub version: u32,
+    pub classes: Vec<ModelClass>,
+}
+
+/// A class of the model, with the Java file it was parsed from
+#[derive(PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct ModelClass {
+    /// The Java file relative to the input folder, which the Kotlin file is written next to.
+    /// None for classes from WSDL and XSD files, which are written in the folder of their package
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<PathBuf>,
+    #[serde(flatten)]
+    pub class: Class,
+}
+
+impl Model {
+    pub fn new(classes: Vec<ModelClass>) -> Model {
+        Model {
+            version: MODEL_VERSION,
+            classes,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Model> {
+        fs::read_to_string(path)
+            .map_err(|why| Error::read(why.to_string()))
+            .and_then(|content| Model::parse(&content))
+            .map_err(|error| error.in_file(path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let write_error = |why: String| Error::write(why).in_file(path);
+        let json =
+            serde_json::to_string_pretty(self).map_err(|why| write_error(why.to_string()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|why| write_error(why.to_string()))?;
+        }
+        fs::write(path, json + "\n").map_err(|why| write_error(why.to_string()))
+    }
+
+    fn parse(content: &str) -> Result<Model> {
+        let model: Model = serde_json::from_str(content)
+            .map_err(|why| Error::new(Stage::Parse, format!("invalid model: {}", why)))?;
+        if model.version != MODEL_VERSION {
+            return Err(Error::new(
+                Stage::Parse,
+                format!(
+                    "model version {} is not supported, expected {}",
+                    model.version, MODEL_VERSION
+                ),
+            ));
+        }
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::javaparser;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let source_code =
+            fs::read_to_string("test-resources/src/main/kotlin/dataclasses/Tyre.java").unwrap();
+        let tyre = javaparser::parse_class(&source_code, &Config::default()).unwrap();
+        let model = Model::new(vec![ModelClass {
+            source: Some(PathBuf::from("dataclasses/Tyre.java")),
+            class: tyre,
+        }]);
+
+        let json = serde_json::to_string(&model).unwrap();
+
+        assert_eq!(Model::parse(&json).unwrap(), model);
+        assert!(json.contains(r#""source":"dataclasses/Tyre.java","package":"dataclasses""#));
+        assert!(json.contains(r#""facets":{"min_length":1,"max_length":40"#));
+        assert!(Model::parse(r#"{"version":0,"classes":[]}"#)
+            .unwrap_err()
+            .to_string()
+            .contains("model version 0 is not supported, expected 1"));
+        assert!(Model::parse(r#"{"classes":[]}"#)
+            .unwrap_err()
+            .to_string()
+            .contains("invalid model: missing field `version`"));
+    }
+}
@@ -0,0 +1,1765 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use roxmltree::{Document, Node, TextPos};
+
+use crate::error::{Error, Position, Result};
+use crate::javaparser::{
+    Argument, Binding, Class, EnumConstant, Facets, Field, Function, InnerClass, ReturnType,
+    Subclass, Superclass, Type,
+};
+
+const XSD: &str = "http://www.w3.org/2001/XMLSchema";
+const WSDL: &str = "http://schemas.xmlsoap.org/wsdl/";
+const SOAP: &str = "http://schemas.xmlsoap.org/wsdl/soap/";
+const SOAP12: &str = "http://schemas.xmlsoap.org/wsdl/soap12/";
+
+const JAVA_KEYWORDS: [&str; 50] = [
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+];
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+struct QName {
+    namespace: String,
+    name: String,
+}
+
+/// The Java type CXF generates for a schema type
+#[derive(Clone, Debug)]
+struct TypeRef {
+    class: String,
+    package: Option<String>,
+    builtin: bool,
+    primitive: Option<&'static str>,
+    /// The XSD type, for types that CXF annotates with `@XmlSchemaType`
+    schema_type: Option<String>,
+}
+
+impl TypeRef {
+    fn builtin(class: &str, primitive: Option<&'static str>) -> TypeRef {
+        TypeRef {
+            class: class.to_string(),
+            package: None,
+            builtin: true,
+            primitive,
+            schema_type: None,
+        }
+    }
+
+    fn class(class: String, package: &str) -> TypeRef {
+        TypeRef {
+            class,
+            package: Some(package.to_string()),
+            builtin: false,
+            primitive: None,
+            schema_type: None,
+        }
+    }
+
+    /// Name as written in a Java class in `package`, fully qualified if it's in another package
+    fn name_in(&self, package: &str) -> String {
+        match &self.package {
+            Some(type_package) if type_package != package && !self.is_stdlib() => {
+                format!("{}.{}", type_package, self.class)
+            }
+            _ => self.class.clone(),
+        }
+    }
+
+    fn is_stdlib(&self) -> bool {
+        self.package
+            .as_ref()
+            .is_none_or(|package| package.starts_with("java"))
+    }
+}
+
+/// The class being read, and whether it is an inner class
+struct ClassContext<'c> {
+    name: &'c str,
+    package: &'c str,
+    inner: bool,
+}
+
+#[derive(Default)]
+struct Content {
+    fields: Vec<Field>,
+    inner_classes: Vec<InnerClass>,
+    base: Option<QName>,
+}
+
+struct Schemas<'a, 'input> {
+    documents: &'a [(PathBuf, Document<'input>)],
+    complex_types: HashMap<QName, Node<'a, 'input>>,
+    simple_types: HashMap<QName, Node<'a, 'input>>,
+    elements: HashMap<QName, Node<'a, 'input>>,
+    groups: HashMap<QName, Node<'a, 'input>>,
+    attribute_groups: HashMap<QName, Node<'a, 'input>>,
+    messages: HashMap<QName, Node<'a, 'input>>,
+    bindings: Vec<Node<'a, 'input>>,
+}
+
+/// Reads the classes CXF wsdl2java would generate for a set of `.wsdl` and `.xsd` files
+pub fn parse_schemas(sources: &[(PathBuf, String)]) -> Vec<Result<Class>> {
+    let mut results: Vec<Result<Class>> = vec![];
+
+    let documents: Vec<(PathBuf, Document)> = sources
+        .iter()
+        .filter_map(|(path, content)| match Document::parse(content) {
+            Ok(document) => Some((path.clone(), document)),
+            Err(why) => {
+                results.push(Err(
+                    Error::parse(why.to_string(), to_position(why.pos())).in_file(path)
+                ));
+                None
+            }
+        })
+        .collect();
+
+    let schemas = Schemas::new(&documents);
+    results.extend(resolve_inheritance(schemas.classes()));
+    results
+}
+
+impl<'a, 'input> Schemas<'a, 'input> {
+    fn new(documents: &'a [(PathBuf, Document<'input>)]) -> Schemas<'a, 'input> {
+        let mut schemas = Schemas {
+            documents,
+            complex_types: HashMap::new(),
+            simple_types: HashMap::new(),
+            elements: HashMap::new(),
+            groups: HashMap::new(),
+            attribute_groups: HashMap::new(),
+            messages: HashMap::new(),
+            bindings: vec![],
+        };
+
+        for (_, document) in documents {
+            for schema in document
+                .descendants()
+                .filter(|node| node.has_tag_name((XSD, "schema")))
+            {
+                let namespace = schema.attribute("targetNamespace").unwrap_or("");
+                for definition in xsd_children(schema) {
+                    let name = match definition.attribute("name") {
+                        Some(name) => qname(namespace, name),
+                        None => continue,
+                    };
+                    let definitions = match definition.tag_name().name() {
+                        "complexType" => &mut schemas.complex_types,
+                        "simpleType" => &mut schemas.simple_types,
+                        "element" => &mut schemas.elements,
+                        "group" => &mut schemas.groups,
+                        "attributeGroup" => &mut schemas.attribute_groups,
+                        _ => continue,
+                    };
+                    definitions.insert(name, definition);
+                }
+            }
+
+            let root = document.root_element();
+            if root.has_tag_name((WSDL, "definitions")) {
+                let namespace = root.attribute("targetNamespace").unwrap_or("");
+                for definition in root.children().filter(|node| node.is_element()) {
+                    if definition.has_tag_name((WSDL, "message")) {
+                        if let Some(name) = definition.attribute("name") {
+                            schemas.messages.insert(qname(namespace, name), definition);
+                        }
+                    } else if definition.has_tag_name((WSDL, "binding")) {
+                        schemas.bindings.push(definition);
+                    }
+                }
+            }
+        }
+        schemas
+    }
+
+    fn classes(&self) -> Vec<Result<Class>> {
+        let mut classes = vec![];
+        for (_, document) in self.documents {
+            for schema in document
+                .descendants()
+                .filter(|node| node.has_tag_name((XSD, "schema")))
+            {
+                let package = package_name(schema.attribute("targetNamespace").unwrap_or(""));
+                for definition in xsd_children(schema) {
+                    let name = match definition.attribute("name") {
+                        Some(name) => name,
+                        None => continue,
+                    };
+                    match definition.tag_name().name() {
+                        "complexType" => {
+                            classes.push(self.complex_class(name, &package, definition))
+                        }
+                        "element" => {
+                            if let Some(complex_type) = xsd_child(definition, "complexType") {
+                                classes.push(self.complex_class(name, &package, complex_type))
+                            }
+                        }
+                        "simpleType" => {
+                            if let Some(enum_class) = self.enum_class(name, &package, definition) {
+                                classes.push(Ok(enum_class))
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            let root = document.root_element();
+            if root.has_tag_name((WSDL, "definitions")) {
+                let namespace = root.attribute("targetNamespace").unwrap_or("");
+                for port_type in root
+                    .children()
+                    .filter(|node| node.has_tag_name((WSDL, "portType")))
+                {
+                    classes.push(self.port_type_class(namespace, port_type));
+                }
+            }
+        }
+        classes
+    }
+
+    fn complex_class(&self, xml_name: &str, package: &str, node: Node) -> Result<Class> {
+        let name = java_class_name(xml_name);
+        let context = ClassContext {
+            name: &name,
+            package,
+            inner: false,
+        };
+        let mut content = Content::default();
+        self.read_content(node, &context, &mut content)?;
+
+        let superclass = match content.base {
+            Some(base) => match self.complex_types.get(&base) {
+                Some(_) => Some(Superclass {
+                    name: java_class_name(&base.name),
+                    package: package_name(&base.namespace),
+                    ..Default::default()
+                }),
+                None if base.namespace == XSD => None,
+                None => return Err(self.error(node, format!("unknown base type '{}'", base.name))),
+            },
+            None => None,
+        };
+
+        Ok(Class {
+            package: package.to_string(),
+            name,
+            fields: content.fields,
+            is_abstract: node.attribute("abstract") == Some("true"),
+            superclass,
+            inner_classes: content.inner_classes,
+            doc: class_doc(node),
+            ..Default::default()
+        })
+    }
+
+    fn enum_class(&self, xml_name: &str, package: &str, node: Node) -> Option<Class> {
+        let constants: Vec<EnumConstant> = xsd_child(node, "restriction")
+            .map(|restriction| {
+                xsd_children(restriction)
+                    .filter(|facet| facet.has_tag_name((XSD, "enumeration")))
+                    .filter_map(|facet| Some((facet, facet.attribute("value")?)))
+                    .map(|(facet, value)| EnumConstant {
+                        name: enum_constant_name(value),
+                        xml_value: value.to_string(),
+                        doc: documentation(facet),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if constants.is_empty() {
+            None
+        } else {
+            Some(Class {
+                package: package.to_string(),
+                name: java_class_name(xml_name),
+                enum_constants: constants,
+                doc: class_doc(node),
+                ..Default::default()
+            })
+        }
+    }
+
+    fn read_content(
+        &self,
+        node: Node,
+        context: &ClassContext,
+        content: &mut Content,
+    ) -> Result<()> {
+        for child in xsd_children(node) {
+            match child.tag_name().name() {
+                "sequence" | "choice" | "all" | "group" => {
+                    self.read_particles(child, context, false, false, content)?
+                }
+                "attribute" => content.fields.push(self.attribute_field(child, context)?),
+                "anyAttribute" => content.fields.push(Field {
+                    name: "otherAttributes".to_string(),
+                    package: context.package.to_string(),
+                    r#type: qname_type(),
+                    generic_type: Some("Map".to_string()),
+                    type_arguments: vec![
+                        qname_type(),
+                        Type {
+                            package: None,
+                            class: "String".to_string(),
+                            stdlib: true,
+                            arguments: vec![],
+                        },
+                    ],
+                    nullable: true,
+                    binding: Binding::Any,
+                    ..Default::default()
+                }),
+                "attributeGroup" => {
+                    let group = self.referenced(child, &self.attribute_groups)?;
+                    self.read_content(group, context, content)?
+                }
+                "complexContent" => {
+                    if let Some(derivation) = xsd_children(child).next() {
+                        content.base = self.base(derivation)?;
+                        if derivation.has_tag_name((XSD, "extension")) {
+                            self.read_content(derivation, context, content)?
+                        }
+                    }
+                }
+                "simpleContent" => {
+                    if let Some(derivation) = xsd_children(child).next() {
+                        let base = self.base(derivation)?;
+                        match base
+                            .as_ref()
+                            .filter(|base| self.complex_types.contains_key(base))
+                        {
+                            Some(_) => content.base = base,
+                            None => content.fields.push(self.value_field(derivation, context)?),
+                        }
+                        self.read_content(derivation, context, content)?
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn read_particles(
+        &self,
+        node: Node,
+        context: &ClassContext,
+        repeated: bool,
+        optional: bool,
+        content: &mut Content,
+    ) -> Result<()> {
+        let node = if node.has_tag_name((XSD, "group")) {
+            match xsd_children(self.referenced(node, &self.groups)?).next() {
+                Some(particle) => particle,
+                None => return Ok(()),
+            }
+        } else {
+            node
+        };
+        let repeated = repeated || is_repeated(node);
+        let optional = optional || is_optional(node) || node.has_tag_name((XSD, "choice"));
+
+        for child in xsd_children(node) {
+            match child.tag_name().name() {
+                "element" => {
+                    let field = self.element_field(child, context, repeated, optional, content)?;
+                    content.fields.push(field)
+                }
+                "sequence" | "choice" | "all" | "group" => {
+                    self.read_particles(child, context, repeated, optional, content)?
+                }
+                "any" => {
+                    // JAXB only unmarshals the known elements of a lax wildcard
+                    let lax = child.attribute("processContents") == Some("lax");
+                    let r#type = match lax {
+                        true => Type {
+                            package: None,
+                            class: "Object".to_string(),
+                            stdlib: true,
+                            arguments: vec![],
+                        },
+                        false => Type {
+                            package: Some("org.w3c.dom".to_string()),
+                            class: "Element".to_string(),
+                            stdlib: false,
+                            arguments: vec![],
+                        },
+                    };
+                    let repeated = repeated || is_repeated(child);
+                    content.fields.push(Field {
+                        name: "any".to_string(),
+                        package: context.package.to_string(),
+                        type_arguments: repeated.then(|| r#type.clone()).into_iter().collect(),
+                        r#type,
+                        generic_type: repeated.then(|| "List".to_string()),
+                        builtin: lax,
+                        nullable: true,
+                        binding: Binding::Any,
+                        ..Default::default()
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn element_field(
+        &self,
+        node: Node,
+        context: &ClassContext,
+        repeated: bool,
+        optional: bool,
+        content: &mut Content,
+    ) -> Result<Field> {
+        let (declaration, xml_name) = match node.attribute("ref") {
+            Some(_) => {
+                let declaration = self.referenced(node, &self.elements)?;
+                (
+                    declaration,
+                    declaration.attribute("name").unwrap_or_default(),
+                )
+            }
+            None => (
+                node,
+                node.attribute("name")
+                    .ok_or_else(|| self.error(node, "element without name or ref"))?,
+            ),
+        };
+
+        let repeated = repeated || is_repeated(node);
+        let optional = optional || is_optional(node);
+        let nillable = declaration.attribute("nillable") == Some("true");
+
+        let type_ref = if let Some(type_name) = declaration.attribute("type") {
+            self.type_ref(declaration, type_name)?
+        } else if let Some(complex_type) = xsd_child(declaration, "complexType") {
+            if context.inner {
+                return Err(self.error(
+                    complex_type,
+                    format!(
+                        "anonymous type of '{}' is nested more than one level deep",
+                        xml_name
+                    ),
+                ));
+            }
+            let inner_name = java_class_name(xml_name);
+            let inner_context = ClassContext {
+                name: &inner_name,
+                package: context.package,
+                inner: true,
+            };
+            let mut inner_content = Content::default();
+            self.read_content(complex_type, &inner_context, &mut inner_content)?;
+            content.inner_classes.push(InnerClass {
+                name: inner_name.clone(),
+                fields: inner_content.fields,
+                doc: class_doc(complex_type),
+            });
+            TypeRef::class(format!("{}.{}", context.name, inner_name), context.package)
+        } else if let Some(simple_type) = xsd_child(declaration, "simpleType") {
+            self.simple_type_ref(simple_type)?
+        } else {
+            TypeRef::builtin("Object", None)
+        };
+
+        let primitive = !optional && !nillable && !repeated && type_ref.primitive.is_some();
+        let generic_type = if repeated {
+            Some("List".to_string())
+        } else if optional && nillable {
+            Some("JAXBElement".to_string())
+        } else {
+            None
+        };
+
+        let doc = match documentation(node) {
+            doc if doc.is_empty() => documentation(declaration),
+            doc => doc,
+        };
+        Ok(Field {
+            doc,
+            facets: self.simple_type_facets(declaration)?,
+            ..field(
+                java_variable_name(xml_name),
+                xml_name,
+                context.package,
+                type_ref,
+                generic_type,
+                primitive,
+                !primitive && (optional || nillable),
+            )
+        })
+    }
+
+    fn attribute_field(&self, node: Node, context: &ClassContext) -> Result<Field> {
+        let (declaration, xml_name) = match node.attribute("ref") {
+            Some(reference) => (node, self.qname(node, reference)?.name),
+            None => (
+                node,
+                node.attribute("name")
+                    .ok_or_else(|| self.error(node, "attribute without name or ref"))?
+                    .to_string(),
+            ),
+        };
+
+        let type_ref = match declaration.attribute("type") {
+            Some(type_name) => self.type_ref(declaration, type_name)?,
+            None => match xsd_child(declaration, "simpleType") {
+                Some(simple_type) => self.simple_type_ref(simple_type)?,
+                None => TypeRef::builtin("String", None),
+            },
+        };
+
+        let required = node.attribute("use") == Some("required");
+        let primitive = required && type_ref.primitive.is_some();
+        Ok(Field {
+            binding: Binding::Attribute,
+            doc: documentation(declaration),
+            facets: self.simple_type_facets(declaration)?,
+            ..field(
+                java_variable_name(&xml_name),
+                &xml_name,
+                context.package,
+                type_ref,
+                None,
+                primitive,
+                !required,
+            )
+        })
+    }
+
+    /// The `value` field of a class with simple content
+    fn value_field(&self, derivation: Node, context: &ClassContext) -> Result<Field> {
+        let type_ref = match derivation.attribute("base") {
+            Some(base) => self.type_ref(derivation, base)?,
+            None => TypeRef::builtin("String", None),
+        };
+        let mut value = field(
+            "value".to_string(),
+            "",
+            context.package,
+            type_ref,
+            None,
+            false,
+            true,
+        );
+        value.xml_name = None;
+        value.binding = Binding::Value;
+        Ok(value)
+    }
+
+    fn base(&self, derivation: Node) -> Result<Option<QName>> {
+        derivation
+            .attribute("base")
+            .map(|base| self.qname(derivation, base))
+            .transpose()
+    }
+
+    fn type_ref(&self, node: Node, type_name: &str) -> Result<TypeRef> {
+        let name = self.qname(node, type_name)?;
+        if name.namespace == XSD {
+            Ok(xsd_type(&name.name))
+        } else if self.complex_types.contains_key(&name) {
+            Ok(TypeRef::class(
+                java_class_name(&name.name),
+                &package_name(&name.namespace),
+            ))
+        } else if let Some(simple_type) = self.simple_types.get(&name) {
+            if is_enumeration(*simple_type) {
+                Ok(TypeRef::class(
+                    java_class_name(&name.name),
+                    &package_name(&name.namespace),
+                ))
+            } else {
+                self.simple_type_ref(*simple_type)
+            }
+        } else {
+            Err(self.error(node, format!("unknown type '{}'", type_name)))
+        }
+    }
+
+    /// The facets of the anonymous or named simple type of an element or attribute. Named
+    /// enumerations are Java enums, and have no facets
+    fn simple_type_facets(&self, declaration: Node) -> Result<Facets> {
+        let simple_type = match declaration.attribute("type") {
+            Some(type_name) => self
+                .simple_types
+                .get(&self.qname(declaration, type_name)?)
+                .copied()
+                .filter(|simple_type| !is_enumeration(*simple_type)),
+            None => xsd_child(declaration, "simpleType"),
+        };
+        Ok(simple_type
+            .and_then(|simple_type| xsd_child(simple_type, "restriction"))
+            .map_or_else(Facets::default, facets))
+    }
+
+    fn simple_type_ref(&self, simple_type: Node) -> Result<TypeRef> {
+        match xsd_child(simple_type, "restriction") {
+            Some(restriction) => match restriction.attribute("base") {
+                Some(base) => self.type_ref(restriction, base),
+                None => match xsd_child(restriction, "simpleType") {
+                    Some(inner) => self.simple_type_ref(inner),
+                    None => Ok(TypeRef::builtin("String", None)),
+                },
+            },
+            // Lists and unions are bound to String
+            None => Ok(TypeRef::builtin("String", None)),
+        }
+    }
+
+    fn port_type_class(&self, namespace: &str, port_type: Node) -> Result<Class> {
+        let package = package_name(namespace);
+        let xml_name = port_type.attribute("name").unwrap_or_default();
+        let port_type_name = qname(namespace, xml_name);
+
+        let binding = self.bindings.iter().find(|binding| {
+            binding
+                .attribute("type")
+                .and_then(|type_name| self.qname(**binding, type_name).ok())
+                == Some(port_type_name.clone())
+        });
+
+        let functions = port_type
+            .children()
+            .filter(|node| node.has_tag_name((WSDL, "operation")))
+            .map(|operation| {
+                let operation_name = operation.attribute("name").unwrap_or_default();
+                let binding_operation = binding.and_then(|binding| {
+                    binding.children().find(|node| {
+                        node.has_tag_name((WSDL, "operation"))
+                            && node.attribute("name") == Some(operation_name)
+                    })
+                });
+                self.function(&package, operation, binding_operation)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Class {
+            package,
+            name: java_class_name(xml_name),
+            functions,
+            ..Default::default()
+        })
+    }
+
+    fn function(
+        &self,
+        package: &str,
+        operation: Node,
+        binding_operation: Option<Node>,
+    ) -> Result<Function> {
+        let headers: Vec<(QName, &str)> = binding_operation
+            .and_then(|binding_operation| {
+                binding_operation
+                    .children()
+                    .find(|node| node.has_tag_name((WSDL, "input")))
+            })
+            .map(|input| {
+                input
+                    .children()
+                    .filter(|node| {
+                        node.has_tag_name((SOAP, "header")) || node.has_tag_name((SOAP12, "header"))
+                    })
+                    .filter_map(|header| {
+                        let message = self.qname(header, header.attribute("message")?).ok()?;
+                        Some((message, header.attribute("part")?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let input = self.message(operation, "input")?;
+        let mut arguments: Vec<Argument> = vec![];
+        if let Some((input_name, input)) = &input {
+            for part in message_parts(*input) {
+                let part_name = part.attribute("name").unwrap_or_default();
+                let header = headers.contains(&((*input_name).clone(), part_name));
+                arguments.push(self.argument(package, part, header)?);
+            }
+        }
+        for (message_name, part_name) in headers.iter() {
+            if input
+                .as_ref()
+                .is_some_and(|(input_name, _)| input_name == message_name)
+            {
+                continue;
+            }
+            let message = self.messages.get(message_name).ok_or_else(|| {
+                self.error(
+                    operation,
+                    format!("unknown message '{}'", message_name.name),
+                )
+            })?;
+            if let Some(part) =
+                message_parts(*message).find(|part| part.attribute("name") == Some(part_name))
+            {
+                arguments.push(self.argument(package, part, true)?);
+            }
+        }
+
+        let return_type = match self.message(operation, "output")? {
+            Some((_, output)) => match message_parts(output).next() {
+                Some(part) => {
+                    let type_ref = self.part_type(part)?;
+                    Some(ReturnType {
+                        r#type: Type {
+                            class: type_ref.name_in(package),
+                            stdlib: type_ref.is_stdlib(),
+                            package: type_ref.package,
+                            arguments: vec![],
+                        },
+                        generic_type: None,
+                        builtin: type_ref.builtin,
+                        nullable: true,
+                    })
+                }
+                None => None,
+            },
+            None => None,
+        };
+
+        Ok(Function {
+            name: java_variable_name(operation.attribute("name").unwrap_or_default()),
+            arguments,
+            return_type,
+            // The fault exceptions are only read from Java sources
+            faults: vec![],
+            fault_classes: vec![],
+            doc: documentation(operation),
+        })
+    }
+
+    fn message(
+        &self,
+        operation: Node,
+        direction: &str,
+    ) -> Result<Option<(QName, Node<'a, 'input>)>> {
+        let reference = match operation
+            .children()
+            .find(|node| node.has_tag_name((WSDL, direction)))
+            .and_then(|node| Some((node, node.attribute("message")?)))
+        {
+            Some(reference) => reference,
+            None => return Ok(None),
+        };
+        let name = self.qname(reference.0, reference.1)?;
+        match self.messages.get(&name) {
+            Some(message) => Ok(Some((name, *message))),
+            None => Err(self.error(reference.0, format!("unknown message '{}'", reference.1))),
+        }
+    }
+
+    /// The parameter for a part, named like the `@WebParam` CXF writes for it
+    fn argument(&self, package: &str, part: Node, header: bool) -> Result<Argument> {
+        let part_name = part.attribute("name").unwrap_or_default();
+        let element = match part.attribute("element") {
+            Some(element) => Some(self.qname(part, element)?),
+            None => None,
+        };
+        Ok(Argument {
+            name: java_variable_name(part_name),
+            r#type: self.part_type(part)?.name_in(package),
+            nullable: header,
+            part_name: Some(part_name.to_string()),
+            xml_name: Some(
+                element
+                    .as_ref()
+                    .map_or(part_name.to_string(), |element| element.name.clone()),
+            ),
+            target_namespace: element.map(|element| element.namespace),
+            header,
+            ..Default::default()
+        })
+    }
+
+    fn part_type(&self, part: Node) -> Result<TypeRef> {
+        if let Some(element) = part.attribute("element") {
+            let name = self.qname(part, element)?;
+            let declaration = self
+                .elements
+                .get(&name)
+                .ok_or_else(|| self.error(part, format!("unknown element '{}'", element)))?;
+            match declaration.attribute("type") {
+                Some(type_name) => self.type_ref(*declaration, type_name),
+                None => Ok(TypeRef::class(
+                    java_class_name(&name.name),
+                    &package_name(&name.namespace),
+                )),
+            }
+        } else if let Some(type_name) = part.attribute("type") {
+            self.type_ref(part, type_name)
+        } else {
+            Err(self.error(part, "part without element or type"))
+        }
+    }
+
+    fn referenced(
+        &self,
+        node: Node,
+        definitions: &HashMap<QName, Node<'a, 'input>>,
+    ) -> Result<Node<'a, 'input>> {
+        let reference = node
+            .attribute("ref")
+            .ok_or_else(|| self.error(node, format!("{} without ref", node.tag_name().name())))?;
+        definitions
+            .get(&self.qname(node, reference)?)
+            .copied()
+            .ok_or_else(|| self.error(node, format!("unknown reference '{}'", reference)))
+    }
+
+    fn qname(&self, node: Node, prefixed_name: &str) -> Result<QName> {
+        let (prefix, name) = match prefixed_name.split_once(':') {
+            Some((prefix, name)) => (Some(prefix), name),
+            None => (None, prefixed_name),
+        };
+        let namespace = node.lookup_namespace_uri(prefix).or(match prefix {
+            Some(_) => None,
+            None => Some(""),
+        });
+        match namespace {
+            Some(namespace) => Ok(qname(namespace, name)),
+            None => Err(self.error(
+                node,
+                format!("unknown namespace prefix in '{}'", prefixed_name),
+            )),
+        }
+    }
+
+    fn error(&self, node: Node, message: impl Into<String>) -> Error {
+        let position = to_position(node.document().text_pos_at(node.range().start));
+        let error = Error::parse(message, position);
+        match self
+            .documents
+            .iter()
+            .find(|(_, document)| std::ptr::eq(document, node.document()))
+        {
+            Some((path, _)) => error.in_file(path),
+            None => error,
+        }
+    }
+}
+
+/// Fills in inherited fields and subclasses, which needs all classes to be read first
+fn resolve_inheritance(mut classes: Vec<Result<Class>>) -> Vec<Result<Class>> {
+    let index: HashMap<(String, String), usize> = classes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, class)| {
+            class
+                .as_ref()
+                .ok()
+                .map(|class| ((class.package.clone(), class.name.clone()), i))
+        })
+        .collect();
+
+    let parent = |classes: &Vec<Result<Class>>, i: usize| -> Option<usize> {
+        let superclass = classes[i].as_ref().ok()?.superclass.as_ref()?;
+        index
+            .get(&(superclass.package.clone(), superclass.name.clone()))
+            .copied()
+    };
+
+    let mut resolved: Vec<(usize, Vec<Field>, bool)> = vec![];
+    for i in 0..classes.len() {
+        let mut ancestors = vec![];
+        let mut current = parent(&classes, i);
+        while let Some(ancestor) = current {
+            if ancestors.contains(&ancestor) || ancestor == i {
+                break;
+            }
+            ancestors.push(ancestor);
+            current = parent(&classes, ancestor);
+        }
+        if let Some(direct_parent) = ancestors.first() {
+            let fields = ancestors
+                .iter()
+                .rev()
+                .filter_map(|ancestor| classes[*ancestor].as_ref().ok())
+                .flat_map(|ancestor| ancestor.fields.clone())
+                .collect();
+            let is_abstract = classes[*direct_parent]
+                .as_ref()
+                .is_ok_and(|class| class.is_abstract);
+            resolved.push((i, fields, is_abstract));
+        }
+    }
+
+    for (i, fields, is_abstract) in resolved {
+        let (subclass, parent_index) = match &mut classes[i] {
+            Ok(class) => {
+                let superclass = class.superclass.as_mut().unwrap();
+                superclass.fields = fields;
+                superclass.is_abstract = is_abstract;
+                let parent_index = index[&(superclass.package.clone(), superclass.name.clone())];
+                (
+                    (
+                        class.name.clone(),
+                        class.package.clone(),
+                        superclass.package.clone(),
+                    ),
+                    parent_index,
+                )
+            }
+            Err(_) => continue,
+        };
+        if let Ok(parent) = &mut classes[parent_index] {
+            let (name, package, parent_package) = subclass;
+            parent.subclasses.push(Subclass {
+                name,
+                package: (package != parent_package).then_some(package),
+            });
+        }
+    }
+
+    for class in classes.iter_mut() {
+        let missing_superclass = match class {
+            Ok(Class {
+                superclass: Some(superclass),
+                ..
+            }) if !index.contains_key(&(superclass.package.clone(), superclass.name.clone())) => {
+                Some(format!(
+                    "superclass {}.{} not found",
+                    superclass.package, superclass.name
+                ))
+            }
+            _ => None,
+        };
+        if let Some(message) = missing_superclass {
+            *class = Err(Error::new(crate::error::Stage::Parse, message));
+        }
+    }
+    classes
+}
+
+/// The `QName` keys of the other attributes of an `xs:anyAttribute`
+fn qname_type() -> Type {
+    Type {
+        package: Some("javax.xml.namespace".to_string()),
+        class: "QName".to_string(),
+        stdlib: true,
+        arguments: vec![],
+    }
+}
+
+fn field(
+    name: String,
+    xml_name: &str,
+    package: &str,
+    type_ref: TypeRef,
+    generic_type: Option<String>,
+    primitive: bool,
+    nullable: bool,
+) -> Field {
+    let stdlib = type_ref.is_stdlib();
+    let r#type = Type {
+        package: type_ref.package,
+        class: match (primitive, type_ref.primitive) {
+            (true, Some(primitive)) => primitive.to_string(),
+            _ => type_ref.class,
+        },
+        stdlib,
+        arguments: vec![],
+    };
+    Field {
+        name,
+        xml_name: Some(xml_name.to_string()),
+        package: package.to_string(),
+        type_arguments: generic_type.iter().map(|_| r#type.clone()).collect(),
+        r#type,
+        generic_type,
+        builtin: type_ref.builtin,
+        nullable,
+        binding: Binding::Element,
+        schema_type: type_ref.schema_type,
+        factory_method: None,
+        choice: None,
+        doc: vec![],
+        facets: Facets::default(),
+    }
+}
+
+fn xsd_type(name: &str) -> TypeRef {
+    match name {
+        "int" | "unsignedShort" => TypeRef::builtin("Integer", Some("int")),
+        "long" | "unsignedInt" => TypeRef::builtin("Long", Some("long")),
+        "short" | "unsignedByte" => TypeRef::builtin("Short", Some("short")),
+        "byte" => TypeRef::builtin("Byte", Some("byte")),
+        "boolean" => TypeRef::builtin("Boolean", Some("boolean")),
+        "double" => TypeRef::builtin("Double", Some("double")),
+        "float" => TypeRef::builtin("Float", Some("float")),
+        "base64Binary" | "hexBinary" => TypeRef::builtin("byte[]", None),
+        "anyType" | "anySimpleType" => TypeRef::builtin("Object", None),
+        "decimal" => TypeRef::class("BigDecimal".to_string(), "java.math"),
+        "integer" | "nonNegativeInteger" | "positiveInteger" | "nonPositiveInteger"
+        | "negativeInteger" | "unsignedLong" => {
+            TypeRef::class("BigInteger".to_string(), "java.math")
+        }
+        "date" | "dateTime" | "time" | "gYear" | "gYearMonth" | "gMonth" | "gMonthDay" | "gDay" => {
+            TypeRef {
+                schema_type: Some(name.to_string()),
+                ..TypeRef::class("XMLGregorianCalendar".to_string(), "javax.xml.datatype")
+            }
+        }
+        "duration" => TypeRef::class("Duration".to_string(), "javax.xml.datatype"),
+        "QName" => TypeRef::class("QName".to_string(), "javax.xml.namespace"),
+        _ => TypeRef::builtin("String", None),
+    }
+}
+
+/// The text of the `xs:documentation` of a declaration, or the `wsdl:documentation` of an
+/// operation, as KDoc lines
+fn documentation(node: Node) -> Vec<String> {
+    let text = xsd_child(node, "annotation")
+        .and_then(|annotation| xsd_child(annotation, "documentation"))
+        .or_else(|| {
+            node.children()
+                .find(|child| child.has_tag_name((WSDL, "documentation")))
+        })
+        .and_then(|documentation| documentation.text())
+        .unwrap_or_default();
+
+    let mut lines: Vec<String> = vec![];
+    for line in text.lines().map(str::trim) {
+        if !line.is_empty() || lines.last().is_some_and(|last| !last.is_empty()) {
+            lines.push(line.to_string());
+        }
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// The KDoc of a class: the documentation of the type, or of the element of an anonymous type,
+/// and the schema fragment of the type like JAXB puts in the Javadoc
+fn class_doc(node: Node) -> Vec<String> {
+    let mut doc = documentation(node);
+    if doc.is_empty() && node.attribute("name").is_none() {
+        if let Some(element) = node.parent_element() {
+            doc = documentation(element);
+        }
+    }
+    if !doc.is_empty() {
+        doc.push(String::new());
+    }
+    doc.push(
+        "The following schema fragment specifies the expected content contained within this class."
+            .to_string(),
+    );
+    doc.push(String::new());
+    doc.push("```xml".to_string());
+    doc.extend(schema_fragment(node));
+    doc.push("```".to_string());
+    doc
+}
+
+/// The source of a node without its annotations, and without the indentation it has in the
+/// document
+fn schema_fragment(node: Node) -> Vec<String> {
+    let document = node.document();
+    let text = document.input_text();
+    let mut source = String::new();
+    let mut position = node.range().start;
+    for annotation in node
+        .descendants()
+        .filter(|descendant| descendant.has_tag_name((XSD, "annotation")))
+    {
+        // From the end of the line before the annotation
+        let start = text[..annotation.range().start]
+            .rfind('\n')
+            .unwrap_or_default();
+        if start >= position {
+            source.push_str(&text[position..start]);
+            position = annotation.range().end;
+        }
+    }
+    source.push_str(&text[position..node.range().end]);
+
+    let indentation = document.text_pos_at(node.range().start).col as usize - 1;
+    source
+        .lines()
+        .map(|line| {
+            let indented = line.len() - line.trim_start().len();
+            line[indented.min(indentation)..].trim_end().to_string()
+        })
+        .collect()
+}
+
+/// The facets of a restriction, from the schema or from the schema fragment in a Javadoc
+pub fn facets(restriction: Node) -> Facets {
+    let mut facets = Facets::default();
+    for facet in restriction.children().filter(|child| child.is_element()) {
+        let value = facet.attribute("value").unwrap_or_default();
+        let number = value.parse::<usize>().ok();
+        match facet.tag_name().name() {
+            "length" => {
+                facets.min_length = number;
+                facets.max_length = number;
+            }
+            "minLength" => facets.min_length = number,
+            "maxLength" => facets.max_length = number,
+            // The patterns of a restriction are alternatives
+            "pattern" => {
+                facets.pattern = Some(match facets.pattern {
+                    Some(pattern) => format!("(?:{})|(?:{})", pattern, value),
+                    None => value.to_string(),
+                })
+            }
+            "totalDigits" => facets.total_digits = number,
+            "enumeration" => facets.enumeration.push(value.to_string()),
+            _ => {}
+        }
+    }
+    facets
+}
+
+fn is_enumeration(simple_type: Node) -> bool {
+    xsd_child(simple_type, "restriction").is_some_and(|restriction| {
+        xsd_children(restriction).any(|facet| facet.has_tag_name((XSD, "enumeration")))
+    })
+}
+
+fn is_repeated(node: Node) -> bool {
+    match node.attribute("maxOccurs") {
+        Some("unbounded") => true,
+        Some(max) => max.parse::<u32>().is_ok_and(|max| max > 1),
+        None => false,
+    }
+}
+
+fn is_optional(node: Node) -> bool {
+    node.attribute("minOccurs") == Some("0")
+}
+
+fn message_parts<'a, 'input>(message: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    message
+        .children()
+        .filter(|node| node.has_tag_name((WSDL, "part")))
+}
+
+fn xsd_children<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(|child| child.is_element() && child.tag_name().namespace() == Some(XSD))
+}
+
+fn xsd_child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.has_tag_name((XSD, name)))
+}
+
+fn qname(namespace: &str, name: &str) -> QName {
+    QName {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+    }
+}
+
+fn to_position(position: TextPos) -> Position {
+    Position {
+        line: position.row as usize,
+        column: position.col as usize,
+    }
+}
+
+fn words(xml_name: &str) -> Vec<&str> {
+    xml_name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Package name for a namespace, like JAXB derives it: `http://www.eika.no/cars/v1` -> `no.eika.cars.v1`
+pub fn package_name(namespace: &str) -> String {
+    if namespace.is_empty() {
+        return "generated".to_string();
+    }
+
+    let (scheme, rest) = match namespace.split_once(':') {
+        Some((scheme, rest)) if !scheme.contains('/') => (scheme, rest),
+        _ => ("", namespace),
+    };
+    let mut parts: Vec<String> = if scheme == "urn" {
+        rest.split([':', '/'])
+            .map(|part| part.replace('-', "_"))
+            .collect()
+    } else {
+        let mut path = rest.trim_start_matches('/').split('/');
+        let host = path.next().unwrap_or_default();
+        let mut host_parts: Vec<String> = host
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .rev()
+            .map(|part| part.to_string())
+            .collect();
+        if host_parts.last().map(|part| part.as_str()) == Some("www") {
+            host_parts.pop();
+        }
+        host_parts.extend(path.map(|part| part.to_string()));
+        if let Some(last) = host_parts.last_mut() {
+            for extension in [".xsd", ".wsdl", ".html"] {
+                if let Some(stripped) = last.strip_suffix(extension) {
+                    *last = stripped.to_string();
+                }
+            }
+        }
+        host_parts
+    };
+
+    parts.retain(|part| !part.is_empty());
+    parts
+        .iter()
+        .map(|part| {
+            let mut part: String = part
+                .to_lowercase()
+                .chars()
+                .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                .collect();
+            if part.starts_with(|c: char| c.is_ascii_digit()) {
+                part.insert(0, '_');
+            }
+            if JAVA_KEYWORDS.contains(&part.as_str()) {
+                part.push('_');
+            }
+            part
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Class name for an XML name, like JAXB derives it: `car-type` -> `CarType`
+pub fn java_class_name(xml_name: &str) -> String {
+    words(xml_name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Property name for an XML name, like JAXB derives it: `ResponseState` -> `responseState`, `URL` -> `url`
+pub fn java_variable_name(xml_name: &str) -> String {
+    let class_name = java_class_name(xml_name);
+    let chars: Vec<char> = class_name.chars().collect();
+    let upper_run = chars.iter().take_while(|c| c.is_uppercase()).count();
+    let lowered = if upper_run > 1 && upper_run < chars.len() && chars[upper_run].is_lowercase() {
+        upper_run - 1
+    } else {
+        upper_run.max(1)
+    };
+
+    let name: String = chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            if i < lowered {
+                c.to_ascii_lowercase()
+            } else {
+                *c
+            }
+        })
+        .collect();
+
+    if name == "class" {
+        "clazz".to_string()
+    } else if JAVA_KEYWORDS.contains(&name.as_str()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Enum constant name for an enumeration value, like JAXB derives it: `mercedes-benz` -> `MERCEDES_BENZ`
+pub fn enum_constant_name(value: &str) -> String {
+    let mut name = String::new();
+    for word in words(value) {
+        let mut previous_lowercase = false;
+        if !name.is_empty() {
+            name.push('_');
+        }
+        for c in word.chars() {
+            if c.is_uppercase() && previous_lowercase {
+                name.push('_');
+            }
+            previous_lowercase = c.is_lowercase() || c.is_ascii_digit();
+            name.extend(c.to_uppercase());
+        }
+    }
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "VALUE_");
+    }
+    name
+}
+
+pub fn is_schema(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "wsdl" || extension == "xsd")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    fn parse_fixtures() -> Vec<Class> {
+        let sources: Vec<(PathBuf, String)> = ["CarsService.wsdl", "common.xsd"]
+            .iter()
+            .map(|file| {
+                let path = PathBuf::from("test-resources/src/main/resources/wsdl").join(file);
+                let content = fs::read_to_string(&path).unwrap();
+                (path, content)
+            })
+            .collect();
+
+        parse_schemas(&sources)
+            .into_iter()
+            .map(|class| class.unwrap())
+            .collect()
+    }
+
+    fn find<'c>(classes: &'c [Class], name: &str) -> &'c Class {
+        classes.iter().find(|class| class.name == name).unwrap()
+    }
+
+    fn field(
+        name: &str,
+        xml_name: &str,
+        package: &str,
+        r#type: (Option<&str>, &str, bool),
+        generic_type: Option<&str>,
+        builtin: bool,
+        nullable: bool,
+    ) -> Field {
+        let r#type = Type {
+            package: r#type.0.map(|package| package.to_string()),
+            class: r#type.1.to_string(),
+            stdlib: r#type.2,
+            arguments: vec![],
+        };
+        Field {
+            name: name.to_string(),
+            xml_name: Some(xml_name.to_string()),
+            package: package.to_string(),
+            type_arguments: generic_type.iter().map(|_| r#type.clone()).collect(),
+            r#type,
+            generic_type: generic_type.map(|generic_type| generic_type.to_string()),
+            builtin,
+            nullable,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(
+            package_name("http://www.eika.no/cars/v1"),
+            "no.eika.cars.v1"
+        );
+        assert_eq!(package_name("urn:eika:cars-v2"), "eika.cars_v2");
+        assert_eq!(
+            package_name("http://eika.no/2021/types.xsd"),
+            "no.eika._2021.types"
+        );
+        assert_eq!(package_name(""), "generated");
+        assert_eq!(java_class_name("car-type"), "CarType");
+        assert_eq!(java_class_name("baseResponseType"), "BaseResponseType");
+        assert_eq!(java_variable_name("ResponseState"), "responseState");
+        assert_eq!(java_variable_name("SCAused"), "scAused");
+        assert_eq!(java_variable_name("URL"), "url");
+        assert_eq!(java_variable_name("return"), "_return");
+        assert_eq!(enum_constant_name("mercedes-benz"), "MERCEDES_BENZ");
+        assert_eq!(enum_constant_name("VW"), "VW");
+        assert_eq!(enum_constant_name("valueOne"), "VALUE_ONE");
+        assert_eq!(enum_constant_name("1"), "VALUE_1");
+    }
+
+    #[test]
+    fn test_parse_schemas() {
+        let classes = parse_fixtures();
+        let cars = "no.eika.cars.v1";
+        let common = "no.eika.common";
+
+        assert_eq!(
+            find(&classes, "VehicleType"),
+            &Class {
+                package: cars.to_string(),
+                name: "VehicleType".to_string(),
+                fields: vec![Field {
+                    doc: vec![
+                        "The number of doors,".to_string(),
+                        "not counting the trunk".to_string(),
+                    ],
+                    ..field(
+                        "doors",
+                        "Doors",
+                        cars,
+                        (None, "int", true),
+                        None,
+                        true,
+                        false,
+                    )
+                }],
+                is_abstract: true,
+                subclasses: vec![Subclass {
+                    name: "Car".to_string(),
+                    package: None,
+                }],
+                doc: [
+                    "A vehicle of any kind",
+                    "",
+                    "The following schema fragment specifies the expected content contained within this class.",
+                    "",
+                    "```xml",
+                    "<xs:complexType name=\"VehicleType\" abstract=\"true\">",
+                    "    <xs:sequence>",
+                    "        <xs:element name=\"Doors\" type=\"xs:int\">",
+                    "        </xs:element>",
+                    "    </xs:sequence>",
+                    "</xs:complexType>",
+                    "```",
+                ]
+                .iter()
+                .map(|line| line.to_string())
+                .collect(),
+                ..Default::default()
+            }
+        );
+
+        let car = find(&classes, "Car");
+        assert_eq!(car.doc[2], "```xml");
+        assert_eq!(car.doc[3], "<xs:complexType name=\"Car\">");
+        let extras_doc = &car.inner_classes[0].doc;
+        assert_eq!(extras_doc[3], "<xs:complexType>");
+        assert_eq!(
+            &Class {
+                doc: vec![],
+                ..car.clone()
+            },
+            &Class {
+                package: cars.to_string(),
+                name: "Car".to_string(),
+                fields: vec![
+                    Field {
+                        facets: Facets {
+                            max_length: Some(7),
+                            pattern: Some("[A-Z]{2}\\d{4,5}".to_string()),
+                            ..Default::default()
+                        },
+                        ..field(
+                            "registrationNumber",
+                            "RegistrationNumber",
+                            cars,
+                            (None, "String", true),
+                            None,
+                            true,
+                            false,
+                        )
+                    },
+                    Field {
+                        facets: Facets {
+                            total_digits: Some(9),
+                            ..Default::default()
+                        },
+                        ..field(
+                            "price",
+                            "Price",
+                            cars,
+                            (Some("java.math"), "BigDecimal", true),
+                            None,
+                            false,
+                            true,
+                        )
+                    },
+                    field(
+                        "nickname",
+                        "Nickname",
+                        cars,
+                        (None, "String", true),
+                        Some("JAXBElement"),
+                        true,
+                        true
+                    ),
+                    field(
+                        "owner",
+                        "Owner",
+                        cars,
+                        (Some(common), "PersonType", false),
+                        None,
+                        false,
+                        true
+                    ),
+                    field(
+                        "extras",
+                        "Extras",
+                        cars,
+                        (Some(cars), "Car.Extras", false),
+                        None,
+                        false,
+                        true
+                    ),
+                ],
+                superclass: Some(Superclass {
+                    name: "VehicleType".to_string(),
+                    package: cars.to_string(),
+                    fields: find(&classes, "VehicleType").fields.clone(),
+                    is_abstract: true,
+                    ..Default::default()
+                }),
+                inner_classes: vec![InnerClass {
+                    name: "Extras".to_string(),
+                    fields: vec![field(
+                        "towbar",
+                        "Towbar",
+                        cars,
+                        (None, "boolean", true),
+                        None,
+                        true,
+                        false
+                    )],
+                    doc: extras_doc.clone(),
+                }],
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            find(&classes, "PersonType").fields,
+            vec![
+                field(
+                    "name",
+                    "Name",
+                    common,
+                    (None, "String", true),
+                    None,
+                    true,
+                    false
+                ),
+                field(
+                    "age",
+                    "Age",
+                    common,
+                    (None, "Integer", true),
+                    None,
+                    true,
+                    true
+                ),
+                field(
+                    "nicknames",
+                    "Nicknames",
+                    common,
+                    (None, "String", true),
+                    Some("List"),
+                    true,
+                    true
+                ),
+                Field {
+                    binding: Binding::Attribute,
+                    ..field("id", "id", common, (None, "long", true), None, true, false)
+                },
+            ]
+        );
+
+        assert_eq!(
+            find(&classes, "ExtensionType").fields,
+            vec![
+                Field {
+                    name: "any".to_string(),
+                    package: common.to_string(),
+                    r#type: Type {
+                        package: None,
+                        class: "Object".to_string(),
+                        stdlib: true,
+                        arguments: vec![],
+                    },
+                    generic_type: Some("List".to_string()),
+                    type_arguments: vec![Type {
+                        package: None,
+                        class: "Object".to_string(),
+                        stdlib: true,
+                        arguments: vec![],
+                    }],
+                    builtin: true,
+                    nullable: true,
+                    binding: Binding::Any,
+                    ..Default::default()
+                },
+                Field {
+                    name: "otherAttributes".to_string(),
+                    package: common.to_string(),
+                    r#type: Type {
+                        package: Some("javax.xml.namespace".to_string()),
+                        class: "QName".to_string(),
+                        stdlib: true,
+                        arguments: vec![],
+                    },
+                    generic_type: Some("Map".to_string()),
+                    type_arguments: vec![
+                        qname_type(),
+                        Type {
+                            package: None,
+                            class: "String".to_string(),
+                            stdlib: true,
+                            arguments: vec![],
+                        },
+                    ],
+                    nullable: true,
+                    binding: Binding::Any,
+                    ..Default::default()
+                },
+            ]
+        );
+
+        let constants: Vec<(&str, &str, Vec<String>)> = find(&classes, "CarBrand")
+            .enum_constants
+            .iter()
+            .map(|constant| {
+                (
+                    constant.name.as_str(),
+                    constant.xml_value.as_str(),
+                    constant.doc.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            constants,
+            vec![
+                ("VW", "VW", vec![]),
+                ("BMW", "BMW", vec![]),
+                (
+                    "MERCEDES_BENZ",
+                    "mercedes-benz",
+                    vec!["Mercedes-Benz, written like in the registry".to_string()]
+                )
+            ]
+        );
+
+        assert_eq!(
+            find(&classes, "CarsService").functions,
+            vec![Function {
+                name: "getCars".to_string(),
+                arguments: vec![
+                    Argument {
+                        name: "getCars".to_string(),
+                        r#type: "GetCars".to_string(),
+                        nullable: false,
+                        part_name: Some("GetCars".to_string()),
+                        xml_name: Some("GetCars".to_string()),
+                        target_namespace: Some("http://www.eika.no/cars/v1".to_string()),
+                        ..Default::default()
+                    },
+                    Argument {
+                        name: "soapHeader".to_string(),
+                        r#type: "no.eika.common.AuthHeader".to_string(),
+                        nullable: true,
+                        part_name: Some("SoapHeader".to_string()),
+                        xml_name: Some("AuthHeader".to_string()),
+                        target_namespace: Some("http://www.eika.no/common".to_string()),
+                        header: true,
+                        ..Default::default()
+                    },
+                ],
+                return_type: Some(ReturnType {
+                    r#type: Type {
+                        package: Some("no.eika.cars.v1".to_string()),
+                        class: "GetCarsResponse".to_string(),
+                        stdlib: false,
+                        arguments: vec![],
+                    },
+                    generic_type: None,
+                    builtin: false,
+                    nullable: true,
+                }),
+                faults: vec![],
+                fault_classes: vec![],
+                doc: vec!["Finds the cars of a brand".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unknown_type() {
+        let source = (
+            PathBuf::from("broken.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" targetNamespace="urn:broken">
+    <xs:complexType name="Broken">
+        <xs:sequence>
+            <xs:element name="Missing" type="xs:string"/>
+            <xs:element name="Other" type="Unknown"/>
+        </xs:sequence>
+    </xs:complexType>
+</xs:schema>"#
+                .to_string(),
+        );
+
+        let error = parse_schemas(&[source]).remove(0).unwrap_err();
+
+        assert_eq!(error.path, Some(PathBuf::from("broken.xsd")));
+        assert_eq!(
+            error.position,
+            Some(Position {
+                line: 5,
+                column: 13
+            })
+        );
+        assert_eq!(error.message, "unknown type 'Unknown'");
+    }
+}
@@ -0,0 +1,54 @@
+package dataclasses;
+
+import javax.xml.bind.annotation.XmlEnum;
+import javax.xml.bind.annotation.XmlEnumValue;
+import javax.xml.bind.annotation.XmlType;
+
+
+/**
+ * <p>Java class for CarBrand.
+ * 
+ * <p>The following schema fragment specifies the expected content contained within this class.
+ * <pre>
+ * &lt;simpleType name="CarBrand"&gt;
+ *   &lt;restriction base="{http://www.w3.org/2001/XMLSchema}string"&gt;
+ *     &lt;enumeration value="VW"/&gt;
+ *     &lt;enumeration value="BMW"/&gt;
+ *     &lt;enumeration value="mercedes-benz"/&gt;
+ *   &lt;/restriction&gt;
+ * &lt;/simpleType&gt;
+ * </pre>
+ * 
+ */
+@XmlType(name = "CarBrand")
+@XmlEnum
+public enum CarBrand {
+
+    VW("VW"),
+    BMW("BMW"),
+
+    /**
+     * Mercedes-Benz, written like in the registry
+     * 
+     */
+    @XmlEnumValue("mercedes-benz")
+    MERCEDES_BENZ("mercedes-benz");
+    private final String value;
+
+    CarBrand(String v) {
+        value = v;
+    }
+
+    public String value() {
+        return value;
+    }
+
+    public static CarBrand fromValue(String v) {
+        for (CarBrand c: CarBrand.values()) {
+            if (c.value.equals(v)) {
+                return c;
+            }
+        }
+        throw new IllegalArgumentException(v);
+    }
+}
@@ -0,0 +1,39 @@
+package dataclasses
+
+
+/**
+ * The following schema fragment specifies the expected content contained within this class.
+ * ```xml
+ * <simpleType name="CarBrand">
+ *   <restriction base="{http://www.w3.org/2001/XMLSchema}string">
+ *     <enumeration value="VW"/>
+ *     <enumeration value="BMW"/>
+ *     <enumeration value="mercedes-benz"/>
+ *   </restriction>
+ * </simpleType>
+ * ```
+ *
+ * This file is GENERATED. Please don't change
+ */
+enum class CarBrandKt(val value: CarBrand, val xmlValue: String) {
+    VW(CarBrand.VW, "VW"),
+    BMW(CarBrand.BMW, "BMW"),
+    /**
+     * Mercedes-Benz, written like in the registry
+     *
+     * The XML value `mercedes-benz`
+     */
+    MERCEDES_BENZ(CarBrand.MERCEDES_BENZ, "mercedes-benz"),
+    ;
+
+	fun toJava(): CarBrand = value
+
+    companion object {
+        fun fromJava(value: CarBrand): CarBrandKt =
+            fromJavaOrNull(value) ?: throw IllegalArgumentException("No CarBrandKt for $value")
+
+        fun fromJavaOrNull(value: CarBrand?): CarBrandKt? = values().find { it.value == value }
+
+        fun fromXmlValue(value: String): CarBrandKt? = values().find { it.xmlValue == value }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+package dataclasses
+
+/**
+ * Marks the builder DSLs of the data classes
+ *
+ * This file is GENERATED. Please don't change
+ */
+@DslMarker
+annotation class CarsDsl
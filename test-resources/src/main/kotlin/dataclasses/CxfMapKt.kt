@@ -1,45 +1,25 @@
 package dataclasses
 
+import dataclasses.DoorKt
 
 /**
  * This file is GENERATED. Please don't change
  */
 @Suppress("unused", "useless_cast")
 data class CxfMapKt(
-    val entry: List<EntryKt> = emptyList(),
+    val entry: Map<String, DoorKt?> = emptyMap(),
 ) {
 
     fun toJava(): CxfMap = CxfMap().also {
-        it.entry = entry.map { elem -> elem.toJava() }
+        it.entry = entry.map { (key, value) -> CxfMap.Entry().also { it.key = key; it.value = value?.toJava() } }
     }
 
     companion object {
         internal val factory = ObjectFactory()
 
         fun fromJava(javaClass: CxfMap): CxfMapKt = CxfMapKt(
-            entry = javaClass.entry?.map { EntryKt.fromJava(it) as EntryKt } ?: emptyList(),
+            entry = javaClass.entry?.let { entries -> entries.associate { entry -> requireNotNull(entry.key) { "entry has an entry without a key" } to entry.value?.let { DoorKt.fromJava(it) as DoorKt } }.also { require(it.size == entries.size) { "entry has entries with the same key" } } } ?: emptyMap(),
         )
 
     }
-
-    data class EntryKt(
-        val key: String? = null,
-        val value: DoorKt? = null,
-    ) {
-
-        fun toJava(): CxfMap.Entry = CxfMap.Entry().also {
-            it.key = key
-            it.value = value?.toJava()
-        }
-
-        companion object {
-            internal val factory = ObjectFactory()
-
-            fun fromJava(javaClass: CxfMap.Entry): EntryKt = EntryKt(
-                key = javaClass.key,
-                value = javaClass.value?.let { DoorKt.fromJava(it) as DoorKt },
-            )
-
-        }
-    }
 }
\ No newline at end of file
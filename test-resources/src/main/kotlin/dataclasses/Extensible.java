@@ -0,0 +1,58 @@
+package dataclasses;
+
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import javax.xml.bind.annotation.XmlAccessType;
+import javax.xml.bind.annotation.XmlAccessorType;
+import javax.xml.bind.annotation.XmlAnyAttribute;
+import javax.xml.bind.annotation.XmlAnyElement;
+import javax.xml.bind.annotation.XmlElement;
+import javax.xml.bind.annotation.XmlType;
+import javax.xml.namespace.QName;
+import org.w3c.dom.Element;
+
+@XmlAccessorType(XmlAccessType.FIELD)
+@XmlType(name = "Extensible", propOrder = {
+    "name",
+    "any",
+    "extensions"
+})
+public class Extensible {
+
+    @XmlElement(required = true)
+    protected String name;
+    @XmlAnyElement(lax = true)
+    protected List<Object> any;
+    @XmlAnyElement
+    protected List<Element> extensions;
+    @XmlAnyAttribute
+    private Map<QName, String> otherAttributes = new HashMap<QName, String>();
+
+    public String getName() {
+        return name;
+    }
+
+    public void setName(String value) {
+        this.name = value;
+    }
+
+    public List<Object> getAny() {
+        if (any == null) {
+            any = new ArrayList<Object>();
+        }
+        return this.any;
+    }
+
+    public List<Element> getExtensions() {
+        if (extensions == null) {
+            extensions = new ArrayList<Element>();
+        }
+        return this.extensions;
+    }
+
+    public Map<QName, String> getOtherAttributes() {
+        return otherAttributes;
+    }
+}
@@ -0,0 +1,38 @@
+package dataclasses
+
+import javax.xml.namespace.QName
+import org.w3c.dom.Element
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class ExtensibleKt(
+    val name: String,
+    /** Any other XML elements */
+    val any: List<Any> = emptyList(),
+    /** Any other XML elements */
+    val extensions: List<Element> = emptyList(),
+    /** Any other XML attributes */
+    val otherAttributes: Map<QName, String> = emptyMap(),
+) {
+
+    fun toJava(): Extensible = Extensible().also {
+        it.name = name
+        it.any.addAll(any)
+        it.extensions.addAll(extensions)
+        it.otherAttributes.putAll(otherAttributes)
+    }
+
+    companion object {
+        internal val factory = ObjectFactory()
+
+        fun fromJava(javaClass: Extensible): ExtensibleKt = ExtensibleKt(
+            name = javaClass.name,
+            any = javaClass.any,
+            extensions = javaClass.extensions,
+            otherAttributes = javaClass.otherAttributes,
+        )
+
+    }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+package dataclasses;
+
+import java.util.ArrayList;
+import java.util.List;
+import javax.xml.bind.JAXBElement;
+import javax.xml.bind.annotation.XmlAccessType;
+import javax.xml.bind.annotation.XmlAccessorType;
+import javax.xml.bind.annotation.XmlElement;
+import javax.xml.bind.annotation.XmlElementRef;
+import javax.xml.bind.annotation.XmlElementRefs;
+import javax.xml.bind.annotation.XmlElements;
+import javax.xml.bind.annotation.XmlType;
+
+@XmlAccessorType(XmlAccessType.FIELD)
+@XmlType(name = "Garage", propOrder = {
+    "carOrRegistrationNumber",
+    "doorOrOwner"
+})
+public class Garage {
+
+    @XmlElements({
+        @XmlElement(name = "car", type = Car.class),
+        @XmlElement(name = "registrationNumber", type = String.class)
+    })
+    protected List<Object> carOrRegistrationNumber;
+    @XmlElementRefs({
+        @XmlElementRef(name = "door", namespace = "http://www.eika.no/cars", type = JAXBElement.class, required = false),
+        @XmlElementRef(name = "owner", namespace = "http://www.eika.no/cars", type = JAXBElement.class, required = false)
+    })
+    protected JAXBElement<?> doorOrOwner;
+
+    public List<Object> getCarOrRegistrationNumber() {
+        if (carOrRegistrationNumber == null) {
+            carOrRegistrationNumber = new ArrayList<Object>();
+        }
+        return this.carOrRegistrationNumber;
+    }
+
+    public JAXBElement<?> getDoorOrOwner() {
+        return doorOrOwner;
+    }
+
+    public void setDoorOrOwner(JAXBElement<?> value) {
+        this.doorOrOwner = value;
+    }
+}
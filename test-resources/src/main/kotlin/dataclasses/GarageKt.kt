@@ -0,0 +1,69 @@
+package dataclasses
+
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class GarageKt(
+    val carOrRegistrationNumber: List<GarageCarOrRegistrationNumberKt> = emptyList(),
+    val doorOrOwner: GarageDoorOrOwnerKt? = null,
+) {
+
+    fun toJava(): Garage = Garage().also {
+        it.carOrRegistrationNumber = carOrRegistrationNumber.map { elem -> elem.toJava() }
+        it.doorOrOwner = doorOrOwner?.toJava()
+    }
+
+    companion object {
+        internal val factory = ObjectFactory()
+
+        fun fromJava(javaClass: Garage): GarageKt = GarageKt(
+            carOrRegistrationNumber = javaClass.carOrRegistrationNumber?.map { GarageCarOrRegistrationNumberKt.fromJava(it) as GarageCarOrRegistrationNumberKt } ?: emptyList(),
+            doorOrOwner = javaClass.doorOrOwner?.let { GarageDoorOrOwnerKt.fromJava(it) as GarageDoorOrOwnerKt },
+        )
+
+    }
+}
+
+sealed interface GarageCarOrRegistrationNumberKt {
+
+    fun toJava(): Any
+
+    data class Car(val value: dataclasses.CarKt) : GarageCarOrRegistrationNumberKt {
+        override fun toJava(): Any = value.toJava()
+    }
+
+    data class RegistrationNumber(val value: String) : GarageCarOrRegistrationNumberKt {
+        override fun toJava(): Any = value
+    }
+
+    companion object {
+        fun fromJava(value: Any): GarageCarOrRegistrationNumberKt = when {
+            value is dataclasses.Car -> Car(dataclasses.CarKt.fromJava(value))
+            value is String -> RegistrationNumber(value)
+            else -> throw IllegalArgumentException("Not able to find the element of GarageCarOrRegistrationNumberKt for '${value.javaClass.name}'")
+        }
+    }
+}
+
+sealed interface GarageDoorOrOwnerKt {
+
+    fun toJava(): javax.xml.bind.JAXBElement<*>
+
+    data class Door(val value: dataclasses.DoorKt) : GarageDoorOrOwnerKt {
+        override fun toJava(): javax.xml.bind.JAXBElement<*> = GarageKt.factory.createGarageDoor(value.toJava())
+    }
+
+    data class Owner(val value: String) : GarageDoorOrOwnerKt {
+        override fun toJava(): javax.xml.bind.JAXBElement<*> = GarageKt.factory.createGarageOwner(value)
+    }
+
+    companion object {
+        fun fromJava(value: javax.xml.bind.JAXBElement<*>): GarageDoorOrOwnerKt = when {
+            value.name.localPart == "door" -> Door(dataclasses.DoorKt.fromJava(value.value as dataclasses.Door))
+            value.name.localPart == "owner" -> Owner(value.value as String)
+            else -> throw IllegalArgumentException("Not able to find the element of GarageDoorOrOwnerKt for '${value.name}'")
+        }
+    }
+}
\ No newline at end of file
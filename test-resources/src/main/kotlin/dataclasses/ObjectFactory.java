@@ -1,15 +1,51 @@
 package dataclasses;
 
 import javax.xml.bind.JAXBElement;
+import javax.xml.bind.annotation.XmlElementDecl;
+import javax.xml.bind.annotation.XmlRegistry;
 import javax.xml.namespace.QName;
 
+@XmlRegistry
 public class ObjectFactory {
 
+    private final static QName _Car_QNAME = new QName("http://www.eika.no/cars", "Car");
+    private final static QName _CarStringJAXBElement_QNAME = new QName("http://www.eika.no/cars", "stringJAXBElement");
+    private final static QName _CarInternalClassJAXBElement_QNAME = new QName("http://www.eika.no/cars", "internalClassJAXBElement");
+    private final static QName _GarageDoor_QNAME = new QName("http://www.eika.no/cars", "door");
+    private final static QName _GarageOwner_QNAME = new QName("http://www.eika.no/cars", "owner");
+    private final static QName _ParkingSpareDoor_QNAME = new QName("http://www.eika.no/cars", "spareDoor");
+
+    public Car createCar() {
+        return new Car();
+    }
+
+    @XmlElementDecl(namespace = "http://www.eika.no/cars", name = "Car")
+    public JAXBElement<Car> createCar(Car value) {
+        return new JAXBElement<Car>(_Car_QNAME, Car.class, null, value);
+    }
+
+    @XmlElementDecl(namespace = "http://www.eika.no/cars", name = "stringJAXBElement", scope = Car.class)
     public JAXBElement<String> createStringJAXBElement(String value) {
-        return new JAXBElement<String>(new QName(""), String.class, Integer.class, value);
+        return new JAXBElement<String>(_CarStringJAXBElement_QNAME, String.class, Car.class, value);
     }
 
+    @XmlElementDecl(namespace = "http://www.eika.no/cars", name = "internalClassJAXBElement", scope = Car.class)
     public JAXBElement<Door> createInternalClassJAXBElement(Door value) {
-        return new JAXBElement<Door>(new QName(""), Door.class, Integer.class, value);
+        return new JAXBElement<Door>(_CarInternalClassJAXBElement_QNAME, Door.class, Car.class, value);
+    }
+
+    @XmlElementDecl(namespace = "http://www.eika.no/cars", name = "door", scope = Garage.class)
+    public JAXBElement<Door> createGarageDoor(Door value) {
+        return new JAXBElement<Door>(_GarageDoor_QNAME, Door.class, Garage.class, value);
+    }
+
+    @XmlElementDecl(namespace = "http://www.eika.no/cars", name = "owner", scope = Garage.class)
+    public JAXBElement<String> createGarageOwner(String value) {
+        return new JAXBElement<String>(_GarageOwner_QNAME, String.class, Garage.class, value);
+    }
+
+    @XmlElementDecl(namespace = "http://www.eika.no/cars", name = "spareDoor", scope = Parking.class)
+    public JAXBElement<Door> createParkingSpareDoor(Door value) {
+        return new JAXBElement<Door>(_ParkingSpareDoor_QNAME, Door.class, Parking.class, value);
     }
 }
@@ -0,0 +1,15 @@
+package dataclasses
+
+import javax.xml.bind.JAXBElement
+import javax.xml.namespace.QName
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+object ObjectFactoryKt {
+    private val factory = ObjectFactory()
+
+    val carQName = QName("http://www.eika.no/cars", "Car")
+
+    fun createCar(value: CarKt): JAXBElement<Car> = factory.createCar(value.toJava())
+}
@@ -0,0 +1,55 @@
+package dataclasses;
+
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import javax.xml.bind.JAXBElement;
+import javax.xml.bind.annotation.XmlAccessType;
+import javax.xml.bind.annotation.XmlAccessorType;
+import javax.xml.bind.annotation.XmlElementRef;
+import javax.xml.bind.annotation.XmlType;
+
+@XmlAccessorType(XmlAccessType.FIELD)
+@XmlType(name = "Parking", propOrder = {
+    "spareDoors",
+    "doorsByLevel",
+    "levelsByDoor",
+    "rows"
+})
+public class Parking {
+
+    @XmlElementRef(name = "spareDoor", namespace = "http://www.eika.no/cars", type = JAXBElement.class, required = false)
+    protected List<JAXBElement<Door>> spareDoors;
+    protected Map<String, List<Door>> doorsByLevel;
+    protected Map<Door, String> levelsByDoor;
+    protected List<List<String>> rows;
+
+    public List<JAXBElement<Door>> getSpareDoors() {
+        if (spareDoors == null) {
+            spareDoors = new ArrayList<JAXBElement<Door>>();
+        }
+        return this.spareDoors;
+    }
+
+    public Map<String, List<Door>> getDoorsByLevel() {
+        if (doorsByLevel == null) {
+            doorsByLevel = new HashMap<String, List<Door>>();
+        }
+        return this.doorsByLevel;
+    }
+
+    public Map<Door, String> getLevelsByDoor() {
+        if (levelsByDoor == null) {
+            levelsByDoor = new HashMap<Door, String>();
+        }
+        return this.levelsByDoor;
+    }
+
+    public List<List<String>> getRows() {
+        if (rows == null) {
+            rows = new ArrayList<List<String>>();
+        }
+        return this.rows;
+    }
+}
@@ -0,0 +1,33 @@
+package dataclasses
+
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class ParkingKt(
+    val spareDoors: List<DoorKt?> = emptyList(),
+    val doorsByLevel: Map<String, List<DoorKt>> = emptyMap(),
+    val levelsByDoor: Map<DoorKt, String> = emptyMap(),
+    val rows: List<List<String>> = emptyList(),
+) {
+
+    fun toJava(): Parking = Parking().also {
+        it.spareDoors = spareDoors.map { elem -> elem?.let { elem1 -> ParkingKt.factory.createParkingSpareDoor(elem1.toJava()) } }
+        it.doorsByLevel = doorsByLevel.mapValues { (_, value) -> value.map { elem1 -> elem1.toJava() } }
+        it.levelsByDoor = levelsByDoor.entries.associate { (key, value) -> key.toJava() to value }.also { require(it.size == levelsByDoor.size) { "Two keys of the map convert to the same key" } }
+        it.rows = rows
+    }
+
+    companion object {
+        internal val factory = ObjectFactory()
+
+        fun fromJava(javaClass: Parking): ParkingKt = ParkingKt(
+            spareDoors = javaClass.spareDoors?.map { elem -> elem?.value?.let { elem1 -> DoorKt.fromJava(elem1) as DoorKt } } ?: emptyList(),
+            doorsByLevel = javaClass.doorsByLevel?.mapValues { (_, value) -> value?.map { elem1 -> DoorKt.fromJava(elem1) as DoorKt } ?: emptyList() } ?: emptyMap(),
+            levelsByDoor = javaClass.levelsByDoor?.let { map -> map.entries.associate { (key, value) -> DoorKt.fromJava(key) as DoorKt to value }.also { require(it.size == map.size) { "Two keys of the map convert to the same key" } } } ?: emptyMap(),
+            rows = javaClass.rows ?: emptyList(),
+        )
+
+    }
+}
\ No newline at end of file
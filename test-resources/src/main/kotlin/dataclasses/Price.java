@@ -0,0 +1,45 @@
+package dataclasses;
+
+import javax.xml.bind.annotation.XmlAccessType;
+import javax.xml.bind.annotation.XmlAccessorType;
+import javax.xml.bind.annotation.XmlAttribute;
+import javax.xml.bind.annotation.XmlType;
+import javax.xml.bind.annotation.XmlValue;
+
+@XmlAccessorType(XmlAccessType.FIELD)
+@XmlType(name = "Price", propOrder = {
+    "value"
+})
+public class Price {
+
+    @XmlValue
+    protected String value;
+    @XmlAttribute(name = "currency", required = true)
+    protected String currency;
+    @XmlAttribute(name = "vat")
+    protected Boolean vatIncluded;
+
+    public String getValue() {
+        return value;
+    }
+
+    public void setValue(String value) {
+        this.value = value;
+    }
+
+    public String getCurrency() {
+        return currency;
+    }
+
+    public void setCurrency(String value) {
+        this.currency = value;
+    }
+
+    public Boolean isVatIncluded() {
+        return vatIncluded;
+    }
+
+    public void setVatIncluded(Boolean value) {
+        this.vatIncluded = value;
+    }
+}
@@ -0,0 +1,33 @@
+package dataclasses
+
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class PriceKt(
+    /** The text content of the XML element */
+    val value: String? = null,
+    /** The XML attribute `currency` */
+    val currency: String,
+    /** The XML attribute `vat` */
+    val vatIncluded: Boolean? = null,
+) {
+
+    fun toJava(): Price = Price().also {
+        it.value = value
+        it.currency = currency
+        it.vatIncluded = vatIncluded
+    }
+
+    companion object {
+        internal val factory = ObjectFactory()
+
+        fun fromJava(javaClass: Price): PriceKt = PriceKt(
+            value = javaClass.value,
+            currency = javaClass.currency,
+            vatIncluded = javaClass.vatIncluded,
+        )
+
+    }
+}
\ No newline at end of file
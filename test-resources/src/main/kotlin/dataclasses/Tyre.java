@@ -0,0 +1,174 @@
+package dataclasses;
+
+import java.math.BigDecimal;
+import java.util.ArrayList;
+import java.util.List;
+import javax.xml.bind.annotation.XmlAccessType;
+import javax.xml.bind.annotation.XmlAccessorType;
+import javax.xml.bind.annotation.XmlAttribute;
+import javax.xml.bind.annotation.XmlElement;
+import javax.xml.bind.annotation.XmlType;
+
+
+/**
+ * <p>Java class for Tyre complex type.
+ *
+ * <p>The following schema fragment specifies the expected content contained within this class.
+ *
+ * <pre>
+ * &lt;complexType name="Tyre"&gt;
+ *   &lt;complexContent&gt;
+ *     &lt;restriction base="{http://www.w3.org/2001/XMLSchema}anyType"&gt;
+ *       &lt;sequence&gt;
+ *         &lt;element name="Brand"&gt;
+ *           &lt;simpleType&gt;
+ *             &lt;restriction base="{http://www.w3.org/2001/XMLSchema}string"&gt;
+ *               &lt;minLength value="1"/&gt;
+ *               &lt;maxLength value="40"/&gt;
+ *             &lt;/restriction&gt;
+ *           &lt;/simpleType&gt;
+ *         &lt;/element&gt;
+ *         &lt;element name="Size"&gt;
+ *           &lt;simpleType&gt;
+ *             &lt;restriction base="{http://www.w3.org/2001/XMLSchema}string"&gt;
+ *               &lt;pattern value="\d{3}/\d{2}R\d{2}"/&gt;
+ *             &lt;/restriction&gt;
+ *           &lt;/simpleType&gt;
+ *         &lt;/element&gt;
+ *         &lt;element name="LoadIndex" minOccurs="0"&gt;
+ *           &lt;simpleType&gt;
+ *             &lt;restriction base="{http://www.w3.org/2001/XMLSchema}decimal"&gt;
+ *               &lt;totalDigits value="3"/&gt;
+ *             &lt;/restriction&gt;
+ *           &lt;/simpleType&gt;
+ *         &lt;/element&gt;
+ *         &lt;element name="Tread" maxOccurs="unbounded" minOccurs="0"&gt;
+ *           &lt;simpleType&gt;
+ *             &lt;restriction base="{http://www.w3.org/2001/XMLSchema}string"&gt;
+ *               &lt;maxLength value="10"/&gt;
+ *             &lt;/restriction&gt;
+ *           &lt;/simpleType&gt;
+ *         &lt;/element&gt;
+ *         &lt;element name="Rating"&gt;
+ *           &lt;complexType&gt;
+ *             &lt;complexContent&gt;
+ *               &lt;restriction base="{http://www.w3.org/2001/XMLSchema}anyType"&gt;
+ *                 &lt;sequence&gt;
+ *                   &lt;element name="Grip"&gt;
+ *                     &lt;simpleType&gt;
+ *                       &lt;restriction base="{http://www.w3.org/2001/XMLSchema}string"&gt;
+ *                         &lt;pattern value="[A-G]"/&gt;
+ *                       &lt;/restriction&gt;
+ *                     &lt;/simpleType&gt;
+ *                   &lt;/element&gt;
+ *                 &lt;/sequence&gt;
+ *               &lt;/restriction&gt;
+ *             &lt;/complexContent&gt;
+ *           &lt;/complexType&gt;
+ *         &lt;/element&gt;
+ *       &lt;/sequence&gt;
+ *       &lt;attribute name="season"&gt;
+ *         &lt;simpleType&gt;
+ *           &lt;restriction base="{http://www.w3.org/2001/XMLSchema}string"&gt;
+ *             &lt;enumeration value="summer"/&gt;
+ *             &lt;enumeration value="winter"/&gt;
+ *           &lt;/restriction&gt;
+ *         &lt;/simpleType&gt;
+ *       &lt;/attribute&gt;
+ *     &lt;/restriction&gt;
+ *   &lt;/complexContent&gt;
+ * &lt;/complexType&gt;
+ * </pre>
+ *
+ *
+ */
+@XmlAccessorType(XmlAccessType.FIELD)
+@XmlType(name = "Tyre", propOrder = {
+    "brand",
+    "size",
+    "loadIndex",
+    "tread",
+    "rating"
+})
+public class Tyre {
+
+    @XmlElement(name = "Brand", required = true)
+    protected String brand;
+    @XmlElement(name = "Size", required = true)
+    protected String size;
+    @XmlElement(name = "LoadIndex")
+    protected BigDecimal loadIndex;
+    @XmlElement(name = "Tread")
+    protected List<String> tread;
+    @XmlElement(name = "Rating", required = true)
+    protected Tyre.Rating rating;
+    @XmlAttribute(name = "season")
+    protected String season;
+
+    public String getBrand() {
+        return brand;
+    }
+
+    public void setBrand(String value) {
+        this.brand = value;
+    }
+
+    public String getSize() {
+        return size;
+    }
+
+    public void setSize(String value) {
+        this.size = value;
+    }
+
+    public BigDecimal getLoadIndex() {
+        return loadIndex;
+    }
+
+    public void setLoadIndex(BigDecimal value) {
+        this.loadIndex = value;
+    }
+
+    public List<String> getTread() {
+        if (tread == null) {
+            tread = new ArrayList<String>();
+        }
+        return this.tread;
+    }
+
+    public Tyre.Rating getRating() {
+        return rating;
+    }
+
+    public void setRating(Tyre.Rating value) {
+        this.rating = value;
+    }
+
+    public String getSeason() {
+        return season;
+    }
+
+    public void setSeason(String value) {
+        this.season = value;
+    }
+
+    @XmlAccessorType(XmlAccessType.FIELD)
+    @XmlType(name = "", propOrder = {
+        "grip"
+    })
+    public static class Rating {
+
+        @XmlElement(name = "Grip", required = true)
+        protected String grip;
+
+        public String getGrip() {
+            return grip;
+        }
+
+        public void setGrip(String value) {
+            this.grip = value;
+        }
+
+    }
+
+}
@@ -0,0 +1,141 @@
+package dataclasses
+
+import java.math.BigDecimal
+
+/**
+ * The following schema fragment specifies the expected content contained within this class.
+ *
+ * ```xml
+ * <complexType name="Tyre">
+ *   <complexContent>
+ *     <restriction base="{http://www.w3.org/2001/XMLSchema}anyType">
+ *       <sequence>
+ *         <element name="Brand">
+ *           <simpleType>
+ *             <restriction base="{http://www.w3.org/2001/XMLSchema}string">
+ *               <minLength value="1"/>
+ *               <maxLength value="40"/>
+ *             </restriction>
+ *           </simpleType>
+ *         </element>
+ *         <element name="Size">
+ *           <simpleType>
+ *             <restriction base="{http://www.w3.org/2001/XMLSchema}string">
+ *               <pattern value="\d{3}/\d{2}R\d{2}"/>
+ *             </restriction>
+ *           </simpleType>
+ *         </element>
+ *         <element name="LoadIndex" minOccurs="0">
+ *           <simpleType>
+ *             <restriction base="{http://www.w3.org/2001/XMLSchema}decimal">
+ *               <totalDigits value="3"/>
+ *             </restriction>
+ *           </simpleType>
+ *         </element>
+ *         <element name="Tread" maxOccurs="unbounded" minOccurs="0">
+ *           <simpleType>
+ *             <restriction base="{http://www.w3.org/2001/XMLSchema}string">
+ *               <maxLength value="10"/>
+ *             </restriction>
+ *           </simpleType>
+ *         </element>
+ *         <element name="Rating">
+ *           <complexType>
+ *             <complexContent>
+ *               <restriction base="{http://www.w3.org/2001/XMLSchema}anyType">
+ *                 <sequence>
+ *                   <element name="Grip">
+ *                     <simpleType>
+ *                       <restriction base="{http://www.w3.org/2001/XMLSchema}string">
+ *                         <pattern value="[A-G]"/>
+ *                       </restriction>
+ *                     </simpleType>
+ *                   </element>
+ *                 </sequence>
+ *               </restriction>
+ *             </complexContent>
+ *           </complexType>
+ *         </element>
+ *       </sequence>
+ *       <attribute name="season">
+ *         <simpleType>
+ *           <restriction base="{http://www.w3.org/2001/XMLSchema}string">
+ *             <enumeration value="summer"/>
+ *             <enumeration value="winter"/>
+ *           </restriction>
+ *         </simpleType>
+ *       </attribute>
+ *     </restriction>
+ *   </complexContent>
+ * </complexType>
+ * ```
+ *
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class TyreKt(
+    val brand: String,
+    val size: String,
+    val loadIndex: BigDecimal? = null,
+    val tread: List<String> = emptyList(),
+    val rating: RatingKt,
+    /** The XML attribute `season` */
+    val season: String? = null,
+) {
+
+    init {
+        require(brand.length >= 1) { "brand is shorter than 1 character" }
+        require(brand.length <= 40) { "brand is longer than 40 characters" }
+        require(SIZE_PATTERN.matches(size)) { "size does not match the pattern \\d{3}/\\d{2}R\\d{2}" }
+        require(loadIndex == null || loadIndex.stripTrailingZeros().precision() <= 3) { "loadIndex has more than 3 digits" }
+        require(tread.all { it.length <= 10 }) { "tread is longer than 10 characters" }
+        require(season == null || season in setOf("summer", "winter")) { "season is not one of summer, winter" }
+    }
+
+    fun toJava(): Tyre = Tyre().also {
+        it.brand = brand
+        it.size = size
+        it.loadIndex = loadIndex
+        it.tread = tread
+        it.rating = rating.toJava()
+        it.season = season
+    }
+
+    companion object {
+        internal val factory = ObjectFactory()
+        private val SIZE_PATTERN = Regex("\\p{Nd}{3}/\\p{Nd}{2}R\\p{Nd}{2}")
+
+        fun fromJava(javaClass: Tyre): TyreKt = TyreKt(
+            brand = javaClass.brand,
+            size = javaClass.size,
+            loadIndex = javaClass.loadIndex,
+            tread = javaClass.tread ?: emptyList(),
+            rating = javaClass.rating.let { RatingKt.fromJava(it) as RatingKt },
+            season = javaClass.season,
+        )
+
+    }
+
+    data class RatingKt(
+        val grip: String,
+    ) {
+
+        init {
+            require(GRIP_PATTERN.matches(grip)) { "grip does not match the pattern [A-G]" }
+        }
+
+        fun toJava(): Tyre.Rating = Tyre.Rating().also {
+            it.grip = grip
+        }
+
+        companion object {
+            internal val factory = ObjectFactory()
+            private val GRIP_PATTERN = Regex("[A-G]")
+
+            fun fromJava(javaClass: Tyre.Rating): RatingKt = RatingKt(
+                grip = javaClass.grip,
+            )
+
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+package dataclasses;
+
+import javax.xml.bind.annotation.XmlAccessType;
+import javax.xml.bind.annotation.XmlAccessorType;
+import javax.xml.bind.annotation.XmlAttribute;
+import javax.xml.bind.annotation.XmlElement;
+import javax.xml.bind.annotation.XmlSchemaType;
+import javax.xml.bind.annotation.XmlType;
+import javax.xml.datatype.XMLGregorianCalendar;
+
+
+/**
+ * A wheel of a car, with the tyre on it
+ * 
+ * <p>Java class for Wheel complex type.
+ * 
+ * <p>The following schema fragment specifies the expected content contained within this class.
+ * 
+ * <pre>
+ * &lt;complexType name="Wheel"&gt;
+ *   &lt;complexContent&gt;
+ *     &lt;restriction base="{http://www.w3.org/2001/XMLSchema}anyType"&gt;
+ *       &lt;sequence&gt;
+ *         &lt;element name="Diameter" type="{http://www.w3.org/2001/XMLSchema}int"/&gt;
+ *         &lt;element name="Mounted" type="{http://www.w3.org/2001/XMLSchema}date" minOccurs="0"/&gt;
+ *       &lt;/sequence&gt;
+ *       &lt;attribute name="position" type="{http://www.w3.org/2001/XMLSchema}string" /&gt;
+ *     &lt;/restriction&gt;
+ *   &lt;/complexContent&gt;
+ * &lt;/complexType&gt;
+ * </pre>
+ * 
+ * 
+ */
+@XmlAccessorType(XmlAccessType.FIELD)
+@XmlType(name = "Wheel", propOrder = {
+    "diameter",
+    "mounted"
+})
+public class Wheel {
+
+    /**
+     * The rim diameter in inches, like 17 for a
+     * {@link Car } of &lt;= 2 tonnes
+     * 
+     */
+    @XmlElement(name = "Diameter")
+    protected int diameter;
+    /**
+     * When the tyre was mounted
+     * 
+     */
+    @XmlElement(name = "Mounted")
+    @XmlSchemaType(name = "date")
+    protected XMLGregorianCalendar mounted;
+    /**
+     * Front or rear, left or right
+     * 
+     */
+    @XmlAttribute(name = "position")
+    protected String position;
+
+    /**
+     * The rim diameter in inches, like 17 for a
+     * {@link Car } of &lt;= 2 tonnes
+     * 
+     */
+    public int getDiameter() {
+        return diameter;
+    }
+
+    /**
+     * Sets the value of the diameter property.
+     * 
+     */
+    public void setDiameter(int value) {
+        this.diameter = value;
+    }
+
+    /**
+     * When the tyre was mounted
+     * 
+     * @return
+     *     possible object is
+     *     {@link XMLGregorianCalendar }
+     *     
+     */
+    public XMLGregorianCalendar getMounted() {
+        return mounted;
+    }
+
+    /**
+     * Sets the value of the mounted property.
+     * 
+     * @param value
+     *     allowed object is
+     *     {@link XMLGregorianCalendar }
+     *     
+     */
+    public void setMounted(XMLGregorianCalendar value) {
+        this.mounted = value;
+    }
+
+    /**
+     * Front or rear, left or right
+     * 
+     * @return
+     *     possible object is
+     *     {@link String }
+     *     
+     */
+    public String getPosition() {
+        return position;
+    }
+
+    /**
+     * Sets the value of the position property.
+     * 
+     * @param value
+     *     allowed object is
+     *     {@link String }
+     *     
+     */
+    public void setPosition(String value) {
+        this.position = value;
+    }
+
+}
@@ -0,0 +1,65 @@
+package dataclasses
+
+import javax.xml.datatype.XMLGregorianCalendar
+
+/**
+ * A wheel of a car, with the tyre on it
+ *
+ * The following schema fragment specifies the expected content contained within this class.
+ *
+ * ```xml
+ * <complexType name="Wheel">
+ *   <complexContent>
+ *     <restriction base="{http://www.w3.org/2001/XMLSchema}anyType">
+ *       <sequence>
+ *         <element name="Diameter" type="{http://www.w3.org/2001/XMLSchema}int"/>
+ *         <element name="Mounted" type="{http://www.w3.org/2001/XMLSchema}date" minOccurs="0"/>
+ *       </sequence>
+ *       <attribute name="position" type="{http://www.w3.org/2001/XMLSchema}string" />
+ *     </restriction>
+ *   </complexContent>
+ * </complexType>
+ * ```
+ *
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class WheelKt(
+    /**
+     * The rim diameter in inches, like 17 for a
+     * [Car] of <= 2 tonnes
+     *
+     * The XML element `Diameter`, required
+     */
+    val diameter: Int,
+    /**
+     * When the tyre was mounted
+     *
+     * The XML element `Mounted`, of XSD type `date`
+     */
+    val mounted: XMLGregorianCalendar? = null,
+    /**
+     * Front or rear, left or right
+     *
+     * The XML attribute `position`
+     */
+    val position: String? = null,
+) {
+
+    fun toJava(): Wheel = Wheel().also {
+        it.diameter = diameter
+        it.mounted = mounted
+        it.position = position
+    }
+
+    companion object {
+        internal val factory = ObjectFactory()
+
+        fun fromJava(javaClass: Wheel): WheelKt = WheelKt(
+            diameter = javaClass.diameter,
+            mounted = javaClass.mounted,
+            position = javaClass.position,
+        )
+
+    }
+}
\ No newline at end of file
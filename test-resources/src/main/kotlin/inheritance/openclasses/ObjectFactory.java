@@ -1,12 +1,18 @@
 package inheritance.openclasses;
 
 import javax.xml.bind.JAXBElement;
+import javax.xml.bind.annotation.XmlElementDecl;
+import javax.xml.bind.annotation.XmlRegistry;
 import javax.xml.namespace.QName;
 
+@XmlRegistry
 public class ObjectFactory {
 
+    private final static QName _ALevelAa_QNAME = new QName("", "aa");
+
+    @XmlElementDecl(namespace = "", name = "aa", scope = ALevel.class)
     public JAXBElement<Window> createAa(Window value) {
-        return new JAXBElement<Window>(new QName(""), Window.class, Integer.class, value);
+        return new JAXBElement<Window>(_ALevelAa_QNAME, Window.class, ALevel.class, value);
     }
 
 }
@@ -0,0 +1,41 @@
+package misc.a;
+
+import javax.xml.ws.WebFault;
+
+
+/**
+ * This class was generated by Apache CXF 3.5.5
+ * Generated source version: 3.5.5
+ */
+
+@WebFault(name = "CarLocked", targetNamespace = "http://asdf.com")
+public class CarLockedFault extends Exception {
+
+    private misc.b.CarLocked faultInfo;
+
+    public CarLockedFault() {
+        super();
+    }
+
+    public CarLockedFault(String message) {
+        super(message);
+    }
+
+    public CarLockedFault(String message, java.lang.Throwable cause) {
+        super(message, cause);
+    }
+
+    public CarLockedFault(String message, misc.b.CarLocked carLocked) {
+        super(message);
+        this.faultInfo = carLocked;
+    }
+
+    public CarLockedFault(String message, misc.b.CarLocked carLocked, java.lang.Throwable cause) {
+        super(message, cause);
+        this.faultInfo = carLocked;
+    }
+
+    public misc.b.CarLocked getFaultInfo() {
+        return this.faultInfo;
+    }
+}
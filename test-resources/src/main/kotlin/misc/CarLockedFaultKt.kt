@@ -0,0 +1,19 @@
+package misc.a
+
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+class CarLockedFaultKt(
+    message: String?,
+    val faultInfo: misc.b.CarLockedKt?,
+    cause: Throwable? = null,
+) : Exception(message, cause) {
+    companion object {
+        fun fromJava(javaException: CarLockedFault) = CarLockedFaultKt(
+            javaException.message,
+            javaException.faultInfo?.let { misc.b.CarLockedKt.fromJava(it) },
+            javaException,
+        )
+    }
+}
@@ -0,0 +1,41 @@
+package misc.a;
+
+import javax.xml.ws.WebFault;
+
+
+/**
+ * This class was generated by Apache CXF 3.5.5
+ * Generated source version: 3.5.5
+ */
+
+@WebFault(name = "CarNotFound", targetNamespace = "http://asdf.com")
+public class CarNotFoundFault extends Exception {
+
+    private misc.b.CarNotFound faultInfo;
+
+    public CarNotFoundFault() {
+        super();
+    }
+
+    public CarNotFoundFault(String message) {
+        super(message);
+    }
+
+    public CarNotFoundFault(String message, java.lang.Throwable cause) {
+        super(message, cause);
+    }
+
+    public CarNotFoundFault(String message, misc.b.CarNotFound carNotFound) {
+        super(message);
+        this.faultInfo = carNotFound;
+    }
+
+    public CarNotFoundFault(String message, misc.b.CarNotFound carNotFound, java.lang.Throwable cause) {
+        super(message, cause);
+        this.faultInfo = carNotFound;
+    }
+
+    public misc.b.CarNotFound getFaultInfo() {
+        return this.faultInfo;
+    }
+}
@@ -0,0 +1,19 @@
+package misc.a
+
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+class CarNotFoundFaultKt(
+    message: String?,
+    val faultInfo: misc.b.CarNotFoundKt?,
+    cause: Throwable? = null,
+) : Exception(message, cause) {
+    companion object {
+        fun fromJava(javaException: CarNotFoundFault) = CarNotFoundFaultKt(
+            javaException.message,
+            javaException.faultInfo?.let { misc.b.CarNotFoundKt.fromJava(it) },
+            javaException,
+        )
+    }
+}
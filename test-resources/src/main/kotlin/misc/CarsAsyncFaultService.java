@@ -0,0 +1,26 @@
+package misc.a;
+
+import java.util.concurrent.Future;
+import javax.jws.WebParam;
+import javax.xml.ws.AsyncHandler;
+import javax.xml.ws.Response;
+
+public interface CarsAsyncFaultService {
+
+    public Response<GetCarResponse> getCarAsync(
+        @WebParam(partName = "GetCar", name = "GetCar", targetNamespace = "http://asdf.com")
+        GetCar getCar
+    );
+
+    public Future<?> getCarAsync(
+        @WebParam(partName = "GetCar", name = "GetCar", targetNamespace = "http://asdf.com")
+        GetCar getCar,
+        @WebParam(name = "asyncHandler", targetNamespace = "")
+        AsyncHandler<GetCarResponse> asyncHandler
+    );
+
+    public GetCarResponse getCar(
+        @WebParam(partName = "GetCar", name = "GetCar", targetNamespace = "http://asdf.com")
+        GetCar getCar
+    ) throws CarNotFoundFault;
+}
@@ -0,0 +1,42 @@
+package misc.a
+
+import java.util.concurrent.ExecutionException
+import kotlin.coroutines.resume
+import kotlin.coroutines.resumeWithException
+import kotlinx.coroutines.suspendCancellableCoroutine
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+ class CarsAsyncFaultServiceKt(val portType: CarsAsyncFaultService) {
+
+    private inline fun <T> faults(block: () -> T): T = try {
+        block()
+    } catch (e: Exception) {
+        throw fault(e) ?: e
+    }
+
+    private fun fault(e: Throwable): Exception? = when (e) {
+        is CarNotFoundFault -> CarNotFoundFaultKt.fromJava(e)
+        is CarNotFoundFaultKt -> e
+        else -> null
+    }
+
+    suspend fun getCar(
+		getCar: GetCarKt,
+	): GetCarResponseKt = faults { suspendCancellableCoroutine { continuation ->
+        val future = portType.getCarAsync(
+		getCar.toJava(),
+	) { response ->
+            try {
+                continuation.resume(GetCarResponseKt.fromJava(response.get()))
+            } catch (e: ExecutionException) {
+                continuation.resumeWithException((e.cause ?: e).let { fault(it) ?: it })
+            } catch (e: Throwable) {
+                continuation.resumeWithException(fault(e) ?: e)
+            }
+        }
+        continuation.invokeOnCancellation { future.cancel(true) }
+    } }
+		
+}
@@ -0,0 +1,31 @@
+package misc.a;
+
+import java.util.concurrent.Future;
+import javax.jws.WebParam;
+import javax.xml.ws.AsyncHandler;
+import javax.xml.ws.Response;
+
+public interface CarsAsyncService {
+
+    public Response<GetCarsResponse> getCarsAsync(
+        @WebParam(partName = "GetCars", name = "GetCars", targetNamespace = "http://asdf.com")
+        GetCars getCars
+    );
+
+    public Future<?> getCarsAsync(
+        @WebParam(partName = "GetCars", name = "GetCars", targetNamespace = "http://asdf.com")
+        GetCars getCars,
+        @WebParam(name = "asyncHandler", targetNamespace = "")
+        AsyncHandler<GetCarsResponse> asyncHandler
+    );
+
+    public GetCarsResponse getCars(
+        @WebParam(partName = "GetCars", name = "GetCars", targetNamespace = "http://asdf.com")
+        GetCars getCars
+    );
+
+    public GetTrucksResponse getTrucks(
+        @WebParam(partName = "GetTrucks", name = "GetTrucks", targetNamespace = "http://asdf.com")
+        GetTrucks getTrucks
+    );
+}
@@ -0,0 +1,38 @@
+package misc.a
+
+import java.util.concurrent.ExecutionException
+import kotlin.coroutines.resume
+import kotlin.coroutines.resumeWithException
+import kotlinx.coroutines.suspendCancellableCoroutine
+import kotlinx.coroutines.withContext
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+ class CarsAsyncServiceKt(val portType: CarsAsyncService) {
+    suspend fun getCars(
+		getCars: GetCarsKt,
+	): GetCarsResponseKt = suspendCancellableCoroutine { continuation ->
+        val future = portType.getCarsAsync(
+		getCars.toJava(),
+	) { response ->
+            try {
+                continuation.resume(GetCarsResponseKt.fromJava(response.get()))
+            } catch (e: ExecutionException) {
+                continuation.resumeWithException(e.cause ?: e)
+            } catch (e: Throwable) {
+                continuation.resumeWithException(e)
+            }
+        }
+        continuation.invokeOnCancellation { future.cancel(true) }
+    }
+		
+    suspend fun getTrucks(
+		getTrucks: GetTrucksKt,
+	): GetTrucksResponseKt = GetTrucksResponseKt.fromJava(withContext(kotlinx.coroutines.Dispatchers.IO) {
+        portType.getTrucks(
+		getTrucks.toJava(),
+	)
+    })
+		
+}
@@ -0,0 +1,22 @@
+package misc.a;
+
+import java.io.IOException;
+import javax.jws.WebParam;
+
+public interface CarsFaultService {
+
+    public GetCarResponse getCar(
+        @WebParam(partName = "GetCar", name = "GetCar", targetNamespace = "http://asdf.com")
+        GetCar getCar
+    ) throws CarNotFoundFault;
+
+    public void deleteCar(
+        @WebParam(partName = "DeleteCar", name = "DeleteCar", targetNamespace = "http://asdf.com")
+        DeleteCar deleteCar
+    ) throws CarNotFoundFault, CarLockedFault;
+
+    public String getCarName(
+        @WebParam(partName = "CarId", name = "CarId", targetNamespace = "http://asdf.com")
+        String carId
+    ) throws IOException;
+}
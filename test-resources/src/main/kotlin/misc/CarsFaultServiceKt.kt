@@ -0,0 +1,60 @@
+package misc.a
+
+import misc.OperationResult
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+ class CarsFaultServiceKt(val portType: CarsFaultService) {
+
+    private inline fun <S, reified F : Exception> result(block: () -> S): OperationResult<S, F> = try {
+        OperationResult.Success(block())
+    } catch (e: Exception) {
+        OperationResult.Fault(fault(e) as? F ?: throw e)
+    }
+
+    private inline fun <S, F : Exception> result(fault: (Exception) -> F?, block: () -> S): OperationResult<S, F> = try {
+        OperationResult.Success(block())
+    } catch (e: Exception) {
+        OperationResult.Fault(fault(e) ?: throw e)
+    }
+
+    private fun fault(e: Throwable): Exception? = when (e) {
+        is CarNotFoundFault -> CarNotFoundFaultKt.fromJava(e)
+        is CarNotFoundFaultKt -> e
+        is CarLockedFault -> CarLockedFaultKt.fromJava(e)
+        is CarLockedFaultKt -> e
+        else -> null
+    }
+
+    fun getCar(
+		getCar: GetCarKt,
+	): OperationResult<GetCarResponseKt, CarNotFoundFaultKt> = result<GetCarResponseKt, CarNotFoundFaultKt> { GetCarResponseKt.fromJava(portType.getCar(
+		getCar.toJava(),
+	)) }
+		
+
+    sealed class DeleteCarFault(cause: Exception) : Exception(cause.message, cause) {
+        class CarNotFoundFault(val fault: CarNotFoundFaultKt) : DeleteCarFault(fault)
+        class CarLockedFault(val fault: CarLockedFaultKt) : DeleteCarFault(fault)
+    }
+
+    private fun deleteCarFault(e: Exception): DeleteCarFault? = when (val fault = fault(e)) {
+        is CarNotFoundFaultKt -> DeleteCarFault.CarNotFoundFault(fault)
+        is CarLockedFaultKt -> DeleteCarFault.CarLockedFault(fault)
+        else -> null
+    }
+
+    fun deleteCar(
+		deleteCar: DeleteCarKt,
+	): OperationResult<Unit, DeleteCarFault> = result(this::deleteCarFault) { portType.deleteCar(
+		deleteCar.toJava(),
+	) }
+		
+    fun getCarName(
+		carId: String,
+	) = portType.getCarName(
+		carId,
+	)
+		
+}
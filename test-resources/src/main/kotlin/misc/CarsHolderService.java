@@ -0,0 +1,25 @@
+package misc.a;
+
+import javax.jws.WebParam;
+import javax.xml.ws.Holder;
+
+public interface CarsHolderService {
+
+    public GetCarsResponse getCars(
+        @WebParam(partName = "GetCars", name = "GetCars", targetNamespace = "http://asdf.com")
+        GetCars getCars,
+        @WebParam(partName = "AuthHeader", name = "AuthHeader", targetNamespace = "http://asdf.com", header = true)
+        AuthHeader authHeader,
+        @WebParam(partName = "Status", name = "Status", targetNamespace = "http://asdf.com", mode = WebParam.Mode.OUT)
+        Holder<String> status,
+        @WebParam(partName = "Paging", name = "Paging", targetNamespace = "http://asdf.com", mode = WebParam.Mode.INOUT)
+        javax.xml.ws.Holder<Paging> paging
+    );
+
+    public void deleteCar(
+        @WebParam(partName = "DeleteCar", name = "DeleteCar", targetNamespace = "http://asdf.com")
+        DeleteCar deleteCar,
+        @WebParam(partName = "Receipt", name = "Receipt", targetNamespace = "http://asdf.com", mode = WebParam.Mode.OUT)
+        Holder<Receipt> receipt
+    );
+}
@@ -0,0 +1,58 @@
+package misc.a
+
+import javax.xml.ws.Holder
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+ class CarsHolderServiceKt(val portType: CarsHolderService) {
+
+    data class GetCarsHeaders(
+        val authHeader: AuthHeaderKt? = null,
+    )
+
+    data class GetCarsResult(
+        val returnValue: GetCarsResponseKt,
+        val status: String?,
+        val paging: PagingKt?,
+    )
+
+    fun getCars(
+		getCars: GetCarsKt,
+		paging: PagingKt,
+		headers: GetCarsHeaders = GetCarsHeaders(),
+	): GetCarsResult {
+        val statusHolder = Holder<String>()
+        val pagingHolder = Holder(paging.toJava())
+        val returnValue = portType.getCars(
+		getCars.toJava(),
+		headers.authHeader?.toJava(),
+		statusHolder,
+		pagingHolder,
+	)
+        return GetCarsResult(
+            returnValue = GetCarsResponseKt.fromJava(returnValue),
+            status = statusHolder.value,
+            paging = pagingHolder.value?.let { PagingKt.fromJava(it) },
+        )
+    }
+		
+
+    data class DeleteCarResult(
+        val receipt: ReceiptKt?,
+    )
+
+    fun deleteCar(
+		deleteCar: DeleteCarKt,
+	): DeleteCarResult {
+        val receiptHolder = Holder<Receipt>()
+        portType.deleteCar(
+		deleteCar.toJava(),
+		receiptHolder,
+	)
+        return DeleteCarResult(
+            receipt = receiptHolder.value?.let { ReceiptKt.fromJava(it) },
+        )
+    }
+		
+}
@@ -0,0 +1,25 @@
+package misc.a;
+
+import java.util.List;
+import javax.jws.Oneway;
+import javax.jws.WebParam;
+
+public interface CarsReturnsService {
+
+    @Oneway
+    public void registerCar(
+        @WebParam(partName = "Car", name = "Car", targetNamespace = "http://asdf.com")
+        Car car
+    );
+
+    public int countCars();
+
+    public String getCarName(
+        @WebParam(partName = "CarId", name = "CarId", targetNamespace = "http://asdf.com")
+        String carId
+    );
+
+    public List<Car> listCars();
+
+    public List<String> listCarNames();
+}
@@ -0,0 +1,32 @@
+package misc.a
+
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+ class CarsReturnsServiceKt(val portType: CarsReturnsService) {
+    fun registerCar(
+		car: CarKt,
+	) = portType.registerCar(
+		car.toJava(),
+	)
+		
+    fun countCars(
+	) = portType.countCars(
+	)
+		
+    fun getCarName(
+		carId: String,
+	) = portType.getCarName(
+		carId,
+	)
+		
+    fun listCars(
+	) = portType.listCars(
+	).orEmpty().map { CarKt.fromJava(it) }
+		
+    fun listCarNames(
+	) = portType.listCarNames(
+	).orEmpty()
+		
+}
@@ -1,7 +1,15 @@
 package misc.a;
 
+/**
+ * This class was generated by Apache CXF 3.5.5
+ * Generated source version: 3.5.5
+ *
+ */
 public interface CarsService {
 
+    /**
+     * Finds the cars of a brand
+     */
     public GetCarsResponse getCars(
 
         @WebParam(partName = "SoapHeader", name = "AutHeader", targetNamespace = "http://asdf.com", header = true)
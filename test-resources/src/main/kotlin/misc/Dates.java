@@ -0,0 +1,25 @@
+package misc;
+
+import java.util.List;
+import javax.xml.bind.JAXBElement;
+import javax.xml.bind.annotation.XmlElement;
+import javax.xml.bind.annotation.XmlElementRef;
+import javax.xml.bind.annotation.XmlSchemaType;
+import javax.xml.datatype.XMLGregorianCalendar;
+
+public class Dates {
+    @XmlElement(name = "BirthDate", required = true)
+    @XmlSchemaType(name = "date")
+    protected XMLGregorianCalendar birthDate;
+    @XmlElement(name = "Registered")
+    @XmlSchemaType(name = "dateTime")
+    protected XMLGregorianCalendar registered;
+    @XmlElement(name = "ServiceDates")
+    @XmlSchemaType(name = "date")
+    protected List<XMLGregorianCalendar> serviceDates;
+    @XmlElementRef(name = "Sold", type = JAXBElement.class, required = false)
+    protected JAXBElement<XMLGregorianCalendar> sold;
+    @XmlElement(name = "ModelYear")
+    @XmlSchemaType(name = "gYear")
+    protected XMLGregorianCalendar modelYear;
+}
@@ -0,0 +1,42 @@
+package misc
+
+import java.time.LocalDate
+import java.time.OffsetDateTime
+import javax.xml.datatype.XMLGregorianCalendar
+
+/**
+ * This file is GENERATED. Please don't change
+ */
+@Suppress("unused", "useless_cast")
+data class DatesKt(
+    val birthDate: LocalDate,
+    val registered: OffsetDateTime? = null,
+    val serviceDates: List<LocalDate> = emptyList(),
+    val sold: OffsetDateTime? = null,
+    val modelYear: XMLGregorianCalendar? = null,
+) {
+
+    fun toJava(): Dates = Dates().also {
+        it.birthDate = datatypeFactory.newXMLGregorianCalendar(birthDate.toString())
+        it.registered = registered?.let { elem -> datatypeFactory.newXMLGregorianCalendar(java.time.format.DateTimeFormatter.ISO_OFFSET_DATE_TIME.format(elem)) }
+        it.serviceDates = serviceDates.map { elem -> datatypeFactory.newXMLGregorianCalendar(elem.toString()) }
+        it.sold = sold?.let { elem -> DatesKt.factory.createDatesSold(datatypeFactory.newXMLGregorianCalendar(java.time.format.DateTimeFormatter.ISO_OFFSET_DATE_TIME.format(elem))) }
+        it.modelYear = modelYear
+    }
+
+    companion object {
+        internal val factory = ObjectFactory()
+
+        fun fromJava(javaClass: Dates): DatesKt = DatesKt(
+            birthDate = javaClass.birthDate.let { LocalDate.of(it.year, it.month, it.day) },
+            registered = javaClass.registered?.let { it.toGregorianCalendar().toZonedDateTime().toOffsetDateTime() },
+            serviceDates = javaClass.serviceDates?.map { LocalDate.of(it.year, it.month, it.day) } ?: emptyList(),
+            sold = javaClass.sold?.value?.let { it.toGregorianCalendar().toZonedDateTime().toOffsetDateTime() },
+            modelYear = javaClass.modelYear,
+        )
+
+    }
+}
+
+/** Creates the calendars of the Java classes. Looking the factory up is slow, so it is done once */
+private val datatypeFactory = javax.xml.datatype.DatatypeFactory.newInstance()
\ No newline at end of file
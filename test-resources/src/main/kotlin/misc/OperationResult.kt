@@ -0,0 +1,11 @@
+package misc
+
+/**
+ * The value of a service method, or the SOAP fault it declares that the service returned
+ *
+ * This file is GENERATED. Please don't change
+ */
+sealed class OperationResult<out S, out F : Exception> {
+    data class Success<S>(val value: S) : OperationResult<S, Nothing>()
+    data class Fault<F : Exception>(val fault: F) : OperationResult<Nothing, F>()
+}